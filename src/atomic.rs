@@ -0,0 +1,351 @@
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{marker::PhantomData, ptr, sync::atomic::{AtomicBool, AtomicPtr, AtomicU8, AtomicUsize, Ordering}};
+use futures_util::task::AtomicWaker;
+
+/// `AtomicArc` holds an `Arc` that can be loaded and replaced from many threads without locks.
+///
+/// Writers publish with a single compare-and-swap on `value` and retry on conflict.
+/// Readers protect the pointer they load with a hazard pointer before taking a reference to it, and writers retire the pointers they replace instead of releasing them,
+/// releasing each only once no hazard pointer refers to it, so neither readers nor writers ever wait for one another.
+///
+/// While a `Txn` commits, `value` may hold a tagged pointer to a `Claim` instead of a value. Any thread that meets an undecided claim while writing aborts it, and settles it in place of the committing thread, rather than waiting for it.
+pub(crate) struct AtomicArc<T> {
+    value: AtomicUsize,
+    hazards: AtomicPtr<Hazard>,
+    retired: AtomicPtr<Retired>,
+    phantom: PhantomData<Arc<T>>,
+}
+
+// Marks a `value` holding a `Claim` rather than a value. Both are `Arc` payloads and so word aligned, leaving the low bit free.
+const CLAIM: usize = 1;
+
+impl <T> AtomicArc<T> {
+    pub(crate) fn new(value: Arc<T>) -> Self {
+        Self {
+            value: AtomicUsize::new(Arc::into_raw(value) as usize),
+            hazards: AtomicPtr::new(ptr::null_mut()),
+            retired: AtomicPtr::new(ptr::null_mut()),
+            phantom: PhantomData,
+        }
+    }
+
+    pub(crate) fn load(&self) -> Arc<T> {
+        let hazard = self.hazard();
+        let word = hazard.protect(&self.value);
+        // SAFETY: `word` is protected by `hazard`.
+        unsafe {Self::resolve(word)}
+    }
+
+    pub(crate) fn swap(&self, new: Arc<T>) -> Arc<T> {
+        let mut current = self.load();
+        loop {
+            match self.compare_and_swap(&current, new.clone()) {
+                Ok(replaced) => return replaced,
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    pub(crate) fn compare_and_swap(&self, current: &Arc<T>, new: Arc<T>) -> Result<Arc<T>, Arc<T>> {
+        let hazard = self.hazard();
+        let new = Arc::into_raw(new) as usize;
+        loop {
+            let word = hazard.protect(&self.value);
+            if word & CLAIM != 0 {
+                self.settle(word);
+            }
+            else if word != Arc::as_ptr(current) as usize {
+                // SAFETY: `new` was never published, so this is still its only reference.
+                drop(unsafe {Arc::from_raw(new as *const T)});
+                // SAFETY: `word` is protected by `hazard`.
+                return Err(unsafe {Self::resolve(word)});
+            }
+            else if self.value.compare_exchange(word, new, Ordering::SeqCst, Ordering::SeqCst).is_ok() {
+                // SAFETY: `word` is protected by `hazard`.
+                let replaced = unsafe {Self::resolve(word)};
+                drop(hazard);
+                self.retire(word);
+                return Ok(replaced);
+            }
+        }
+    }
+
+    pub(crate) fn claim(&self, status: &Arc<Status>, read: Option<&Arc<T>>, write: Option<&Arc<T>>) -> bool {
+        let hazard = self.hazard();
+        loop {
+            let word = hazard.protect(&self.value);
+            if word & CLAIM != 0 {
+                self.settle(word);
+                continue;
+            }
+            if let Some(read) = read {
+                if word != Arc::as_ptr(read) as usize {
+                    return false;
+                }
+            }
+            // SAFETY: `word` is protected by `hazard`.
+            let old = unsafe {Self::resolve(word)};
+            let new = write.cloned().unwrap_or_else(|| old.clone());
+            let claim = Arc::into_raw(Arc::new(Claim {status: status.clone(), old, new})) as usize | CLAIM;
+            if self.value.compare_exchange(word, claim, Ordering::SeqCst, Ordering::SeqCst).is_ok() {
+                drop(hazard);
+                self.retire(word);
+                return true;
+            }
+            // SAFETY: `claim` was never published, so this is still its only reference.
+            drop(unsafe {Arc::from_raw((claim & !CLAIM) as *const Claim<T>)});
+        }
+    }
+
+    pub(crate) fn unclaim(&self, status: &Arc<Status>) {
+        let hazard = self.hazard();
+        let word = hazard.protect(&self.value);
+        // SAFETY: `word` is protected by `hazard`.
+        if word & CLAIM != 0 && Arc::ptr_eq(&unsafe {&*((word & !CLAIM) as *const Claim<T>)}.status, status) {
+            self.settle(word);
+        }
+    }
+
+    // Replace a protected claim with the value it decides on, aborting its transaction first if still undecided.
+    fn settle(&self, word: usize) {
+        // SAFETY: the caller protects `word`.
+        let claim = unsafe {&*((word & !CLAIM) as *const Claim<T>)};
+        claim.status.abort();
+        let value = Arc::into_raw(claim.value().clone()) as usize;
+        if self.value.compare_exchange(word, value, Ordering::SeqCst, Ordering::SeqCst).is_ok() {
+            self.retire(word);
+        }
+        else {
+            // SAFETY: `value` was never published, so this is still its only reference.
+            drop(unsafe {Arc::from_raw(value as *const T)});
+        }
+    }
+
+    // SAFETY: `word` must be protected, or otherwise kept from being released.
+    unsafe fn resolve(word: usize) -> Arc<T> {
+        if word & CLAIM != 0 {
+            (*((word & !CLAIM) as *const Claim<T>)).value().clone()
+        }
+        else {
+            Arc::increment_strong_count(word as *const T);
+            Arc::from_raw(word as *const T)
+        }
+    }
+
+    // SAFETY: `word` must have been published and must come from `value` or `retired`.
+    unsafe fn release(word: usize) {
+        if word & CLAIM != 0 {
+            drop(Arc::from_raw((word & !CLAIM) as *const Claim<T>));
+        }
+        else {
+            drop(Arc::from_raw(word as *const T));
+        }
+    }
+
+    fn hazard(&self) -> HazardGuard<'_> {
+        let mut hazard = self.hazards.load(Ordering::Acquire);
+        while !hazard.is_null() {
+            // SAFETY: hazard records are only freed when the cell is dropped.
+            let record = unsafe {&*hazard};
+            if record.active.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed).is_ok() {
+                return HazardGuard {record};
+            }
+            hazard = record.next;
+        }
+        let record = Box::into_raw(Box::new(Hazard {active: AtomicBool::new(true), word: AtomicUsize::new(0), next: ptr::null_mut()}));
+        let mut head = self.hazards.load(Ordering::Relaxed);
+        loop {
+            // SAFETY: `record` is not yet shared.
+            unsafe {(*record).next = head};
+            match self.hazards.compare_exchange_weak(head, record, Ordering::Release, Ordering::Relaxed) {
+                // SAFETY: hazard records are only freed when the cell is dropped.
+                Ok(_) => return HazardGuard {record: unsafe {&*record}},
+                Err(actual) => head = actual,
+            }
+        }
+    }
+
+    // Defer releasing an unpublished word, then release every retired word that no hazard pointer refers to.
+    fn retire(&self, word: usize) {
+        let mut pending = Box::into_raw(Box::new(Retired {word, next: self.retired.swap(ptr::null_mut(), Ordering::SeqCst)}));
+        let mut kept: Vec<*mut Retired> = Vec::new();
+        while !pending.is_null() {
+            // SAFETY: the swap above took sole ownership of the retired list.
+            let retired = unsafe {Box::from_raw(pending)};
+            pending = retired.next;
+            if self.is_protected(retired.word) {
+                kept.push(Box::into_raw(retired));
+            }
+            else {
+                // SAFETY: `retired.word` is unpublished and no hazard pointer refers to it.
+                unsafe {Self::release(retired.word)};
+            }
+        }
+        for retired in kept {
+            let mut head = self.retired.load(Ordering::Relaxed);
+            loop {
+                // SAFETY: `retired` is not shared until pushed.
+                unsafe {(*retired).next = head};
+                match self.retired.compare_exchange_weak(head, retired, Ordering::Release, Ordering::Relaxed) {
+                    Ok(_) => break,
+                    Err(actual) => head = actual,
+                }
+            }
+        }
+    }
+
+    fn is_protected(&self, word: usize) -> bool {
+        let mut hazard = self.hazards.load(Ordering::Acquire);
+        while !hazard.is_null() {
+            // SAFETY: hazard records are only freed when the cell is dropped.
+            let record = unsafe {&*hazard};
+            if record.word.load(Ordering::SeqCst) == word {
+                return true;
+            }
+            hazard = record.next;
+        }
+        false
+    }
+}
+
+impl <T> Drop for AtomicArc<T> {
+    fn drop(&mut self) {
+        // SAFETY: `&mut self` excludes every other thread, and the published word owns one reference.
+        unsafe {Self::release(*self.value.get_mut())};
+        let mut retired = *self.retired.get_mut();
+        while !retired.is_null() {
+            // SAFETY: `&mut self` excludes every other thread, so no hazard pointer is held.
+            let node = unsafe {
+                let node = Box::from_raw(retired);
+                Self::release(node.word);
+                node
+            };
+            retired = node.next;
+        }
+        let mut hazard = *self.hazards.get_mut();
+        while !hazard.is_null() {
+            // SAFETY: `&mut self` excludes every other thread.
+            let record = unsafe {Box::from_raw(hazard)};
+            hazard = record.next;
+        }
+    }
+}
+
+unsafe impl <T: Send + Sync> Send for AtomicArc<T> {}
+unsafe impl <T: Send + Sync> Sync for AtomicArc<T> {}
+
+/// `Status` decides, exactly once, whether a committing `Txn` takes effect. Every `Claim` the commit installs shares it.
+pub(crate) struct Status(AtomicU8);
+
+const UNDECIDED: u8 = 0;
+const COMMITTED: u8 = 1;
+const ABORTED: u8 = 2;
+
+impl Status {
+    pub(crate) fn new() -> Self {
+        Self(AtomicU8::new(UNDECIDED))
+    }
+
+    pub(crate) fn commit(&self) -> bool {
+        self.0.compare_exchange(UNDECIDED, COMMITTED, Ordering::SeqCst, Ordering::SeqCst).is_ok()
+    }
+
+    fn abort(&self) {
+        let _ = self.0.compare_exchange(UNDECIDED, ABORTED, Ordering::SeqCst, Ordering::SeqCst);
+    }
+
+    fn committed(&self) -> bool {
+        self.0.load(Ordering::SeqCst) == COMMITTED
+    }
+}
+
+// A committing transaction's stake in one cell: the cell reads as `new` once `status` commits, and as `old` until then or if it aborts.
+struct Claim<T> {
+    status: Arc<Status>,
+    old: Arc<T>,
+    new: Arc<T>,
+}
+
+impl <T> Claim<T> {
+    fn value(&self) -> &Arc<T> {
+        if self.status.committed() {&self.new} else {&self.old}
+    }
+}
+
+struct Hazard {
+    active: AtomicBool,
+    word: AtomicUsize,
+    next: *mut Hazard,
+}
+
+struct HazardGuard<'a> {
+    record: &'a Hazard,
+}
+
+impl <'a> HazardGuard<'a> {
+    // Load `value` and publish it as hazardous, retrying until it is still current, so it cannot be released while guarded.
+    fn protect(&self, value: &AtomicUsize) -> usize {
+        let mut word = value.load(Ordering::SeqCst);
+        loop {
+            self.record.word.store(word, Ordering::SeqCst);
+            let actual = value.load(Ordering::SeqCst);
+            if actual == word {
+                return word;
+            }
+            word = actual;
+        }
+    }
+}
+
+impl <'a> Drop for HazardGuard<'a> {
+    fn drop(&mut self) {
+        self.record.word.store(0, Ordering::SeqCst);
+        self.record.active.store(false, Ordering::Release);
+    }
+}
+
+struct Retired {
+    word: usize,
+    next: *mut Retired,
+}
+
+/// `Watchers` wakes every registered task whenever the value it accompanies is replaced.
+pub(crate) struct Watchers {
+    wakers: AtomicArc<Vec<Arc<AtomicWaker>>>,
+}
+
+impl Watchers {
+    pub(crate) fn new() -> Self {
+        Self {
+            wakers: AtomicArc::new(Arc::new(Vec::new()))
+        }
+    }
+
+    pub(crate) fn register(&self) -> Arc<AtomicWaker> {
+        let waker = Arc::new(AtomicWaker::new());
+        self.update(|wakers| wakers.push(waker.clone()));
+        waker
+    }
+
+    pub(crate) fn unregister(&self, waker: &Arc<AtomicWaker>) {
+        self.update(|wakers| wakers.retain(|w| !Arc::ptr_eq(w, waker)));
+    }
+
+    pub(crate) fn notify(&self) {
+        for waker in self.wakers.load().iter() {
+            waker.wake();
+        }
+    }
+
+    fn update<Op: Fn(&mut Vec<Arc<AtomicWaker>>)>(&self, op: Op) {
+        let mut current = self.wakers.load();
+        loop {
+            let mut updated = (*current).clone();
+            op(&mut updated);
+            match self.wakers.compare_and_swap(&current, Arc::new(updated)) {
+                Ok(_) => return,
+                Err(actual) => current = actual,
+            }
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use crate::{atomic::{AtomicArc, Watchers}, results::MapChange, traits::*, HashTrieMap};
+use alloc::{sync::Arc, vec::Vec};
+use core::{pin::Pin, task::{Context, Poll}};
+use futures_core::Stream;
+use futures_util::task::AtomicWaker;
+
+/// `AtomicHashTrieMap` holds the current version of a `HashTrieMap` so that many threads can share it without a `Mutex`.
+///
+/// Readers take consistent snapshots with `load` without ever blocking. Writers build a new version from a snapshot, reusing all untouched subtrees, and publish it with `compare_and_swap`, or let `rcu` retry for them when another writer got there first.
+/// Publishing is a single compare-and-swap, with replaced maps released once no reader part way through `load` can still be taking a reference to them, so neither readers nor writers ever wait for one another.
+#[must_use]
+pub struct AtomicHashTrieMap <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V> = ()> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    pub(crate) map: AtomicArc<HashTrieMap<H, F, K, V, M, A>>,
+    pub(crate) watchers: Watchers,
+}
+
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> AtomicHashTrieMap<H, F, K, V, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    /// Get a new AtomicHashTrieMap holding `map`.
+    pub fn new(map: HashTrieMap<H, F, K, V, M, A>) -> Self {
+        Self {
+            map: AtomicArc::new(Arc::new(map)),
+            watchers: Watchers::new(),
+        }
+    }
+
+    /// Get a snapshot of the current map. Later writes are not reflected in it.
+    #[must_use]
+    pub fn load(&self) -> Arc<HashTrieMap<H, F, K, V, M, A>> {
+        self.map.load()
+    }
+
+    /// Replace the current map unconditionally. Returns the map that was replaced.
+    pub fn store(&self, map: HashTrieMap<H, F, K, V, M, A>) -> Arc<HashTrieMap<H, F, K, V, M, A>> {
+        let replaced = self.map.swap(Arc::new(map));
+        self.watchers.notify();
+        replaced
+    }
+
+    /// Replace the current map with `new` only if it is still the snapshot `current`, compared by identity. Returns the replaced map on success or the actual current map on failure.
+    #[allow(clippy::type_complexity)]
+    pub fn compare_and_swap(&self, current: &Arc<HashTrieMap<H, F, K, V, M, A>>, new: HashTrieMap<H, F, K, V, M, A>) -> Result<Arc<HashTrieMap<H, F, K, V, M, A>>, Arc<HashTrieMap<H, F, K, V, M, A>>> {
+        let replaced = self.map.compare_and_swap(current, Arc::new(new))?;
+        self.watchers.notify();
+        Ok(replaced)
+    }
+
+    /// Read, copy and update: apply `op` to the current map and publish the result, retrying with a fresh snapshot whenever another writer intervened. Returns the map that was replaced.
+    pub fn rcu<Op: FnMut(&HashTrieMap<H, F, K, V, M, A>) -> HashTrieMap<H, F, K, V, M, A>>(&self, mut op: Op) -> Arc<HashTrieMap<H, F, K, V, M, A>> {
+        let mut current = self.load();
+        loop {
+            match self.compare_and_swap(&current, op(&current)) {
+                Ok(replaced) => return replaced,
+                Err(actual) => current = actual,
+            }
+        }
+    }
+}
+
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value + PartialEq, M: HasherBv<H, K>, A: Measure<K, V>> AtomicHashTrieMap<H, F, K, V, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    /// Get a `Stream` yielding new versions of the map, each with the changes since the previous version yielded.
+    /// 
+    /// The stream does not queue versions: each poll compares the current map with the last one yielded.
+    /// Versions published between polls are therefore merged into one item carrying their net changes, and versions that leave the entries as they were yield nothing.
+    pub fn subscribe(self: &Arc<Self>) -> MapVersions<H, F, K, V, M, A> {
+        MapVersions {
+            waker: self.watchers.register(),
+            last: self.load(),
+            cell: self.clone(),
+        }
+    }
+}
+
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> Default for AtomicHashTrieMap<H, F, K, V, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    fn default() -> Self {
+        Self::new(HashTrieMap::default())
+    }
+}
+
+/// `MapVersion` is one version of a map yielded by `MapVersions`.
+#[must_use]
+pub struct MapVersion <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V> = ()> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    /// The new version of the map.
+    pub map: Arc<HashTrieMap<H, F, K, V, M, A>>,
+    /// The changes since the previous version yielded.
+    pub changes: Vec<MapChange<K, V>>,
+}
+
+/// `MapVersions` is the `Stream` returned by `AtomicHashTrieMap::subscribe`.
+#[must_use]
+pub struct MapVersions <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V> = ()> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    cell: Arc<AtomicHashTrieMap<H, F, K, V, M, A>>,
+    last: Arc<HashTrieMap<H, F, K, V, M, A>>,
+    waker: Arc<AtomicWaker>,
+}
+
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value + PartialEq, M: HasherBv<H, K>, A: Measure<K, V>> Stream for MapVersions<H, F, K, V, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    type Item = MapVersion<H, F, K, V, M, A>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        this.waker.register(cx.waker());
+        let map = this.cell.load();
+        let changes = this.last.changes(&map);
+        this.last = map.clone();
+        if changes.is_empty() {
+            Poll::Pending
+        }
+        else {
+            Poll::Ready(Some(MapVersion {map, changes}))
+        }
+    }
+}
+
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> Drop for MapVersions<H, F, K, V, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    fn drop(&mut self) {
+        self.cell.watchers.unregister(&self.waker);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{results::MapChange, *};
+    use alloc::sync::Arc;
+
+    #[test]
+    fn atomic_map_rcu() {
+        let cell = Arc::new(AtomicHashTrieMap::<u64, u32, i32, i32, fnv::FnvHasher>::default());
+        let snapshot = cell.load();
+
+        let threads: Vec<_> = (0..4).map(|t| {
+            let cell = cell.clone();
+            std::thread::spawn(move || {
+                for i in 0..250 {
+                    cell.rcu(|map| map.insert(t * 250 + i, i, false).unwrap().0);
+                    assert!(cell.load().size() > i as usize);
+                }
+            })
+        }).collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        assert_eq!(snapshot.size(), 0);
+        let map = cell.load();
+        assert_eq!(map.size(), 1000);
+        for t in 0..4 {
+            for i in 0..250 {
+                assert_eq!(*map.find(&(t * 250 + i)).unwrap().1, i);
+            }
+        }
+
+        match cell.compare_and_swap(&snapshot, HashTrieMap::new()) {
+            Ok(_) => panic!(),
+            Err(actual) => assert!(Arc::ptr_eq(&actual, &map)),
+        }
+        match cell.compare_and_swap(&map, HashTrieMap::new()) {
+            Ok(replaced) => assert!(Arc::ptr_eq(&replaced, &map)),
+            Err(_) => panic!(),
+        }
+        assert_eq!(cell.load().size(), 0);
+    }
+
+    #[test]
+    fn atomic_map_busy_readers() {
+        use core::sync::atomic::{AtomicBool, Ordering};
+
+        let cell = Arc::new(AtomicHashTrieMap::<u64, u32, i32, i32, fnv::FnvHasher>::default());
+        let done = Arc::new(AtomicBool::new(false));
+        let readers: Vec<_> = (0..4).map(|_| {
+            let (cell, done) = (cell.clone(), done.clone());
+            std::thread::spawn(move || {
+                let mut size = 0;
+                while !done.load(Ordering::SeqCst) {
+                    let map = cell.load();
+                    assert!(map.size() >= size);
+                    size = map.size();
+                }
+            })
+        }).collect();
+
+        for i in 0..1000 {
+            cell.store(cell.load().insert(i, i, false).unwrap().0);
+        }
+        done.store(true, Ordering::SeqCst);
+        for reader in readers {
+            reader.join().unwrap();
+        }
+        assert_eq!(cell.load().size(), 1000);
+    }
+
+    #[test]
+    fn atomic_map_subscribe() {
+        use futures_util::StreamExt;
+
+        let cell = Arc::new(AtomicHashTrieMap::<u64, u32, i32, i32, fnv::FnvHasher>::default());
+        let mut versions = cell.subscribe();
+
+        let writer = {
+            let cell = cell.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(std::time::Duration::from_millis(10));
+                cell.rcu(|map| map.insert(1, 1, false).unwrap().0);
+            })
+        };
+        let version = futures_executor::block_on(versions.next()).unwrap();
+        writer.join().unwrap();
+        assert_eq!(version.changes, vec![MapChange::Added(1, 1)]);
+        assert_eq!(version.map.size(), 1);
+
+        cell.rcu(|map| map.insert(2, 2, false).unwrap().0);
+        cell.rcu(|map| map.insert(1, 10, true).unwrap().0);
+        let mut changes = futures_executor::block_on(versions.next()).unwrap().changes;
+        changes.sort_by_key(|change| match change {
+            MapChange::Added(k, _) | MapChange::Removed(k, _) | MapChange::Changed(k, _, _) => *k,
+        });
+        assert_eq!(changes, vec![MapChange::Changed(1, 1, 10), MapChange::Added(2, 2)]);
+
+        let mut txn = Txn::new();
+        let map = txn.read(&cell);
+        txn.write(&cell, map.remove(&2).unwrap().0);
+        assert!(txn.commit());
+        assert_eq!(futures_executor::block_on(versions.next()).unwrap().changes, vec![MapChange::Removed(2, 2)]);
+    }
+
+    #[test]
+    fn atomic_map_subscribe_merges_versions() {
+        use futures_util::{FutureExt, StreamExt};
+
+        let cell = Arc::new(AtomicHashTrieMap::<u64, u32, i32, i32, fnv::FnvHasher>::default());
+        let mut versions = cell.subscribe();
+
+        cell.rcu(|map| map.insert(1, 1, false).unwrap().0);
+        cell.rcu(|map| map.insert(2, 2, false).unwrap().0);
+        cell.rcu(|map| map.remove(&1).unwrap().0);
+        let version = versions.next().now_or_never().unwrap().unwrap();
+        assert_eq!(version.changes, vec![MapChange::Added(2, 2)]);
+        assert!(Arc::ptr_eq(&version.map, &cell.load()));
+        assert!(versions.next().now_or_never().is_none());
+
+        cell.store((*cell.load()).clone());
+        cell.rcu(|map| map.insert(3, 3, false).unwrap().0);
+        cell.rcu(|map| map.remove(&3).unwrap().0);
+        assert!(versions.next().now_or_never().is_none());
+
+        cell.rcu(|map| map.insert(2, 20, true).unwrap().0);
+        assert_eq!(versions.next().now_or_never().unwrap().unwrap().changes, vec![MapChange::Changed(2, 2, 20)]);
+    }
+}
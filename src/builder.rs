@@ -0,0 +1,71 @@
+use crate::{traits::*, HashTrieMap};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+/// `HashTrieMapBuilder` batches insertions and removals and builds the resulting `HashTrieMap` bottom up in one pass, rather than copying paths for every operation.
+///
+/// Operations apply in the order given, so a later insertion replaces an earlier one for the same key and a later removal cancels it.
+#[must_use]
+pub struct HashTrieMapBuilder <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V> = ()> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    entries: Vec<(H, K, Option<V>)>,
+    phantom: PhantomData<(F, M, A)>,
+}
+
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> HashTrieMapBuilder<H, F, K, V, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    /// Get a new, empty HashTrieMapBuilder.
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Get a new HashTrieMapBuilder starting from the entries of `map`, reusing the hash values its leaves hold.
+    pub fn from_map(map: &HashTrieMap<H, F, K, V, M, A>) -> Self {
+        Self {
+            entries: map.entries_with_hashes().into_iter().map(|(key, value, hash_value)| (hash_value, key.clone(), Some(value.clone()))).collect(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Get the number of operations batched so far.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Test whether no operations have been batched.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Batch inserting or replacing the entry for the key.
+    pub fn insert(&mut self, key: K, value: V) -> &mut Self {
+        self.entries.push((M::default().hash(&key), key, Some(value)));
+        self
+    }
+
+    /// Batch removing the entry for the key, if present.
+    pub fn remove(&mut self, key: K) -> &mut Self {
+        self.entries.push((M::default().hash(&key), key, None));
+        self
+    }
+
+    /// Discard every operation batched so far.
+    pub fn clear(&mut self) -> &mut Self {
+        self.entries.clear();
+        self
+    }
+
+    /// Build the map resulting from the batched operations.
+    pub fn build(self) -> HashTrieMap<H, F, K, V, M, A> {
+        HashTrieMap::build(self.entries)
+    }
+}
+
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> Default for HashTrieMapBuilder<H, F, K, V, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    fn default() -> Self {
+        Self::new()
+    }
+}
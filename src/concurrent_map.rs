@@ -0,0 +1,128 @@
+use crate::{atomic::AtomicArc, flag::Flag, traits::*, HashTrieMap};
+use alloc::{sync::Arc, vec::Vec};
+
+/// `ConcurrentHashTrieMap` shards a `HashTrieMap` by the first `F::log_b()` bits of each key's hash, the same bits that select a child of the root.
+///
+/// Each top-level branch is published through its own atomic slot, so writers to different branches never conflict, while `snapshot` reassembles the branches into a regular `HashTrieMap` without copying any entries.
+#[must_use]
+#[allow(clippy::type_complexity)]
+pub struct ConcurrentHashTrieMap <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V> = ()> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    branches: Vec<AtomicArc<HashTrieMap<H, F, K, V, M, A>>>,
+}
+
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> ConcurrentHashTrieMap<H, F, K, V, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    /// Get a new ConcurrentHashTrieMap holding the entries of `map`, sharing its subtrees.
+    pub fn new(map: &HashTrieMap<H, F, K, V, M, A>) -> Self {
+        Self {
+            branches: map.split_by_hash_prefix(F::log_b()).into_iter().map(|branch| AtomicArc::new(Arc::new(branch))).collect()
+        }
+    }
+
+    /// Get the total number of entries in the map. Concurrent writes to other branches may or may not be counted.
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.branches.iter().map(|branch| branch.load().size()).sum()
+    }
+
+    /// Assemble the current branches into a `HashTrieMap` in time independent of the number of entries. Each branch is read atomically, but concurrent writes to other branches may or may not be included.
+    pub fn snapshot(&self) -> HashTrieMap<H, F, K, V, M, A> {
+        let branches: Vec<_> = self.branches.iter().map(|branch| branch.load()).collect();
+        HashTrieMap::concat_disjoint_iter(branches.iter().map(|branch| branch.as_ref()))
+    }
+
+    /// Search the map for the given key and return a copy of the entry if found.
+    #[must_use]
+    pub fn find<L: Key + HashLike<K>>(&self, key: &L) -> Option<(K, V)> where K: PartialEq<L>, M: HasherBv<H, L> {
+        let hash = M::default().hash(key);
+        let branch = self.branch(&hash).load();
+        branch.find_with_hash(hash, key).ok().map(|(key, value)| (key.clone(), value.clone()))
+    }
+
+    /// Insert or replace the entry for the key, retrying only if another writer changed the same branch. Returns the replaced value, if any.
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        let hash = M::default().hash(&key);
+        let branch = self.branch(&hash);
+        let mut current = branch.load();
+        loop {
+            let prev = current.find_with_hash(hash.clone(), &key).ok().map(|(_key, value)| value.clone());
+            let inserted = current.insert_many(core::iter::once((key.clone(), value.clone())));
+            match branch.compare_and_swap(&current, Arc::new(inserted)) {
+                Ok(_) => return prev,
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    /// Remove the entry for the key, retrying only if another writer changed the same branch. Returns the removed entry, if any.
+    pub fn remove<L: Key + HashLike<K>>(&self, key: &L) -> Option<(K, V)> where K: PartialEq<L>, M: HasherBv<H, L> {
+        let hash = M::default().hash(key);
+        let branch = self.branch(&hash);
+        let mut current = branch.load();
+        loop {
+            let (removed, entry) = match current.remove_with_hash(hash.clone(), key) {
+                Ok((removed, key, value)) => (removed, (key.clone(), value.clone())),
+                Err(_) => return None,
+            };
+            match branch.compare_and_swap(&current, Arc::new(removed)) {
+                Ok(_) => return Some(entry),
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    fn branch(&self, hash: &H) -> &AtomicArc<HashTrieMap<H, F, K, V, M, A>> {
+        &self.branches[Flag::<H, F>::new(hash.clone()).index()]
+    }
+}
+
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> Default for ConcurrentHashTrieMap<H, F, K, V, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    fn default() -> Self {
+        Self::new(&HashTrieMap::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use alloc::sync::Arc;
+
+    #[test]
+    fn concurrent_map_snapshot() {
+        let mut map = DefaultHashTrieMap::<i32, i32>::new();
+        for i in 0..100 {
+            map = map.insert(i, i, false).unwrap().0;
+        }
+        let concurrent = Arc::new(ConcurrentHashTrieMap::new(&map));
+        assert_eq!(concurrent.size(), 100);
+        let before = concurrent.snapshot();
+        for i in 0..100 {
+            assert_eq!(*before.find(&i).unwrap().1, i);
+        }
+
+        let threads: Vec<_> = (0..4).map(|t| {
+            let concurrent = concurrent.clone();
+            std::thread::spawn(move || {
+                for i in 0..250 {
+                    let key = 100 + t * 250 + i;
+                    assert_eq!(concurrent.insert(key, i), None);
+                    assert_eq!(concurrent.find(&key), Some((key, i)));
+                }
+                for i in (0..100).filter(|i| i % 4 == t) {
+                    assert_eq!(concurrent.remove(&i), Some((i, i)));
+                }
+            })
+        }).collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        let after = concurrent.snapshot();
+        assert_eq!(after.size(), 1000);
+        assert_eq!(before.size(), 100);
+        for key in 100..1100 {
+            assert_eq!(*after.find(&key).unwrap().1, (key - 100) % 250);
+        }
+        assert_eq!(concurrent.insert(100, 7), Some(0));
+        assert_eq!(concurrent.remove(&0), None);
+    }
+}
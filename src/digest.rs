@@ -0,0 +1,90 @@
+use crate::traits::*;
+use core::hash::{Hash, Hasher};
+use sha2::{Digest as _, Sha256};
+
+/// `Digest` is a 256-bit Merkle hash of a set of entries.
+///
+/// Under `ContentDigest`, entries are hashed with SHA-256, entries sharing a full hash value are hashed together in sorted order,
+/// and each branch hashes the digests of its children in bit-index order, so unequal contents yield equal digests only through a SHA-256 collision.
+/// A branch with a single child takes that child's digest, so maps holding equal entries have equal digests regardless of how they were built.
+///
+/// `combine` is a bytewise exclusive or, kept only to satisfy `Monoid`; `ContentDigest` never uses it.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Digest(pub [u8; 32]);
+
+impl Monoid for Digest {
+    fn empty() -> Self {
+        Digest([0; 32])
+    }
+
+    fn combine(mut self, other: Self) -> Self {
+        for (byte, other) in self.0.iter_mut().zip(other.0.iter()) {
+            *byte ^= other;
+        }
+        self
+    }
+}
+
+/// `ContentDigest` is a `Measure` caching the Merkle `Digest` of every subtree.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ContentDigest;
+
+impl <K: Hash, V: Hash> Measure<K, V> for ContentDigest {
+    type Summary = Digest;
+
+    fn measure(key: &K, value: &V) -> Digest {
+        let mut hasher = DigestHasher(Sha256::new_with_prefix([ENTRY]));
+        key.hash(&mut hasher);
+        value.hash(&mut hasher);
+        Digest(hasher.0.finalize().into())
+    }
+
+    fn measure_collisions<I: Iterator<Item = Digest>>(summaries: I) -> Digest {
+        let mut summaries: alloc::vec::Vec<Digest> = summaries.collect();
+        if summaries.len() == 1 {
+            return summaries[0];
+        }
+        summaries.sort_unstable_by_key(|summary| summary.0);
+        let mut hasher = Sha256::new_with_prefix([COLLISIONS]);
+        for summary in &summaries {
+            hasher.update(summary.0);
+        }
+        Digest(hasher.finalize().into())
+    }
+
+    fn measure_branch<I: Iterator<Item = (usize, Digest)>>(children: I) -> Digest {
+        let mut children = children.peekable();
+        let (first_index, first) = match children.next() {
+            Some(first) => first,
+            None => return Digest::empty(),
+        };
+        if children.peek().is_none() {
+            return first;
+        }
+        let mut hasher = Sha256::new_with_prefix([BRANCH]);
+        for (index, child) in core::iter::once((first_index, first)).chain(children) {
+            hasher.update([index as u8]);
+            hasher.update(child.0);
+        }
+        Digest(hasher.finalize().into())
+    }
+}
+
+// Domain separation for entries, collision groups, and branches.
+const ENTRY: u8 = 0;
+const COLLISIONS: u8 = 1;
+const BRANCH: u8 = 2;
+
+// Feeds the `Hash` encoding of keys and values into SHA-256.
+struct DigestHasher(Sha256);
+
+impl Hasher for DigestHasher {
+    fn finish(&self) -> u64 {
+        let bytes = self.0.clone().finalize();
+        u64::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7]])
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+}
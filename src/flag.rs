@@ -1,5 +1,6 @@
 use crate::traits::*;
 use alloc::fmt::Debug;
+use core::cmp::Ordering;
 
 #[derive(Debug, Eq, PartialEq)]
 pub(crate) struct Flag <H, F> {
@@ -51,6 +52,56 @@ impl <H: Hashword, F: Flagword<H>> Flag<H, F> where <F as core::convert::TryFrom
     pub(crate) fn next(&self) -> Option<Self> {
         Self::new_at_depth(self.hash_value().clone(), self.depth + 1)
     }
+
+    #[must_use]
+    pub(crate) fn index(&self) -> usize {
+        F::try_from(H::from(self.hash_value.clone().shr(self.depth * F::log_b())).bitand(F::mask_log_b())).unwrap().as_usize()
+    }
+
+    /// Pack the next `bits` bits of the hash, starting from this depth, into a `usize` with this depth's bits lowest.
+    #[must_use]
+    pub(crate) fn prefix(&self, bits: usize) -> usize {
+        let mut prefix = 0;
+        let mut shift = 0;
+        let mut depth = self.depth;
+        while shift < bits && depth * F::log_b() < <H>::max_ones() {
+            let width = F::log_b().min(bits - shift);
+            prefix |= (Self::new_at_depth(self.hash_value(), depth).unwrap().index() & ((1 << width) - 1)) << shift;
+            shift += width;
+            depth += 1;
+        }
+        prefix
+    }
+
+    /// Test whether the next `bits` bits of `hash_value`, starting from this depth, match those of this flag.
+    #[must_use]
+    pub(crate) fn prefix_eq(&self, hash_value: &H, bits: usize) -> bool {
+        let mut bits = bits;
+        let mut depth = self.depth;
+        while bits > 0 && depth * F::log_b() < <H>::max_ones() {
+            let width = F::log_b().min(bits);
+            let mask = (1 << width) - 1;
+            if Self::new_at_depth(self.hash_value(), depth).unwrap().index() & mask != Self::new_at_depth(hash_value.clone(), depth).unwrap().index() & mask {
+                return false;
+            }
+            bits -= width;
+            depth += 1;
+        }
+        true
+    }
+
+    /// Order hash values by their paths through the trie, so that those bound for the same subtree sort together.
+    #[must_use]
+    pub(crate) fn cmp_path(left: &H, right: &H) -> Ordering {
+        let mut depth = 0;
+        while *left != *right && depth * F::log_b() < <H>::max_ones() {
+            match Self::new_at_depth(left.clone(), depth).unwrap().index().cmp(&Self::new_at_depth(right.clone(), depth).unwrap().index()) {
+                Ordering::Equal => depth += 1,
+                ordering => return ordering,
+            }
+        }
+        Ordering::Equal
+    }
 }
 
 impl <H: Hashword, F: Flagword<H>> From<H> for Flag<H, F> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
@@ -1,13 +1,12 @@
-use crate::{ParallelismStrategy, flag::*, transformations::*, node::*, results::*, traits::*};
-use alloc::{fmt::Debug};
-use core::hash::Hash;
+use crate::{ParallelismStrategy, digest::Digest, flag::*, sync::*, transformations::*, node::*, results::*, traits::*};
+use alloc::{collections::BTreeSet, fmt::Debug, vec::Vec};
+use core::{future::Future, hash::Hash};
 
-#[derive(Debug)]
-pub(crate) struct HashTrie <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> {
-    root: MNode<H, F, K, V, M>,
+pub(crate) struct HashTrie <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> {
+    root: MNode<H, F, K, V, M, A>,
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> HashTrie<H, F, K, V, M> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> HashTrie<H, F, K, V, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
     #[must_use]
     pub(crate) fn new() -> Self {
         Self {
@@ -16,7 +15,7 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> HashTrie
     }
 
     #[must_use]
-    fn singleton(mnode: MNode<H, F, K, V, M>) -> Self {
+    fn singleton(mnode: MNode<H, F, K, V, M, A>) -> Self {
         Self {
             root: mnode
         }
@@ -26,20 +25,153 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> HashTrie
         self.root.size()
     }
 
+    pub(crate) fn summary(&self) -> A::Summary {
+        self.root.summary()
+    }
+
+    pub(crate) fn measure_memory(&self, seen: &mut BTreeSet<usize>, usage: &mut MemoryUsage) {
+        self.root.measure_memory(seen, usage);
+    }
+
+    pub(crate) fn diff<'a, Same: Fn(&MNode<H, F, K, V, M, A>, &MNode<H, F, K, V, M, A>) -> bool>(&'a self, right: &'a Self, same: Same) -> Vec<MapDiff<'a, K, V>> where V: PartialEq {
+        let mut diffs = Vec::new();
+        self.root.diff(&right.root, &same, &mut diffs);
+        diffs
+    }
+
+    pub(crate) fn sync_request(&self, paths: Vec<Vec<u8>>) -> SyncRequest where A: Measure<K, V, Summary = Digest> {
+        SyncRequest {
+            paths: paths.into_iter().map(|path| SyncPath {
+                digests: self.digests_at_path(&path).into_iter().map(|(index, digest, _is_cnode)| (index, digest)).collect(),
+                path,
+            }).collect()
+        }
+    }
+
+    pub(crate) fn sync_respond(&self, request: &SyncRequest) -> SyncResponse<K, V> where A: Measure<K, V, Summary = Digest> {
+        let mut entries = Vec::new();
+        let mut descend = Vec::new();
+        for SyncPath {path, digests} in &request.paths {
+            for (index, digest, is_cnode) in self.digests_at_path(path) {
+                let mut child = path.clone();
+                child.push(index);
+                match digests.iter().find(|(i, _d)| *i == index) {
+                    Some((_i, d)) if *d == digest => {},
+                    Some(_) if is_cnode => descend.push(child),
+                    _ => entries.extend(self.entries_at_path(&child).into_iter().map(|(key, value, _hash_value)| (key.clone(), value.clone()))),
+                }
+            }
+        }
+        SyncResponse {entries, descend}
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn at_path(&self, path: &[u8]) -> Option<(&MNode<H, F, K, V, M, A>, usize)> {
+        let mut node = &self.root;
+        for (depth, index) in path.iter().enumerate() {
+            match node {
+                MNode::C(_) => node = node.child(*index as usize)?,
+                MNode::L(_) | MNode::S(_) => return Some((node, depth)),
+            }
+        }
+        Some((node, path.len()))
+    }
+
+    fn entries_at_path(&self, path: &[u8]) -> Vec<(&K, &V, H)> {
+        let mut entries = Vec::new();
+        if let Some((node, depth)) = self.at_path(path) {
+            node.entries_with_hashes(&mut entries);
+            entries.retain(|(_key, _value, hash_value)| (depth..path.len()).all(|level| Self::index_at(hash_value, level) == path[level] as usize));
+        }
+        entries
+    }
+
+    fn digests_at_path(&self, path: &[u8]) -> Vec<(u8, Digest, bool)> where A: Measure<K, V, Summary = Digest> {
+        match self.at_path(path) {
+            Some((node @ MNode::C(_), depth)) if depth == path.len() => {
+                (0..F::max_ones()).filter_map(|index| node.child(index).map(|child| (index as u8, child.summary(), matches!(child, MNode::C(_))))).collect()
+            },
+            _ => {
+                let mut summaries: Vec<(u8, Vec<Digest>)> = Vec::new();
+                for (key, value, hash_value) in self.entries_at_path(path) {
+                    let index = Self::index_at(&hash_value, path.len()) as u8;
+                    match summaries.iter_mut().find(|(i, _s)| *i == index) {
+                        Some((_i, collisions)) => collisions.push(A::measure(key, value)),
+                        None => summaries.push((index, vec!(A::measure(key, value)))),
+                    }
+                }
+                summaries.sort_by_key(|(index, _s)| *index);
+                summaries.into_iter().map(|(index, collisions)| (index, A::measure_collisions(collisions.into_iter()), false)).collect()
+            },
+        }
+    }
+
+    fn index_at(hash_value: &H, level: usize) -> usize {
+        Flag::<H, F>::new_at_depth(hash_value.clone(), level).unwrap().index()
+    }
+
     pub(crate) fn find<'a, L: Key + HashLike<K>>(&'a self, key: &L) -> Result<(&'a K, &'a V), HashTrieError> where K: PartialEq<L>, M: HasherBv<H, L>, <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
-        match self.root.find(key, Some(Flag::new(M::default().hash(key)))) {
+        self.find_at(key, Flag::new(M::default().hash(key)))
+    }
+
+    pub(crate) fn find_with_hash<'a, L: Key + HashLike<K>>(&'a self, hash: H, key: &L) -> Result<(&'a K, &'a V), HashTrieError> where K: PartialEq<L>, M: HasherBv<H, L>, <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+        debug_assert_eq!(hash, M::default().hash(key));
+        self.find_at(key, Flag::new(hash))
+    }
+
+    fn find_at<'a, L: Key + HashLike<K>>(&'a self, key: &L, flag: Flag<H, F>) -> Result<(&'a K, &'a V), HashTrieError> where K: PartialEq<L> {
+        match self.root.find(key, Some(flag)) {
             FindResult::NotFound => Err(HashTrieError::NotFound),
             FindResult::Found(key, value) => Ok((key, value))
         }
     }
 
-    pub(crate) fn insert<'a, L: Key + Into<K> + Hash + HashLike<K>, W: Into<V>>(&'a self, key: L, value: W, replace: bool) -> Result<(Self, *const K, *const V, Option<(&'a K, &'a V)>), (&'a K, &'a V)>
+    pub(crate) fn find_many<'a, L: Key + HashLike<K>>(&'a self, keys: &[L]) -> Vec<Result<(&'a K, &'a V), HashTrieError>> where K: PartialEq<L>, M: HasherBv<H, L> {
+        let mut queries: Vec<(usize, H, &L)> = keys.iter().enumerate().map(|(position, key)| (position, M::default().hash(key), key)).collect();
+        queries.sort_by(|(_lposition, lhash_value, _lkey), (_rposition, rhash_value, _rkey)| Flag::<H, F>::cmp_path(lhash_value, rhash_value));
+        let mut found = Vec::new();
+        found.resize_with(keys.len(), || None);
+        self.root.find_many(&queries, 0, &mut found);
+        found.into_iter().map(|found| found.ok_or(HashTrieError::NotFound)).collect()
+    }
+
+    pub(crate) fn apply(&self, mut entries: Vec<(H, K, Option<V>)>) -> Self {
+        entries.sort_by(|(lhash_value, _lkey, _lvalue), (rhash_value, _rkey, _rvalue)| Flag::<H, F>::cmp_path(lhash_value, rhash_value));
+        match MNode::apply(Some(&self.root), entries, 0) {
+            Some(root) => Self::singleton(root),
+            None => Self::default(),
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn insert<L: Key + Into<K> + Hash + HashLike<K>, W: Into<V>>(&self, key: L, value: W, replace: bool) -> Result<(Self, *const K, *const V, Option<(&K, &V)>), (&K, &V)>
     where
         K: HashLike<L>,
         K: PartialEq<L>,
         M: HasherBv<H, L>
     {
         let flag = Flag::from(M::default().hash(&key));
+        self.insert_at(key, value, flag, replace)
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn insert_with_hash<L: Key + Into<K> + Hash + HashLike<K>, W: Into<V>>(&self, hash: H, key: L, value: W, replace: bool) -> Result<(Self, *const K, *const V, Option<(&K, &V)>), (&K, &V)>
+    where
+        K: HashLike<L>,
+        K: PartialEq<L>,
+        M: HasherBv<H, L>
+    {
+        debug_assert_eq!(hash, M::default().hash(&key));
+        self.insert_at(key, value, Flag::from(hash), replace)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn insert_at<L: Key + Into<K> + Hash + HashLike<K>, W: Into<V>>(&self, key: L, value: W, flag: Flag<H, F>, replace: bool) -> Result<(Self, *const K, *const V, Option<(&K, &V)>), (&K, &V)>
+    where
+        K: HashLike<L>,
+        K: PartialEq<L>,
+        M: HasherBv<H, L>
+    {
         match self.root.insert(key, value, Some(flag), replace) {
             InsertResult::Found(key, value) => Err((key, value)),
             InsertResult::InsertedC(cnode, key, value, prev) => Ok((Self::singleton(MNode::C(cnode)), key, value, prev)),
@@ -49,7 +181,16 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> HashTrie
     }
 
     pub(crate) fn remove<'a, L: Key + HashLike<K>>(&'a self, key: &L) -> Result<(Self, &'a K, &'a V), HashTrieError> where K: PartialEq<L>, M: HasherBv<H, L> {
-        match self.root.remove(key, Some(Flag::from(M::default().hash(key)))) {
+        self.remove_at(key, Flag::from(M::default().hash(key)))
+    }
+
+    pub(crate) fn remove_with_hash<'a, L: Key + HashLike<K>>(&'a self, hash: H, key: &L) -> Result<(Self, &'a K, &'a V), HashTrieError> where K: PartialEq<L>, M: HasherBv<H, L> {
+        debug_assert_eq!(hash, M::default().hash(key));
+        self.remove_at(key, Flag::from(hash))
+    }
+
+    fn remove_at<'a, L: Key + HashLike<K>>(&'a self, key: &L, flag: Flag<H, F>) -> Result<(Self, &'a K, &'a V), HashTrieError> where K: PartialEq<L>, M: HasherBv<H, L> {
+        match self.root.remove(key, Some(flag)) {
             RemoveResult::NotFound => Err(HashTrieError::NotFound),
             RemoveResult::RemovedC(cnode, key, value) => Ok((Self::singleton(MNode::C(cnode)), key, value)),
             RemoveResult::RemovedL(lnode, key, value) => Ok((Self::singleton(MNode::L(lnode)), key, value)),
@@ -62,15 +203,165 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> HashTrie
         self.root.visit(op);
     }
 
+    pub(crate) fn fold<T, Op>(&self, init: T, mut op: Op) -> T where Op: FnMut(T, &K, &V) -> T {
+        self.root.fold(init, &mut op)
+    }
+
+    pub(crate) async fn par_reduce<T, MapOp, ReduceOp>(&self, map_op: MapOp, reduce_op: ReduceOp, par_strat: ParallelismStrategy) -> Option<T>
+    where
+        MapOp: Fn(&K, &V) -> T + Send + Sync,
+        ReduceOp: Fn(T, T) -> T + Send + Sync,
+    {
+        self.root.par_reduce(&map_op, &reduce_op, par_strat).await
+    }
+
+    pub(crate) fn nth(&self, index: usize) -> Result<(&K, &V), HashTrieError> {
+        match self.root.nth(index) {
+            FindResult::NotFound => Err(HashTrieError::NotFound),
+            FindResult::Found(key, value) => Ok((key, value))
+        }
+    }
+
+    #[cfg(feature = "rand")]
+    pub(crate) fn get_random<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Result<(&K, &V), HashTrieError> {
+        if self.size() == 0 {
+            Err(HashTrieError::NotFound)
+        }
+        else {
+            self.nth(rng.gen_range(0..self.size()))
+        }
+    }
+
+    pub(crate) fn build(entries: Vec<(H, K, Option<V>)>) -> Self {
+        Self::singleton(MNode::build(entries, 0).unwrap())
+    }
+
+    pub(crate) fn entries(&self) -> Vec<(&K, &V)> {
+        let mut entries = Vec::new();
+        self.root.entries_with_hash_prefix(None, 0, &mut entries);
+        entries
+    }
+
+    pub(crate) fn entries_with_hashes(&self) -> Vec<(&K, &V, H)> {
+        let mut entries = Vec::new();
+        self.root.entries_with_hashes(&mut entries);
+        entries
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn group_by<G: Key, Op: Fn(&K, &V) -> G>(&self, op: Op) -> Vec<(H, G, Vec<(H, K, Option<V>)>)> where M: HasherBv<H, G> {
+        let mut indices = HashTrie::<H, F, G, usize, M, ()>::default();
+        let mut groups: Vec<(H, G, Vec<(H, K, Option<V>)>)> = Vec::new();
+        for (key, value, hash_value) in self.entries_with_hashes() {
+            let group = op(key, value);
+            let group_hash = M::default().hash(&group);
+            let index = match indices.find_with_hash(group_hash.clone(), &group) {
+                Ok((_group, index)) => *index,
+                Err(_) => {
+                    indices = indices.insert_with_hash(group_hash.clone(), group.clone(), groups.len(), false).unwrap().0;
+                    groups.push((group_hash, group, Vec::new()));
+                    groups.len() - 1
+                },
+            };
+            groups[index].2.push((hash_value, key.clone(), Some(value.clone())));
+        }
+        groups
+    }
+
+    pub(crate) fn entries_with_hash_prefix(&self, prefix: H, bits: usize) -> Vec<(&K, &V)> {
+        let mut entries = Vec::new();
+        self.root.entries_with_hash_prefix(Some(Flag::new(prefix)), bits.min(H::max_ones()), &mut entries);
+        entries
+    }
+
+    pub(crate) fn split_root(&self) -> Vec<Self> {
+        self.root.split_root().into_iter().filter(|root| root.size() != 0).map(Self::singleton).collect()
+    }
+
+    pub(crate) fn concat_disjoint<'a>(tries: impl IntoIterator<Item = &'a Self>) -> Self {
+        Self::singleton(tries.into_iter().fold(MNode::default(), |root, trie| root.concat_disjoint(&trie.root, 0)))
+    }
+
+    pub(crate) fn merge3<Conflict: Fn(&K, Option<&V>, Option<&V>, Option<&V>) -> Resolution<V>>(base: &Self, left: &Self, right: &Self, conflict: Conflict) -> Self where V: PartialEq {
+        Self::singleton(base.root.merge3(&left.root, &right.root, 0, &conflict))
+    }
+
+    pub(crate) fn split_by_hash_prefix(&self, bits: usize) -> Vec<Self> {
+        self.root.split_by_hash_prefix(0, bits).into_iter().map(Self::singleton).collect()
+    }
+
     pub(crate) async fn transform<ReduceT, ReduceOp, Op>
         (&self, reduce_op: ReduceOp, op: MapTransform<ReduceT, Op>, par_strat: ParallelismStrategy) -> (Self, ReduceT)
         where
         Self: Sized,
-        ReduceT: Clone + Default + Send + Sync,
-        ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
         Op: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     {
-        match self.root.transform(reduce_op, op, par_strat).await {
+        let (op, constant) = op.split_reduction();
+        let (trie, reduced) = match self.root.transform(reduce_op.clone(), op, par_strat).await {
+            MNodeTransformResult::Unchanged(reduced) => (self.clone(), reduced),
+            MNodeTransformResult::C(cnode, reduced) => (Self::singleton(MNode::C(cnode)), reduced),
+            MNodeTransformResult::L(lnode, reduced) => (Self::singleton(MNode::L(lnode)), reduced),
+            MNodeTransformResult::S(snode, reduced) => (Self::singleton(MNode::S(snode)), reduced),
+            MNodeTransformResult::Removed(reduced) => (Self::default(), reduced),
+        };
+        (trie, reduce_op.reduce(constant, reduced))
+    }
+
+    pub(crate) fn map_entries<X: Value, B: Measure<K, X>, Op: Fn(&K, &V) -> X>(&self, op: Op) -> HashTrie<H, F, K, X, M, B> {
+        HashTrie::singleton(self.root.map_entries(&op))
+    }
+
+    pub(crate) async fn restrict_keys<B: Measure<K, ()>>(&self, keys: &HashTrie<H, F, K, (), M, B>, keep: bool, par_strat: ParallelismStrategy) -> Self {
+        self.with_transform_result(self.root.restrict_keys(&keys.root, keep, 0, par_strat).await)
+    }
+
+    pub(crate) async fn partition<Pred>(&self, pred: Pred, par_strat: ParallelismStrategy) -> (Self, Self) where Pred: Fn(&K, &V) -> bool + Send + Sync {
+        let (kept, rejected) = self.root.partition(&pred, par_strat).await;
+        (self.with_transform_result(kept), self.with_transform_result(rejected))
+    }
+
+    fn with_transform_result(&self, result: MNodeTransformResult<H, F, K, V, M, A, ()>) -> Self {
+        match result {
+            MNodeTransformResult::Unchanged(()) => self.clone(),
+            MNodeTransformResult::C(cnode, ()) => Self::singleton(MNode::C(cnode)),
+            MNodeTransformResult::L(lnode, ()) => Self::singleton(MNode::L(lnode)),
+            MNodeTransformResult::S(snode, ()) => Self::singleton(MNode::S(snode)),
+            MNodeTransformResult::Removed(()) => Self::default(),
+        }
+    }
+
+    pub(crate) async fn transform_many<'a, ReduceT, ReduceOp, Op>(tries: impl IntoIterator<Item = &'a Self>, reduce_op: ReduceOp, op: Op, par_strat: ParallelismStrategy) -> (Self, ReduceT)
+    where
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
+        Op: Fn(&[(&K, &V)]) -> MapTransformResult<V, ReduceT> + Send + Sync,
+    {
+        let roots: Vec<&MNode<H, F, K, V, M, A>> = tries.into_iter().map(|trie| &trie.root).collect();
+        match MNode::transform_many(&roots, &reduce_op, &op, 0, par_strat).await {
+            (Some(root), reduced) => (Self::singleton(root), reduced),
+            (None, reduced) => (Self::default(), reduced),
+        }
+    }
+
+    pub(crate) async fn join<W: Value, B: Measure<K, W>, X: Value, C: Measure<K, X>, Op>(&self, right: &HashTrie<H, F, K, W, M, B>, op: Op, keep_left: bool, keep_right: bool, par_strat: ParallelismStrategy) -> HashTrie<H, F, K, X, M, C>
+    where
+        Op: Fn(&K, Option<&V>, Option<&W>) -> X + Send + Sync,
+    {
+        HashTrie::singleton(self.root.join(&right.root, &op, (keep_left, keep_right), 0, par_strat).await.unwrap_or_default())
+    }
+
+    pub(crate) async fn transform_async<ReduceT, ReduceOp, Op, Fut>
+        (&self, reduce_op: ReduceOp, op: Op, par_strat: ParallelismStrategy) -> (Self, ReduceT)
+        where
+        Self: Sized,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
+        Op: Fn(&K, &V) -> Fut + Clone + Send + Sync,
+        Fut: Future<Output = MapTransformResult<V, ReduceT>>,
+    {
+        match self.root.transform_async(reduce_op, op, par_strat).await {
             MNodeTransformResult::Unchanged(reduced) => (self.clone(), reduced),
             MNodeTransformResult::C(cnode, reduced) => (Self::singleton(MNode::C(cnode)), reduced),
             MNodeTransformResult::L(lnode, reduced) => (Self::singleton(MNode::L(lnode)), reduced),
@@ -79,51 +370,97 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> HashTrie
         }
     }
 
+    pub(crate) async fn try_transform<ReduceT, ReduceOp, Op, E>
+        (&self, reduce_op: ReduceOp, op: Op, par_strat: ParallelismStrategy) -> Result<(Self, ReduceT), E>
+        where
+        Self: Sized,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
+        Op: Fn(&K, &V) -> Result<MapTransformResult<V, ReduceT>, E> + Clone + Send + Sync,
+    {
+        Ok(match self.root.try_transform(reduce_op, op, par_strat).await? {
+            MNodeTransformResult::Unchanged(reduced) => (self.clone(), reduced),
+            MNodeTransformResult::C(cnode, reduced) => (Self::singleton(MNode::C(cnode)), reduced),
+            MNodeTransformResult::L(lnode, reduced) => (Self::singleton(MNode::L(lnode)), reduced),
+            MNodeTransformResult::S(snode, reduced) => (Self::singleton(MNode::S(snode)), reduced),
+            MNodeTransformResult::Removed(reduced) => (Self::default(), reduced),
+        })
+    }
+
     pub(crate) unsafe fn transmute<S: Key, X: Value, ReduceT, ReduceOp, Op>
-        (&self, reduce_op: ReduceOp, op: MapTransmute<ReduceT, Op>) -> (HashTrie<H, F, S, X, M>, ReduceT)
+        (&self, reduce_op: ReduceOp, op: MapTransmute<ReduceT, Op>) -> (HashTrie<H, F, S, X, M, A>, ReduceT)
         where
+        A: Measure<S, X>,
         Self: Sized,
-        ReduceT: Clone + Default + Send + Sync,
-        ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
         Op: Fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT> + Clone,
         K: HashLike<S>,
         K: PartialEq<S>,
         M: HasherBv<H, S>,
     {
-        match self.root.transmute(reduce_op, op) {
+        let (op, constant) = op.split_reduction();
+        let (trie, reduced) = match self.root.transmute(reduce_op.clone(), op) {
             MNodeTransmuteResult::C(cnode, reduced) => (HashTrie::singleton(MNode::C(cnode)), reduced),
             MNodeTransmuteResult::L(lnode, reduced) => (HashTrie::singleton(MNode::L(lnode)), reduced),
             MNodeTransmuteResult::S(snode, reduced) => (HashTrie::singleton(MNode::S(snode)), reduced),
             MNodeTransmuteResult::Removed(reduced) => (HashTrie::default(), reduced),
-        }
+        };
+        (trie, reduce_op.reduce(constant, reduced))
     }
 
     pub(crate) async fn transform_with_transformed<ReduceT, ReduceOp, BothOp, LeftOp, RightOp>
         (&self, right: &Self, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, par_strat: ParallelismStrategy) -> (Self, ReduceT)
         where
         Self: Sized,
-        ReduceT: Clone + Default + Send + Sync,
-        ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
         BothOp: Fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT> + Clone + Send + Sync,
         LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
         RightOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     {
-        match self.root.transform_with_transformed(&right.root, reduce_op, both_op, left_op, right_op, 0, par_strat).await {
+        let (both_op, both_reduced) = both_op.split_reduction();
+        let (left_op, left_reduced) = left_op.split_reduction();
+        let (right_op, right_reduced) = right_op.split_reduction();
+        let constant = reduce_op.reduce(reduce_op.reduce(both_reduced, left_reduced), right_reduced);
+        let (trie, reduced) = match self.root.transform_with_transformed(&right.root, reduce_op.clone(), both_op, left_op, right_op, 0, par_strat).await {
             MNodeJointTransformResult::UnchangedLR(reduced) | MNodeJointTransformResult::UnchangedL(reduced) => (self.clone(), reduced),
             MNodeJointTransformResult::UnchangedR(reduced) => (right.clone(), reduced),
             MNodeJointTransformResult::C(cnode, reduced) => (HashTrie::singleton(MNode::C(cnode)), reduced),
             MNodeJointTransformResult::L(lnode, reduced) => (HashTrie::singleton(MNode::L(lnode)), reduced),
             MNodeJointTransformResult::S(snode, reduced) => (HashTrie::singleton(MNode::S(snode)), reduced),
             MNodeJointTransformResult::Removed(reduced) => (HashTrie::default(), reduced),
-        }
+        };
+        (trie, reduce_op.reduce(constant, reduced))
+    }
+
+    pub(crate) async fn try_transform_with_transformed<ReduceT, ReduceOp, BothOp, LeftOp, RightOp, E>
+        (&self, right: &Self, reduce_op: ReduceOp, both_op: BothOp, left_op: LeftOp, right_op: RightOp, par_strat: ParallelismStrategy) -> Result<(Self, ReduceT), E>
+        where
+        Self: Sized,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
+        BothOp: Fn(&K, &V, &K, &V) -> Result<MapJointTransformResult<V, ReduceT>, E> + Clone + Send + Sync,
+        LeftOp: Fn(&K, &V) -> Result<MapTransformResult<V, ReduceT>, E> + Clone + Send + Sync,
+        RightOp: Fn(&K, &V) -> Result<MapTransformResult<V, ReduceT>, E> + Clone + Send + Sync,
+    {
+        Ok(match self.root.try_transform_with_transformed(&right.root, JointOps {reduce_op: &reduce_op, both_op: &both_op, left_op: &left_op, right_op: &right_op}, 0, par_strat).await? {
+            MNodeJointTransformResult::UnchangedLR(reduced) | MNodeJointTransformResult::UnchangedL(reduced) => (self.clone(), reduced),
+            MNodeJointTransformResult::UnchangedR(reduced) => (right.clone(), reduced),
+            MNodeJointTransformResult::C(cnode, reduced) => (HashTrie::singleton(MNode::C(cnode)), reduced),
+            MNodeJointTransformResult::L(lnode, reduced) => (HashTrie::singleton(MNode::L(lnode)), reduced),
+            MNodeJointTransformResult::S(snode, reduced) => (HashTrie::singleton(MNode::S(snode)), reduced),
+            MNodeJointTransformResult::Removed(reduced) => (HashTrie::default(), reduced),
+        })
     }
 
     pub(crate) async unsafe fn transform_with_transmuted<L: Key, W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>
-        (&self, right: &HashTrie<H, F, L, W, M>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, par_strat: ParallelismStrategy) -> (Self, ReduceT)
+        (&self, right: &HashTrie<H, F, L, W, M, A>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, par_strat: ParallelismStrategy) -> (Self, ReduceT)
         where
+        A: Measure<L, W>,
         Self: Sized,
-        ReduceT: Clone + Default + Send + Sync,
-        ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
         BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<V, ReduceT> + Clone,
         LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
         RightOp: Fn(&L, &W) -> MapTransmuteResult<K, V, ReduceT> + Clone + Send + Sync,
@@ -133,21 +470,27 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> HashTrie
         L: PartialEq<K>,
         M: HasherBv<H, L>,
     {
-        match self.root.transform_with_transmuted(&right.root, reduce_op, both_op, left_op, right_op, 0, par_strat).await {
+        let (both_op, both_reduced) = both_op.split_reduction();
+        let (left_op, left_reduced) = left_op.split_reduction();
+        let (right_op, right_reduced) = right_op.split_reduction();
+        let constant = reduce_op.reduce(reduce_op.reduce(both_reduced, left_reduced), right_reduced);
+        let (trie, reduced) = match self.root.transform_with_transmuted(&right.root, reduce_op.clone(), both_op, left_op, right_op, 0, par_strat).await {
             MNodeTransformResult::Unchanged(reduced) => (self.clone(), reduced),
             MNodeTransformResult::C(cnode, reduced) => (HashTrie::singleton(MNode::C(cnode)), reduced),
             MNodeTransformResult::L(lnode, reduced) => (HashTrie::singleton(MNode::L(lnode)), reduced),
             MNodeTransformResult::S(snode, reduced) => (HashTrie::singleton(MNode::S(snode)), reduced),
             MNodeTransformResult::Removed(reduced) => (HashTrie::default(), reduced),
-        }
+        };
+        (trie, reduce_op.reduce(constant, reduced))
     }
 
     pub(crate) async unsafe fn transmute_with_transformed<L: Key, W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>
-        (&self, right: &HashTrie<H, F, L, W, M>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, par_strat: ParallelismStrategy) -> (HashTrie<H, F, L, W, M>, ReduceT)
+        (&self, right: &HashTrie<H, F, L, W, M, A>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, par_strat: ParallelismStrategy) -> (HashTrie<H, F, L, W, M, A>, ReduceT)
         where
+        A: Measure<L, W>,
         Self: Sized,
-        ReduceT: Clone + Default + Send + Sync,
-        ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
         BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<W, ReduceT> + Clone,
         LeftOp: Fn(&K, &V) -> MapTransmuteResult<L, W, ReduceT> + Clone + Send + Sync,
         RightOp: Fn(&L, &W) -> MapTransformResult<W, ReduceT> + Clone + Send + Sync,
@@ -157,21 +500,28 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> HashTrie
         L: PartialEq<K>,
         M: HasherBv<H, L>,
     {
-        match self.root.transmute_with_transformed(&right.root, reduce_op, both_op, left_op, right_op, 0, par_strat).await {
+        let (both_op, both_reduced) = both_op.split_reduction();
+        let (left_op, left_reduced) = left_op.split_reduction();
+        let (right_op, right_reduced) = right_op.split_reduction();
+        let constant = reduce_op.reduce(reduce_op.reduce(both_reduced, left_reduced), right_reduced);
+        let (trie, reduced) = match self.root.transmute_with_transformed(&right.root, reduce_op.clone(), both_op, left_op, right_op, 0, par_strat).await {
             MNodeTransformResult::Unchanged(reduced) => (right.clone(), reduced),
             MNodeTransformResult::C(cnode, reduced) => (HashTrie::singleton(MNode::C(cnode)), reduced),
             MNodeTransformResult::L(lnode, reduced) => (HashTrie::singleton(MNode::L(lnode)), reduced),
             MNodeTransformResult::S(snode, reduced) => (HashTrie::singleton(MNode::S(snode)), reduced),
             MNodeTransformResult::Removed(reduced) => (HashTrie::default(), reduced),
-        }
+        };
+        (trie, reduce_op.reduce(constant, reduced))
     }
 
     pub(crate) unsafe fn transmute_with_transmuted<L: Key, W: Value, S: Key, X: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>
-        (&self, right: &HashTrie<H, F, L, W, M>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>) -> (HashTrie<H, F, S, X, M>, ReduceT)
+        (&self, right: &HashTrie<H, F, L, W, M, A>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>) -> (HashTrie<H, F, S, X, M, A>, ReduceT)
         where
+        A: Measure<L, W>,
+        A: Measure<S, X>,
         Self: Sized,
-        ReduceT: Clone + Default + Send + Sync,
-        ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
         BothOp: Fn(&K, &V, &L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
         LeftOp: Fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT> + Clone,
         RightOp: Fn(&L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
@@ -186,31 +536,42 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> HashTrie
         M: HasherBv<H, L>,
         M: HasherBv<H, S>,
     {
-        match self.root.transmute_with_transmuted(&right.root, reduce_op, both_op, left_op, right_op, 0) {
+        let (both_op, both_reduced) = both_op.split_reduction();
+        let (left_op, left_reduced) = left_op.split_reduction();
+        let (right_op, right_reduced) = right_op.split_reduction();
+        let constant = reduce_op.reduce(reduce_op.reduce(both_reduced, left_reduced), right_reduced);
+        let (trie, reduced) = match self.root.transmute_with_transmuted(&right.root, reduce_op.clone(), both_op, left_op, right_op, 0) {
             MNodeTransmuteResult::C(cnode, reduced) => (HashTrie::singleton(MNode::C(cnode)), reduced),
             MNodeTransmuteResult::L(lnode, reduced) => (HashTrie::singleton(MNode::L(lnode)), reduced),
             MNodeTransmuteResult::S(snode, reduced) => (HashTrie::singleton(MNode::S(snode)), reduced),
             MNodeTransmuteResult::Removed(reduced) => (HashTrie::default(), reduced),
-        }
+        };
+        (trie, reduce_op.reduce(constant, reduced))
     }
 
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> Clone for HashTrie<H, F, K, V, M> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> Clone for HashTrie<H, F, K, V, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
     fn clone(&self) -> Self {
         Self::singleton(self.root.clone())
     }
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> Default for HashTrie<H, F, K, V, M> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> Debug for HashTrie<H, F, K, V, M, A> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("HashTrie").field("root", &self.root).finish()
+    }
+}
+
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> Default for HashTrie<H, F, K, V, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> Eq for HashTrie<H, F, K, V, M> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {}
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> Eq for HashTrie<H, F, K, V, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {}
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> PartialEq for HashTrie<H, F, K, V, M> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> PartialEq for HashTrie<H, F, K, V, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
     fn eq(&self, other: &Self) -> bool {
         self.root == other.root
     }
@@ -1,4 +1,4 @@
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
 
 #[allow(unused_imports)]
 #[macro_use]
@@ -6,20 +6,36 @@ extern crate alloc;
 
 #[macro_use]
 pub mod results;
+mod atomic;
+mod atomic_map;
 mod bit_indexed_array;
+mod builder;
+mod concurrent_map;
+pub mod digest;
 mod enums;
 mod flag;
 mod hash_trie;
 mod map;
 mod node;
+pub mod reducers;
 mod set;
+pub mod sync;
 pub mod traits;
 pub mod transformations;
+mod txn;
+mod versioned_map;
+#[cfg(feature = "std")]
+pub mod wal;
 
 pub use enums::ParallelismStrategy;
 
 pub use set::HashTrieSet as HashTrieSet;
 pub use map::HashTrieMap as HashTrieMap;
+pub use atomic_map::{AtomicHashTrieMap, MapVersion, MapVersions};
+pub use builder::HashTrieMapBuilder as HashTrieMapBuilder;
+pub use concurrent_map::ConcurrentHashTrieMap as ConcurrentHashTrieMap;
+pub use txn::Txn as Txn;
+pub use versioned_map::VersionedHashTrieMap as VersionedHashTrieMap;
 
 /// A HashTrieSet using 64-bit hashes, 32-bit flags, and FnvHasher
 pub type DefaultHashTrieSet<V> = set::HashTrieSet<u64, u32, V, fnv::FnvHasher>;
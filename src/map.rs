@@ -1,8 +1,11 @@
-use crate::{results::*, transformations::*, traits::*, hash_trie::HashTrie, *};
-use alloc::fmt::Debug;
+use crate::{digest::Digest, results::*, sync::*, transformations::*, traits::*, hash_trie::HashTrie, *};
+use alloc::{collections::BTreeSet, fmt::Debug, vec::Vec};
+use core::future::Future;
 
 /// `HashTrieMap` implements a hash map using a hash array mapped trie (HAMT).
 /// 
+/// With the `cache_hash` feature, each entry also stores the full hash of its key, `size_of::<H>()` bytes, which is reused rather than recomputed whenever leaves are split, merged or compared.
+/// 
 /// # Example Usage
 /// 
 /// ```
@@ -65,17 +68,16 @@ use alloc::fmt::Debug;
 ///     Err(_) => panic!(),
 /// }
 /// ```
-#[derive(Clone, Debug)]
 #[must_use]
-pub struct HashTrieMap <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
-    set: HashTrie<H, F, K, V, M>,
+pub struct HashTrieMap <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V> = ()> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    pub(crate) set: HashTrie<H, F, K, V, M, A>,
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> HashTrieMap<H, F, K, V, M> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> HashTrieMap<H, F, K, V, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
     /// Get a new, empty HashTrieMap.
     pub fn new() -> Self {
         Self {
-            set: HashTrie::<H, F, K, V, M>::new()
+            set: HashTrie::<H, F, K, V, M, A>::new()
         }
     }
 
@@ -84,6 +86,61 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> HashTrie
         self.set.size()
     }
 
+    /// Get the summary of all entries in the map under the measure `A`, cached incrementally by each node.
+    pub fn summary(&self) -> <A as Measure<K, V>>::Summary {
+        self.set.summary()
+    }
+
+    /// Get the content digest of the whole map in O(1). Maps hold equal entries exactly when their digests are equal, barring a SHA-256 collision.
+    pub fn root_digest(&self) -> Digest where A: Measure<K, V, Summary = Digest> {
+        self.set.summary()
+    }
+
+    /// List the differences going from this map to `right`, skipping subtrees that are shared or whose sizes and digests both match.
+    pub fn diff<'a>(&'a self, right: &'a Self) -> Vec<MapDiff<'a, K, V>> where V: PartialEq, A: Measure<K, V, Summary = Digest> {
+        self.set.diff(&right.set, |left, right| left == right || (left.size() == right.size() && left.summary() == right.summary()))
+    }
+
+    pub(crate) fn build(entries: Vec<(H, K, Option<V>)>) -> Self {
+        Self {set: HashTrie::build(entries)}
+    }
+
+    pub(crate) fn entries(&self) -> Vec<(&K, &V)> {
+        self.set.entries()
+    }
+
+    pub(crate) fn entries_with_hashes(&self) -> Vec<(&K, &V, H)> {
+        self.set.entries_with_hashes()
+    }
+
+    pub(crate) fn measure_memory(&self, seen: &mut BTreeSet<usize>, usage: &mut MemoryUsage) {
+        self.set.measure_memory(seen, usage);
+    }
+
+    pub(crate) fn changes(&self, right: &Self) -> Vec<MapChange<K, V>> where V: PartialEq {
+        self.set.diff(&right.set, |left, right| left == right).into_iter().map(MapChange::from).collect()
+    }
+
+    /// Start an anti-entropy exchange by describing the root of this map to a peer.
+    /// 
+    /// The exchange is one-way: the requester pulls the responder's entries and the responder is left unchanged.
+    /// Nothing is removed, so running a second exchange with the roles swapped makes both replicas hold the union.
+    pub fn sync_request(&self) -> SyncRequest where A: Measure<K, V, Summary = Digest> {
+        self.set.sync_request(vec!(Vec::new()))
+    }
+
+    /// Answer a peer's `SyncRequest` with this map's entries in subtrees whose digests differ, and the subtrees worth comparing more finely.
+    pub fn sync_respond(&self, request: &SyncRequest) -> SyncResponse<K, V> where A: Measure<K, V, Summary = Digest> {
+        self.set.sync_respond(request)
+    }
+
+    /// Apply a peer's `SyncResponse`, taking the peer's value for keys present in both. Returns the merged map and, if subtrees remain to compare, the next request.
+    pub fn sync_apply(&self, response: &SyncResponse<K, V>) -> (Self, Option<SyncRequest>) where A: Measure<K, V, Summary = Digest> {
+        let map = self.insert_many(response.entries.iter().cloned());
+        let request = if response.descend.is_empty() {None} else {Some(map.set.sync_request(response.descend.clone()))};
+        (map, request)
+    }
+
     /// Search the HashTrieMap for the given key and return references if found, or `HashTrieError::NotFound` if not found.
     pub fn find<'a, L: Key + HashLike<K>>(&'a self, key: &L) -> Result<(&'a K, &'a V), HashTrieError> where K: PartialEq<L>, M: HasherBv<H, L> {
         self.set.find(key)
@@ -92,7 +149,7 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> HashTrie
     /// Search the HashTrieMap for the spot to insert the key and return both a mutated map and, if applicable, references to the replaced values.
     /// If found and replacement is disabled, references to the existing values are returned.
     #[allow(clippy::type_complexity)]
-    pub fn insert<'a, L: Key + HashLike<K> + Into<K>, W: Into<V>>(&'a self, key: L, value: W, replace: bool) -> Result<(Self, *const K, *const V, Option<(&'a K, &'a V)>), (&'a K, &'a V)>
+    pub fn insert<L: Key + HashLike<K> + Into<K>, W: Into<V>>(&self, key: L, value: W, replace: bool) -> Result<(Self, *const K, *const V, Option<(&K, &V)>), (&K, &V)>
     where
         K: HashLike<L>,
         K: PartialEq<L>,
@@ -106,17 +163,128 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> HashTrie
         self.set.remove(key).map(|(set, key, value)| (Self {set}, key, value))
     }
 
+    /// Search the HashTrieMap for the given key using a precomputed hash and return references if found, or `HashTrieError::NotFound` if not found.
+    /// The hash must be the one `M` would compute for the key.
+    pub fn find_with_hash<'a, L: Key + HashLike<K>>(&'a self, hash: H, key: &L) -> Result<(&'a K, &'a V), HashTrieError> where K: PartialEq<L>, M: HasherBv<H, L> {
+        self.set.find_with_hash(hash, key)
+    }
+
+    /// Search the HashTrieMap for the spot to insert the key using a precomputed hash and return both a mutated map and, if applicable, references to the replaced values.
+    /// The hash must be the one `M` would compute for the key.
+    #[allow(clippy::type_complexity)]
+    pub fn insert_with_hash<L: Key + HashLike<K> + Into<K>, W: Into<V>>(&self, hash: H, key: L, value: W, replace: bool) -> Result<(Self, *const K, *const V, Option<(&K, &V)>), (&K, &V)>
+    where
+        K: HashLike<L>,
+        K: PartialEq<L>,
+        M: HasherBv<H, L>
+    {
+        self.set.insert_with_hash(hash, key, value, replace).map(|(set, key, value, prev)| (Self {set}, key, value, prev))
+    }
+
+    /// Search the HashTrieMap for the given key to remove using a precomputed hash and return a mutated map, or `HashTrieError::NotFound` if not found.
+    /// The hash must be the one `M` would compute for the key.
+    pub fn remove_with_hash<'a, L: Key + HashLike<K>>(&'a self, hash: H, key: &L) -> Result<(Self, &'a K, &'a V), HashTrieError> where K: PartialEq<L>, M: HasherBv<H, L> {
+        self.set.remove_with_hash(hash, key).map(|(set, key, value)| (Self {set}, key, value))
+    }
+
+    /// Search the HashTrieMap for each of the given keys in a single traversal, returning results in the order of `keys`.
+    pub fn get_many<'a, L: Key + HashLike<K>>(&'a self, keys: &[L]) -> Vec<Result<(&'a K, &'a V), HashTrieError>> where K: PartialEq<L>, M: HasherBv<H, L> {
+        self.set.find_many(keys)
+    }
+
+    /// Insert or replace the entries in a single traversal, rebuilding each touched node once. Later entries replace earlier ones for the same key.
+    pub fn insert_many<I: IntoIterator<Item = (K, V)>>(&self, entries: I) -> Self {
+        Self {set: self.set.apply(entries.into_iter().map(|(key, value)| (M::default().hash(&key), key, Some(value))).collect())}
+    }
+
+    /// Remove the entries for the keys, if present, in a single traversal, rebuilding each touched node once.
+    pub fn remove_many<I: IntoIterator<Item = K>>(&self, keys: I) -> Self {
+        Self {set: self.set.apply(keys.into_iter().map(|key| (M::default().hash(&key), key, None)).collect())}
+    }
+
     /// Run an operation on each entry in the map.
     pub fn visit<Op: Clone>(&self, op: Op) where Op: Fn(&K, &V) {
         self.set.visit(|k,v| op(k, v));
     }
 
+    /// Fold each entry in the map into an accumulator, in visiting order. Reads the map without rebuilding any of it.
+    pub fn fold<T, Op>(&self, init: T, op: Op) -> T where Op: FnMut(T, &K, &V) -> T {
+        self.set.fold(init, op)
+    }
+
+    /// Map each entry in the map and reduce the results, splitting on branch nodes according to `par_strat`. Reads the map without rebuilding any of it. Returns `None` for an empty map.
+    pub async fn par_reduce<T, MapOp, ReduceOp>(&self, map_op: MapOp, reduce_op: ReduceOp, par_strat: ParallelismStrategy) -> Option<T>
+    where
+        MapOp: Fn(&K, &V) -> T + Send + Sync,
+        ReduceOp: Fn(T, T) -> T + Send + Sync,
+    {
+        self.set.par_reduce(map_op, reduce_op, par_strat).await
+    }
+
+    /// Get the entry at the given position in the map's visiting order, or `HashTrieError::NotFound` if out of range. Descends using cached subtree sizes.
+    pub fn nth(&self, index: usize) -> Result<(&K, &V), HashTrieError> {
+        self.set.nth(index)
+    }
+
+    /// Pick an entry uniformly at random, or `HashTrieError::NotFound` if the map is empty.
+    #[cfg(feature = "rand")]
+    pub fn get_random<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Result<(&K, &V), HashTrieError> {
+        self.set.get_random(rng)
+    }
+
+    /// Collect references to every entry whose hash matches `prefix` in its `bits` least significant bits, the bits the trie indexes first.
+    #[must_use]
+    pub fn entries_with_hash_prefix(&self, prefix: H, bits: usize) -> Vec<(&K, &V)> {
+        self.set.entries_with_hash_prefix(prefix, bits)
+    }
+
+    /// Split the map into one map per populated child of the root. Subtrees are shared with this map rather than copied.
+    #[must_use]
+    pub fn split_root(&self) -> Vec<Self> {
+        self.set.split_root().into_iter().map(|set| Self {set}).collect()
+    }
+
+    /// Combine maps known to hold disjoint keys, such as those produced by `split_root`, sharing their subtrees. If a key is present in more than one map, which entry survives is unspecified.
+    pub fn concat_disjoint(maps: &[Self]) -> Self {
+        Self::concat_disjoint_iter(maps)
+    }
+
+    pub(crate) fn concat_disjoint_iter<'a>(maps: impl IntoIterator<Item = &'a Self>) -> Self {
+        Self {set: HashTrie::concat_disjoint(maps.into_iter().map(|map| &map.set))}
+    }
+
+    /// Merge `left` and `right`, both derived from `base`, keeping each side's changes. Subtrees either side left untouched are shared rather than walked, and `conflict` settles keys changed differently on both sides, given the base, left and right values.
+    pub fn merge3<Conflict: Fn(&K, Option<&V>, Option<&V>, Option<&V>) -> Resolution<V>>(base: &Self, left: &Self, right: &Self, conflict: Conflict) -> Self where V: PartialEq {
+        Self {set: HashTrie::merge3(&base.set, &left.set, &right.set, conflict)}
+    }
+
+    /// Split the map into `2^bits` maps, indexed by the `bits` least significant bits of each entry's hash. Subtrees are shared with this map rather than copied.
+    #[must_use]
+    pub fn split_by_hash_prefix(&self, bits: usize) -> Vec<Self> {
+        self.set.split_by_hash_prefix(bits).into_iter().map(|set| Self {set}).collect()
+    }
+
+    /// Group the entries by the key `op` returns for each, bulk building one map per group. Entry hashes are reused rather than recomputed.
+    pub fn group_by<G: Key, Op: Fn(&K, &V) -> G>(&self, op: Op) -> HashTrieMap<H, F, G, Self, M> where M: HasherBv<H, G> {
+        HashTrieMap::build(self.set.group_by(op).into_iter().map(|(hash_value, group, entries)| (hash_value, group, Some(Self::build(entries)))).collect())
+    }
+
+    /// Group the keys by the key `op` returns for each entry, bulk building one set per group. Entry hashes are reused rather than recomputed.
+    pub fn group_keys_by<G: Key, Op: Fn(&K, &V) -> G>(&self, op: Op) -> HashTrieMap<H, F, G, HashTrieSet<H, F, K, M>, M> where M: HasherBv<H, G> {
+        HashTrieMap::build(self.set.group_by(op).into_iter().map(|(hash_value, group, entries)| (hash_value, group, Some(HashTrieSet::build(entries.into_iter().map(|(hash_value, key, _value)| (hash_value, key, Some(()))).collect())))).collect())
+    }
+
+    /// Build a set of the keys in the map, mapping over the trie so its shape and key hashes are reused rather than rebuilt.
+    pub fn keys_set(&self) -> HashTrieSet<H, F, K, M> {
+        HashTrieSet {set: self.set.map_entries(|_key, _value| ())}
+    }
+
     /// Run a transform operation on each entry in the map. Returns the transformed map and a reduction of the secondary returns of the transform operations.
     pub async fn transform<ReduceT, ReduceOp, Op>
         (&self, reduce_op: ReduceOp, op: MapTransform<ReduceT, Op>, par_strat: ParallelismStrategy) -> (Self, ReduceT)
         where
         Self: Sized,
-        ReduceT: Clone + Default + Send + Sync,
+        ReduceT: Default + Send + Sync,
         ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
         Op: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync
     {
@@ -124,12 +292,115 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> HashTrie
         (Self{set}, reduced)
     }
 
+    /// Keep only the entries for which `pred` returns true. Subtrees without rejected entries are shared with this map.
+    pub async fn filter<Pred>(&self, pred: Pred, par_strat: ParallelismStrategy) -> Self where Pred: Fn(&K, &V) -> bool + Clone + Send + Sync {
+        let (set, ()) = self.set.transform(MonoidReducer, new_map_transform_generic(move |key, value| match pred(key, value) {
+            true => MapTransformResult::Unchanged(()),
+            false => MapTransformResult::Removed(()),
+        }), par_strat).await;
+        Self {set}
+    }
+
+    /// Remove the entries for which `pred` returns false, in place.
+    pub async fn retain<Pred>(&mut self, pred: Pred, par_strat: ParallelismStrategy) where Pred: Fn(&K, &V) -> bool + Clone + Send + Sync {
+        *self = self.filter(pred, par_strat).await;
+    }
+
+    /// Replace each value with the result of `op`. Subtrees whose values all compare equal are shared with this map.
+    pub async fn map_values<Op>(&self, op: Op, par_strat: ParallelismStrategy) -> Self where V: PartialEq, Op: Fn(&K, &V) -> V + Clone + Send + Sync {
+        self.filter_map_values(move |key, value| Some(op(key, value)), par_strat).await
+    }
+
+    /// Replace each value with the result of `op`, removing entries for which it returns `None`. Subtrees whose values all compare equal are shared with this map.
+    pub async fn filter_map_values<Op>(&self, op: Op, par_strat: ParallelismStrategy) -> Self where V: PartialEq, Op: Fn(&K, &V) -> Option<V> + Clone + Send + Sync {
+        let (set, ()) = self.set.transform(MonoidReducer, new_map_transform_generic(move |key, value| match op(key, value) {
+            Some(transformed) if transformed == *value => MapTransformResult::Unchanged(()),
+            Some(transformed) => MapTransformResult::Transformed(transformed, ()),
+            None => MapTransformResult::Removed(()),
+        }), par_strat).await;
+        Self {set}
+    }
+
+    /// Split the map in a single pass into the entries for which `pred` returns true and those for which it returns false. Subtrees that fall entirely on one side are shared with this map.
+    pub async fn partition<Pred>(&self, pred: Pred, par_strat: ParallelismStrategy) -> (Self, Self) where Pred: Fn(&K, &V) -> bool + Send + Sync {
+        let (kept, rejected) = self.set.partition(pred, par_strat).await;
+        (Self {set: kept}, Self {set: rejected})
+    }
+
+    /// Keep only the entries whose keys are in `keys`, walking both tries together. Subtrees without removed entries are shared with this map.
+    pub async fn restrict_keys<B: Measure<K, ()>>(&self, keys: &HashTrieSet<H, F, K, M, B>, par_strat: ParallelismStrategy) -> Self {
+        Self {set: self.set.restrict_keys(&keys.set, true, par_strat).await}
+    }
+
+    /// Remove the entries whose keys are in `keys`, walking both tries together. Subtrees without removed entries are shared with this map.
+    pub async fn without_keys<B: Measure<K, ()>>(&self, keys: &HashTrieSet<H, F, K, M, B>, par_strat: ParallelismStrategy) -> Self {
+        Self {set: self.set.restrict_keys(&keys.set, false, par_strat).await}
+    }
+
+    /// Pair the values of the keys present in both maps, walking both tries together.
+    pub async fn inner_join<W: Value, B: Measure<K, W>>(&self, right: &HashTrieMap<H, F, K, W, M, B>, par_strat: ParallelismStrategy) -> HashTrieMap<H, F, K, (V, W), M> {
+        let set = self.set.join(&right.set, |_key, value: Option<&V>, rvalue: Option<&W>| (value.unwrap().clone(), rvalue.unwrap().clone()), false, false, par_strat).await;
+        HashTrieMap {set}
+    }
+
+    /// Pair the values of the keys in this map with those in `right`, if any, walking both tries together.
+    pub async fn left_join<W: Value, B: Measure<K, W>>(&self, right: &HashTrieMap<H, F, K, W, M, B>, par_strat: ParallelismStrategy) -> HashTrieMap<H, F, K, (V, Option<W>), M> {
+        let set = self.set.join(&right.set, |_key, value: Option<&V>, rvalue: Option<&W>| (value.unwrap().clone(), rvalue.cloned()), true, false, par_strat).await;
+        HashTrieMap {set}
+    }
+
+    /// Pair the values of the keys present in either map, walking both tries together.
+    pub async fn outer_join<W: Value, B: Measure<K, W>>(&self, right: &HashTrieMap<H, F, K, W, M, B>, par_strat: ParallelismStrategy) -> HashTrieMap<H, F, K, (Option<V>, Option<W>), M> {
+        let set = self.set.join(&right.set, |_key, value: Option<&V>, rvalue: Option<&W>| (value.cloned(), rvalue.cloned()), true, true, par_strat).await;
+        HashTrieMap {set}
+    }
+
+    /// Run a transform operation on each entry in the map, combining the secondary returns with `Monoid::combine`. Returns the transformed map and the combined reduction.
+    pub async fn transform_monoid<ReduceT, Op>
+        (&self, op: MapTransform<ReduceT, Op>, par_strat: ParallelismStrategy) -> (Self, ReduceT)
+        where
+        Self: Sized,
+        ReduceT: Monoid + Default,
+        Op: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync
+    {
+        let (set, reduced) = self.set.transform(MonoidReducer, op, par_strat).await;
+        (Self{set}, reduced)
+    }
+
+    /// Run an asynchronous transform operation on each entry in the map. Operations are awaited one at a time under `ParallelismStrategy::Sequential`; under `ParallelismStrategy::MiddleIndex` each branch node awaits its two halves concurrently.
+    pub async fn transform_async<ReduceT, ReduceOp, Op, Fut>
+        (&self, reduce_op: ReduceOp, op: Op, par_strat: ParallelismStrategy) -> (Self, ReduceT)
+        where
+        Self: Sized,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+        Op: Fn(&K, &V) -> Fut + Clone + Send + Sync,
+        Fut: Future<Output = MapTransformResult<V, ReduceT>>,
+    {
+        let (set, reduced) = self.set.transform_async(reduce_op, op, par_strat).await;
+        (Self{set}, reduced)
+    }
+
+    /// Run a fallible transform operation on each entry in the map. Stops at the first error, otherwise returns the transformed map and a reduction of the secondary returns of the transform operations.
+    pub async fn try_transform<ReduceT, ReduceOp, Op, E>
+        (&self, reduce_op: ReduceOp, op: Op, par_strat: ParallelismStrategy) -> Result<(Self, ReduceT), E>
+        where
+        Self: Sized,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+        Op: Fn(&K, &V) -> Result<MapTransformResult<V, ReduceT>, E> + Clone + Send + Sync
+    {
+        let (set, reduced) = self.set.try_transform(reduce_op, op, par_strat).await?;
+        Ok((Self{set}, reduced))
+    }
+
     /// Run a transmute operation on each entry in the map. Returns the transmuted map and a reduction of the secondary returns of the transmute operations.
     pub unsafe fn transmute<S: Key + HashLike<K>, X: Value, ReduceT, ReduceOp, Op>
-        (&self, reduce_op: ReduceOp, op: MapTransmute<ReduceT, Op>) -> (HashTrieMap<H, F, S, X, M>, ReduceT)
+        (&self, reduce_op: ReduceOp, op: MapTransmute<ReduceT, Op>) -> (HashTrieMap<H, F, S, X, M, A>, ReduceT)
         where
+        A: Measure<S, X>,
         Self: Sized,
-        ReduceT: Clone + Default + Send + Sync,
+        ReduceT: Default + Send + Sync,
         ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
         Op: Fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT> + Clone,
         K: HashLike<S>,
@@ -140,12 +411,25 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> HashTrie
         (HashTrieMap{set}, reduced)
     }
 
+    /// Merge any number of maps in a single joint walk. `op` sees every key, given its entries in map order; `Unchanged` keeps the first. Subtrees present in only one map are shared wherever `op` leaves all of their entries unchanged. Returns the merged map and a reduction of the secondary returns of `op`.
+    pub async fn transform_many<ReduceT, ReduceOp, Op>
+        (maps: &[Self], reduce_op: ReduceOp, op: Op, par_strat: ParallelismStrategy) -> (Self, ReduceT)
+        where
+        Self: Sized,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+        Op: Fn(&[(&K, &V)]) -> MapTransformResult<V, ReduceT> + Send + Sync,
+    {
+        let (set, reduced) = HashTrie::transform_many(maps.iter().map(|map| &map.set), reduce_op, op, par_strat).await;
+        (Self {set}, reduced)
+    }
+
     /// Run a transform operation on each entry or pair of entries in the maps. Returns the transformed map and a reduction of the secondary returns of the transmute operations. Can reuse nodes from either map.
     pub async fn transform_with_transformed<ReduceT, ReduceOp, BothOp, LeftOp, RightOp>
         (&self, right: &Self, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, par_strat: ParallelismStrategy) -> (Self, ReduceT)
         where
         Self: Sized,
-        ReduceT: Clone + Default + Send + Sync,
+        ReduceT: Default + Send + Sync,
         ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
         BothOp: Fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT> + Clone + Send + Sync,
         LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
@@ -155,12 +439,28 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> HashTrie
         (HashTrieMap{set}, reduced)
     }
 
+    /// Run a fallible transform operation on each entry or pair of entries in the maps. Stops at the first error, otherwise returns the transformed map and a reduction of the secondary returns of the transform operations. Can reuse nodes from either map.
+    pub async fn try_transform_with_transformed<ReduceT, ReduceOp, BothOp, LeftOp, RightOp, E>
+        (&self, right: &Self, reduce_op: ReduceOp, both_op: BothOp, left_op: LeftOp, right_op: RightOp, par_strat: ParallelismStrategy) -> Result<(Self, ReduceT), E>
+        where
+        Self: Sized,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+        BothOp: Fn(&K, &V, &K, &V) -> Result<MapJointTransformResult<V, ReduceT>, E> + Clone + Send + Sync,
+        LeftOp: Fn(&K, &V) -> Result<MapTransformResult<V, ReduceT>, E> + Clone + Send + Sync,
+        RightOp: Fn(&K, &V) -> Result<MapTransformResult<V, ReduceT>, E> + Clone + Send + Sync,
+    {
+        let (set, reduced) = self.set.try_transform_with_transformed(&right.set, reduce_op, both_op, left_op, right_op, par_strat).await?;
+        Ok((HashTrieMap{set}, reduced))
+    }
+
     /// Run a transform/transmute operation on each entry or pair of entries in the maps. Returns the transmuted map and a reduction of the secondary returns of the transmute operations. Can reuse nodes from the transformed map. Like transform_with_transmuted but enforces identity transformations on keys.
     pub async fn transform_with_transfuted<W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>
-        (&self, right: &HashTrieMap<H, F, K, W, M>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: SetTransmute<ReduceT, RightOp>, par_strat: ParallelismStrategy) -> (Self, ReduceT)
+        (&self, right: &HashTrieMap<H, F, K, W, M, A>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: SetTransmute<ReduceT, RightOp>, par_strat: ParallelismStrategy) -> (Self, ReduceT)
         where
+        A: Measure<K, W>,
         Self: Sized,
-        ReduceT: Clone + Default + Send + Sync,
+        ReduceT: Default + Send + Sync,
         ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
         BothOp: Fn(&K, &V, &K, &W) -> MapTransformResult<V, ReduceT> + Clone,
         LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
@@ -180,10 +480,11 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> HashTrie
 
     /// Run a transform/transmute operation on each entry or pair of entries in the maps. Returns the transmuted map and a reduction of the secondary returns of the transmute operations. Can reuse nodes from the transformed map.
     pub async unsafe fn transform_with_transmuted<L: Key + HashLike<K>, W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>
-        (&self, right: &HashTrieMap<H, F, L, W, M>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, par_strat: ParallelismStrategy) -> (Self, ReduceT)
+        (&self, right: &HashTrieMap<H, F, L, W, M, A>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, par_strat: ParallelismStrategy) -> (Self, ReduceT)
         where
+        A: Measure<L, W>,
         Self: Sized,
-        ReduceT: Clone + Default + Send + Sync,
+        ReduceT: Default + Send + Sync,
         ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
         BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<V, ReduceT> + Clone,
         LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
@@ -200,10 +501,11 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> HashTrie
 
     /// Run a transmute/transform operation on each entry or pair of entries in the maps. Returns the transmuted map and a reduction of the secondary returns of the transmute operations. Can reuse nodes from the transformed map. Like transmute_with_transformed but enforces identity transformations on keys.
     pub async fn transfute_with_transformed<W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>
-        (&self, right: &HashTrieMap<H, F, K, W, M>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: SetTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, par_strat: ParallelismStrategy) -> (HashTrieMap<H, F, K, W, M>, ReduceT)
+        (&self, right: &HashTrieMap<H, F, K, W, M, A>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: SetTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, par_strat: ParallelismStrategy) -> (HashTrieMap<H, F, K, W, M, A>, ReduceT)
         where
+        A: Measure<K, W>,
         Self: Sized,
-        ReduceT: Clone + Default + Send + Sync,
+        ReduceT: Default + Send + Sync,
         ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
         BothOp: Fn(&K, &V, &K, &W) -> MapTransformResult<W, ReduceT> + Clone,
         LeftOp: Fn(&K, &V) -> SetTransmuteResult<W, ReduceT> + Clone + Send + Sync,
@@ -223,10 +525,11 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> HashTrie
 
     /// Run a transmute/transform operation on each entry or pair of entries in the maps. Returns the transmuted map and a reduction of the secondary returns of the transmute operations. Can reuse nodes from the transformed map.
     pub async unsafe fn transmute_with_transformed<L: Key + HashLike<K>, W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>
-        (&self, right: &HashTrieMap<H, F, L, W, M>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, par_strat: ParallelismStrategy) -> (HashTrieMap<H, F, L, W, M>, ReduceT)
+        (&self, right: &HashTrieMap<H, F, L, W, M, A>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, par_strat: ParallelismStrategy) -> (HashTrieMap<H, F, L, W, M, A>, ReduceT)
         where
+        A: Measure<L, W>,
         Self: Sized,
-        ReduceT: Clone + Default + Send + Sync,
+        ReduceT: Default + Send + Sync,
         ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
         BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<W, ReduceT> + Clone,
         LeftOp: Fn(&K, &V) -> MapTransmuteResult<L, W, ReduceT> + Clone + Send + Sync,
@@ -243,10 +546,12 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> HashTrie
 
     /// Run a transmute operation on each entry or pair of entries in the maps. Returns the transmuted map and a reduction of the secondary returns of the transmute operations.
     pub unsafe fn transmute_with_transmuted<L: Key + HashLike<K>, W: Value, S: Key + HashLike<K>, X: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>
-        (&self, right: &HashTrieMap<H, F, L, W, M>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>) -> (HashTrieMap<H, F, S, X, M>, ReduceT)
+        (&self, right: &HashTrieMap<H, F, L, W, M, A>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>) -> (HashTrieMap<H, F, S, X, M, A>, ReduceT)
         where
+        A: Measure<L, W>,
+        A: Measure<S, X>,
         Self: Sized,
-        ReduceT: Clone + Default + Send + Sync,
+        ReduceT: Default + Send + Sync,
         ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
         BothOp: Fn(&K, &V, &L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
         LeftOp: Fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT> + Clone,
@@ -268,15 +573,38 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> HashTrie
 
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> Default for HashTrieMap<H, F, K, V, M> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+impl <H: Hashword, F: Flagword<H>, G: Key, K: Key, V: Value, M: HasherBv<H, G> + HasherBv<H, K>, A: Measure<K, V>, B: Measure<G, HashTrieMap<H, F, K, V, M, A>>> HashTrieMap<H, F, G, HashTrieMap<H, F, K, V, M, A>, M, B> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    /// Merge the maps held as values into a single map, undoing `group_by`. If several of them hold the same key, which of its entries survives is unspecified.
+    pub fn flatten(&self) -> HashTrieMap<H, F, K, V, M, A> {
+        let mut entries = Vec::new();
+        for (_group, map) in self.entries() {
+            entries.extend(map.entries_with_hashes().into_iter().map(|(key, value, hash_value)| (hash_value, key.clone(), Some(value.clone()))));
+        }
+        HashTrieMap::build(entries)
+    }
+}
+
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> Clone for HashTrieMap<H, F, K, V, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    fn clone(&self) -> Self {
+        Self {set: self.set.clone()}
+    }
+}
+
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> Debug for HashTrieMap<H, F, K, V, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("HashTrieMap").field("set", &self.set).finish()
+    }
+}
+
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> Default for HashTrieMap<H, F, K, V, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> Eq for HashTrieMap<H, F, K, V, M> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {}
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> Eq for HashTrieMap<H, F, K, V, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {}
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> PartialEq for HashTrieMap<H, F, K, V, M> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> PartialEq for HashTrieMap<H, F, K, V, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
     fn eq(&self, other: &Self) -> bool {
         self.set == other.set
     }
@@ -284,10 +612,429 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> PartialE
 
 #[cfg(test)]
 mod tests {
-    use crate::{*, results::*, transformations::{new_map_joint_transform_generic, new_map_transform_generic, new_map_transform_removed, new_map_transform_transmute_generic, new_map_transmute_generic, new_map_transmute_removed, new_map_transmute_transform_generic, new_map_transmute_transmute_generic}};
+    use crate::{*, results::*, traits::{HasherBv, Measure, Monoid}, transformations::{new_map_joint_transform_generic, new_map_transform_generic, new_map_transform_removed, new_map_transform_transmute_generic, new_map_transmute_generic, new_map_transmute_removed, new_map_transmute_transform_generic, new_map_transmute_transmute_generic}};
     use futures_executor::block_on;
     use rand::Rng;
+    use core::{hash::Hasher, sync::atomic::{AtomicUsize, Ordering}};
+
+    #[cfg(feature = "cache_hash")]
+    #[test]
+    fn map_hash_memoization() {
+        static HASH_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Default)]
+        struct CountingHasher(fnv::FnvHasher);
+
+        impl Hasher for CountingHasher {
+            fn finish(&self) -> u64 {
+                HASH_COUNT.fetch_add(1, Ordering::Relaxed);
+                self.0.finish()
+            }
+
+            fn write(&mut self, bytes: &[u8]) {
+                self.0.write(bytes);
+            }
+        }
+
+        let mut mapa = HashTrieMap::<u64, u32, i32, i32, CountingHasher>::new();
+        let mut mapb = HashTrieMap::<u64, u32, i32, i32, CountingHasher>::new();
+
+        for i in 0..1000 {
+            mapa = mapa.insert(i, i, false).unwrap().0;
+            mapb = mapb.insert(i + 500, i, false).unwrap().0;
+        }
+        assert_eq!(HASH_COUNT.load(Ordering::Relaxed), 2000);
+
+        let merged = block_on(mapa.transform_with_transformed(&mapb, |_,_| (), new_map_joint_transform_generic(|_,_,_,_| MapJointTransformResult::UnchangedL(())), new_map_transform_generic(|_,_| MapTransformResult::Unchanged(())), new_map_transform_generic(|_,_| MapTransformResult::Unchanged(())), ParallelismStrategy::default_par())).0;
+        assert_eq!(merged.size(), 1500);
+        assert_eq!(HASH_COUNT.load(Ordering::Relaxed), 2000);
+
+        assert_eq!(HashTrieMapBuilder::from_map(&merged).build().size(), 1500);
+        assert_eq!(HASH_COUNT.load(Ordering::Relaxed), 2000);
+    }
     
+    #[test]
+    fn map_with_hash() {
+        let hasher = fnv::FnvHasher::default();
+        let mut map = DefaultHashTrieMap::<i32, i32>::new();
+
+        for i in 0..1000 {
+            map = map.insert_with_hash(hasher.hash(&i), i, i * i, false).unwrap().0;
+        }
+        for i in 0..1000 {
+            assert_eq!(map.find_with_hash(hasher.hash(&i), &i), Ok((&i, &(i * i))));
+            assert_eq!(map.find(&i), Ok((&i, &(i * i))));
+        }
+        for i in (0..1000).step_by(2) {
+            map = map.remove_with_hash(hasher.hash(&i), &i).unwrap().0;
+        }
+        assert_eq!(map.size(), 500);
+        assert_eq!(map.find_with_hash(hasher.hash(&0), &0), Err(HashTrieError::NotFound));
+        assert_eq!(map.find_with_hash(hasher.hash(&1), &1), Ok((&1, &1)));
+    }
+
+    #[test]
+    fn map_hash_prefix() {
+        let hasher = fnv::FnvHasher::default();
+        let mut map = DefaultHashTrieMap::<i32, i32>::new();
+        for i in 0..1000 {
+            map = map.insert(i, i, false).unwrap().0;
+        }
+
+        for bits in [0, 3, 5, 7, 12] {
+            let mask = (1_u64 << bits) - 1;
+            let prefix = 0b1011_0110_1101 & mask;
+            let mut found: Vec<i32> = map.entries_with_hash_prefix(prefix, bits).into_iter().map(|(k, _v)| *k).collect();
+            found.sort();
+            let expected: Vec<i32> = (0..1000).filter(|i| HasherBv::<u64, i32>::hash(&hasher, i) & mask == prefix).collect();
+            assert_eq!(found, expected);
+
+            let parts = map.split_by_hash_prefix(bits);
+            assert_eq!(parts.len(), 1 << bits);
+            assert_eq!(parts.iter().map(|part| part.size()).sum::<usize>(), 1000);
+            for i in 0..1000 {
+                let hash: u64 = hasher.hash(&i);
+                let part = &parts[(hash & mask) as usize];
+                assert_eq!(part.find(&i), Ok((&i, &i)));
+            }
+            assert_eq!(parts[prefix as usize].size(), found.len());
+            let part = parts[prefix as usize].insert(1000, 1000, false).unwrap().0.remove(&1000).unwrap().0;
+            assert_eq!(part.size(), found.len());
+        }
+    }
+
+    #[test]
+    fn map_split_root_concat() {
+        let mut map = DefaultHashTrieMap::<i32, i32>::new();
+        for i in 0..1000 {
+            map = map.insert(i, i, false).unwrap().0;
+        }
+
+        let parts = map.split_root();
+        assert_eq!(parts.len(), 32);
+        assert_eq!(parts.iter().map(|part| part.size()).sum::<usize>(), 1000);
+        let parts: Vec<_> = parts.into_iter().map(|part| {
+            let mut part = part;
+            for i in (0..1000).step_by(2) {
+                if let Ok((removed, _k, _v)) = part.remove(&i) {
+                    part = removed;
+                }
+            }
+            part
+        }).collect();
+        let odds = DefaultHashTrieMap::concat_disjoint(&parts);
+        assert_eq!(odds.size(), 500);
+        for i in 0..1000 {
+            assert_eq!(odds.find(&i).is_ok(), i % 2 == 1);
+        }
+
+        let rejoined = DefaultHashTrieMap::concat_disjoint(&map.split_by_hash_prefix(7));
+        assert_eq!(rejoined.size(), 1000);
+
+        let mut evens = DefaultHashTrieMap::<i32, i32>::new();
+        for i in (0..1000).step_by(2) {
+            evens = evens.insert(i, i, false).unwrap().0;
+        }
+        let merged = DefaultHashTrieMap::concat_disjoint(&[evens, odds]);
+        assert_eq!(merged.size(), 1000);
+        for i in 0..1000 {
+            assert_eq!(merged.find(&i), Ok((&i, &i)));
+            assert_eq!(rejoined.find(&i), Ok((&i, &i)));
+        }
+    }
+
+    #[test]
+    fn map_nth() {
+        let mut map = DefaultHashTrieMap::<i32, i32>::new();
+        for i in 0..1000 {
+            map = map.insert(i, i, false).unwrap().0;
+        }
+
+        let mut found: Vec<i32> = (0..1000).map(|index| *map.nth(index).unwrap().0).collect();
+        found.sort();
+        assert_eq!(found, (0..1000).collect::<Vec<i32>>());
+        assert_eq!(map.nth(1000), Err(HashTrieError::NotFound));
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn map_get_random() {
+        let mut rng = rand::thread_rng();
+        let mut map = DefaultHashTrieMap::<i32, i32>::new();
+        assert_eq!(map.get_random(&mut rng), Err(HashTrieError::NotFound));
+        for i in 0..1000 {
+            map = map.insert(i, i, false).unwrap().0;
+        }
+
+        for _ in 0..1000 {
+            let (k, v) = map.get_random(&mut rng).unwrap();
+            assert_eq!(map.find(k), Ok((k, v)));
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Total(i64);
+
+    impl Monoid for Total {
+        fn empty() -> Self {
+            Total(0)
+        }
+
+        fn combine(self, other: Self) -> Self {
+            Total(self.0 + other.0)
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    struct SumValues;
+
+    impl Measure<i32, i32> for SumValues {
+        type Summary = Total;
+
+        fn measure(_key: &i32, value: &i32) -> Total {
+            Total(*value as i64)
+        }
+    }
+
+    #[test]
+    fn map_summary() {
+        let mut map = HashTrieMap::<u64, u32, i32, i32, fnv::FnvHasher, SumValues>::new();
+        assert_eq!(map.summary(), Total(0));
+        for i in 0..1000 {
+            map = map.insert(i, i, false).unwrap().0;
+        }
+        assert_eq!(map.summary(), Total(499500));
+
+        for i in (0..1000).step_by(2) {
+            map = map.remove(&i).unwrap().0;
+        }
+        assert_eq!(map.summary(), Total(250000));
+
+        map = map.insert(1, 1001, true).unwrap().0;
+        assert_eq!(map.summary(), Total(251000));
+
+        let doubled = block_on(map.transform(|_,_| (), new_map_transform_generic(|_,v| MapTransformResult::Transformed(v * 2, ())), ParallelismStrategy::default_par())).0;
+        assert_eq!(doubled.summary(), Total(502000));
+
+        let merged = block_on(map.transform_with_transformed(&doubled, |_,_| (), new_map_joint_transform_generic(|_,v,_,w| MapJointTransformResult::Transformed(v + w, ())), new_map_transform_generic(|_,_| MapTransformResult::Unchanged(())), new_map_transform_generic(|_,_| MapTransformResult::Unchanged(())), ParallelismStrategy::default_par())).0;
+        assert_eq!(merged.summary(), Total(753000));
+    }
+
+    #[test]
+    fn map_debug() {
+        assert_eq!(format!("{:?}", DefaultHashTrieMap::<i32, i32>::new()), "HashTrieMap { set: HashTrie { root: C(CNode { nodes: BitIndexedArray { bits: 0, values: [], extra: 0 }, summary: () }) } }");
+
+        let map = HashTrieMap::<u64, u32, i32, i32, fnv::FnvHasher, SumValues>::new().insert(1, 2, false).unwrap().0;
+        assert_eq!(format!("{:?}", map), "HashTrieMap { set: HashTrie { root: C(CNode { nodes: BitIndexedArray { bits: 16, values: [S(SNode { key: 1, value: 2 })], extra: 1 }, summary: Total(2) }) } }");
+
+        let set = DefaultHashTrieSet::<i32>::new().insert(1, false).unwrap().0;
+        assert!(format!("{:?}", set).starts_with("HashTrieSet { set: HashTrie { root: C(CNode { nodes: BitIndexedArray { bits: "));
+        assert!(format!("{:?}", set).ends_with("values: [S(SNode { key: 1, value: () })], extra: 1 }, summary: () }) } }"));
+    }
+
+    #[test]
+    fn map_digest() {
+        type DigestMap = HashTrieMap<u64, u32, i32, i32, fnv::FnvHasher, digest::ContentDigest>;
+        let mut mapa = DigestMap::new();
+        let mut mapb = DigestMap::new();
+        for i in 0..1000 {
+            mapa = mapa.insert(i, i, false).unwrap().0;
+            mapb = mapb.insert(999 - i, 999 - i, false).unwrap().0;
+        }
+        mapb = mapb.insert(1000, 0, false).unwrap().0.remove(&1000).unwrap().0;
+        assert_eq!(mapa.root_digest(), mapb.root_digest());
+        assert!(mapa.diff(&mapb).is_empty());
+
+        mapb = mapb.insert(7, 70, true).unwrap().0.remove(&8).unwrap().0.insert(1000, 1000, false).unwrap().0;
+        assert_ne!(mapa.root_digest(), mapb.root_digest());
+        let mut diffs = mapa.diff(&mapb);
+        diffs.sort_by_key(|diff| match diff {
+            MapDiff::Added(k, _) | MapDiff::Removed(k, _) | MapDiff::Changed(k, _, _) => **k,
+        });
+        assert_eq!(diffs, vec![MapDiff::Changed(&7, &7, &70), MapDiff::Removed(&8, &8), MapDiff::Added(&1000, &1000)]);
+    }
+
+    #[test]
+    fn map_sync() {
+        type DigestMap = HashTrieMap<u64, u32, i32, i32, fnv::FnvHasher, digest::ContentDigest>;
+        let encode = |k: &i32, v: &i32, bytes: &mut Vec<u8>| {
+            bytes.extend_from_slice(&k.to_le_bytes());
+            bytes.extend_from_slice(&v.to_le_bytes());
+        };
+        let decode = |bytes: &mut &[u8]| {
+            let k = i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+            let v = i32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+            *bytes = &bytes[8..];
+            Some((k, v))
+        };
+        let pull = |from: &DigestMap, mut into: DigestMap| {
+            let (to_responder, requests) = std::sync::mpsc::channel::<Vec<u8>>();
+            let (to_requester, responses) = std::sync::mpsc::channel::<Vec<u8>>();
+            let mut request = Some(into.sync_request());
+            let mut rounds = 0;
+            while let Some(req) = request {
+                to_responder.send(req.to_bytes()).unwrap();
+                let req = sync::SyncRequest::from_bytes(&requests.recv().unwrap()).unwrap();
+                to_requester.send(from.sync_respond(&req).to_bytes(encode)).unwrap();
+                let resp = sync::SyncResponse::from_bytes(&responses.recv().unwrap(), decode).unwrap();
+                let applied = into.sync_apply(&resp);
+                into = applied.0;
+                request = applied.1;
+                rounds += 1;
+            }
+            (into, rounds)
+        };
+
+        let mut mapa = DigestMap::new();
+        let mut mapb = DigestMap::new();
+        for i in 0..1000 {
+            mapa = mapa.insert(i, i, false).unwrap().0;
+            mapb = mapb.insert(i + 500, i + 500, false).unwrap().0;
+        }
+        mapb = mapb.insert(600, 6000, true).unwrap().0;
+
+        let (mapb, rounds) = pull(&mapa, mapb);
+        assert!(rounds > 1);
+        assert_eq!(mapb.size(), 1500);
+        for i in 0..1500 {
+            assert_eq!(*mapb.find(&i).unwrap().1, i);
+        }
+
+        let (mapa, _) = pull(&mapb, mapa);
+        assert_eq!(mapa.root_digest(), mapb.root_digest());
+        assert_eq!(pull(&mapa, mapb).1, 1);
+
+        assert_eq!(sync::SyncRequest::from_bytes(&[1, 0, 0]), None);
+    }
+
+    #[test]
+    fn map_digest_colliding() {
+        type DigestMap = HashTrieMap<u64, u32, i32, i32, CollidingHasher, digest::ContentDigest>;
+        let mut mapa = DigestMap::new();
+        let mut mapb = DigestMap::new();
+        for i in 0..1000 {
+            mapa = mapa.insert(i, i, false).unwrap().0;
+            mapb = mapb.insert(999 - i, 999 - i, false).unwrap().0;
+        }
+        mapb = mapb.insert(1000, 0, false).unwrap().0.remove(&1000).unwrap().0;
+        assert_eq!(mapa.root_digest(), mapb.root_digest());
+        assert!(mapa.diff(&mapb).is_empty());
+
+        let hash_value = |key: &i32| HasherBv::<u64, i32>::hash(&CollidingHasher::default(), key);
+        let changed = (1..1000).find(|i| hash_value(i) == hash_value(&0)).unwrap();
+        mapb = mapb.insert(changed, -1, true).unwrap().0;
+        assert_ne!(mapa.root_digest(), mapb.root_digest());
+        assert_eq!(mapa.diff(&mapb), vec![MapDiff::Changed(&changed, &changed, &-1)]);
+
+        let mut request = Some(mapa.sync_request());
+        while let Some(req) = request {
+            let req = sync::SyncRequest::from_bytes(&req.to_bytes()).unwrap();
+            let (synced, next) = mapa.sync_apply(&mapb.sync_respond(&req));
+            mapa = synced;
+            request = next;
+        }
+        assert_eq!(*mapa.find(&changed).unwrap().1, -1);
+        assert_eq!(mapa.root_digest(), mapb.root_digest());
+        assert!(mapa.diff(&mapb).is_empty());
+    }
+
+    #[test]
+    fn map_merge3() {
+        let mut base = DefaultHashTrieMap::<i32, i32>::new();
+        for i in 0..1000 {
+            base = base.insert(i, i, false).unwrap().0;
+        }
+        let left = base.insert(1, 10, true).unwrap().0.remove(&2).unwrap().0.insert(1001, 1001, false).unwrap().0.insert(5, 50, true).unwrap().0;
+        let right = base.insert(3, 30, true).unwrap().0.remove(&4).unwrap().0.insert(1002, 1002, false).unwrap().0.insert(5, 500, true).unwrap().0.remove(&1).unwrap().0;
+
+        let conflicts = AtomicUsize::new(0);
+        let merged = DefaultHashTrieMap::merge3(&base, &left, &right, |_k, b, l, r| {
+            conflicts.fetch_add(1, Ordering::Relaxed);
+            assert!(b.is_some());
+            match (l, r) {
+                (Some(l), Some(r)) => Resolution::Value(l + r),
+                _ => Resolution::Left,
+            }
+        });
+        assert_eq!(conflicts.load(Ordering::Relaxed), 2);
+        assert_eq!(merged.size(), 1000);
+        assert_eq!(*merged.find(&1).unwrap().1, 10);
+        assert!(merged.find(&2).is_err());
+        assert_eq!(*merged.find(&3).unwrap().1, 30);
+        assert!(merged.find(&4).is_err());
+        assert_eq!(*merged.find(&5).unwrap().1, 550);
+        assert_eq!(*merged.find(&1001).unwrap().1, 1001);
+        assert_eq!(*merged.find(&1002).unwrap().1, 1002);
+        assert_eq!(*merged.find(&999).unwrap().1, 999);
+
+        assert!(DefaultHashTrieMap::merge3(&base, &base, &right, |_k, _b, _l, _r| panic!()) == right);
+
+        let colliding_base = HashTrieMap::<u64, u32, i32, i32, CollidingHasher>::new().insert(0, 0, false).unwrap().0;
+        let mut colliding_left = colliding_base.clone();
+        for i in 1..1000 {
+            colliding_left = colliding_left.insert(i, i, false).unwrap().0;
+        }
+        let colliding_right = colliding_base.insert(0, 1, true).unwrap().0.insert(1000, 1000, false).unwrap().0;
+        let colliding = HashTrieMap::merge3(&colliding_base, &colliding_left, &colliding_right, |_k, _b, _l, _r| panic!());
+        assert_eq!(colliding.size(), 1001);
+        assert_eq!(*colliding.find(&0).unwrap().1, 1);
+        for i in 1..=1000 {
+            assert_eq!(*colliding.find(&i).unwrap().1, i);
+        }
+
+        let set_base = DefaultHashTrieSet::<i32>::new().insert(1, false).unwrap().0.insert(2, false).unwrap().0;
+        let set_left = set_base.remove(&1).unwrap().0;
+        let set_right = set_base.insert(3, false).unwrap().0;
+        let set_merged = DefaultHashTrieSet::merge3(&set_base, &set_left, &set_right);
+        assert_eq!(set_merged.size(), 2);
+        assert!(set_merged.find(&1).is_err());
+    }
+
+    #[derive(Default)]
+    struct CollidingHasher(fnv::FnvHasher);
+
+    impl Hasher for CollidingHasher {
+        fn finish(&self) -> u64 {
+            self.0.finish() & 0xff
+        }
+
+        fn write(&mut self, bytes: &[u8]) {
+            self.0.write(bytes);
+        }
+    }
+
+    #[test]
+    fn map_builder() {
+        let mut map = HashTrieMap::<u64, u32, i32, i32, CollidingHasher>::new();
+        let mut builder = HashTrieMapBuilder::<u64, u32, i32, i32, CollidingHasher>::new();
+        for i in 0..1000 {
+            map = map.insert(i, i, false).unwrap().0;
+            builder.insert(i, -i);
+        }
+        for i in (0..1000).step_by(7) {
+            map = map.remove(&i).unwrap().0;
+            builder.insert(i, i).remove(i);
+        }
+        for i in 0..1000 {
+            builder.insert(i, i);
+        }
+        for i in (0..1000).step_by(7) {
+            builder.remove(i);
+        }
+        assert_eq!(builder.len(), 2429);
+
+        let built = builder.build();
+        assert_eq!(built.size(), map.size());
+        for i in 0..1000 {
+            assert_eq!(built.find(&i).ok(), map.find(&i).ok());
+        }
+        assert!(built.split_root().iter().zip(map.split_root().iter()).all(|(b, m)| b.size() == m.size()));
+
+        let rebuilt = HashTrieMapBuilder::from_map(&built).build();
+        assert_eq!(rebuilt.size(), built.size());
+        assert_eq!(HashTrieMapBuilder::<u64, u32, i32, i32, CollidingHasher>::new().build().size(), 0);
+    }
+
     #[test]
     fn map_transform() {
         let mut map = DefaultHashTrieMap::<i32, i32>::new();
@@ -310,6 +1057,423 @@ mod tests {
         }
     }
     
+    struct YieldOnce(bool);
+
+    impl core::future::Future for YieldOnce {
+        type Output = ();
+
+        fn poll(mut self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> core::task::Poll<()> {
+            if self.0 {
+                core::task::Poll::Ready(())
+            }
+            else {
+                self.0 = true;
+                cx.waker().wake_by_ref();
+                core::task::Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn map_transform_async() {
+        let mut map = DefaultHashTrieMap::<i32, i32>::new();
+        for i in 1..101 {
+            map = map.insert(i, i, false).unwrap().0;
+        }
+
+        let in_flight = AtomicUsize::new(0);
+        let most_in_flight = AtomicUsize::new(0);
+        let lookup = |k: &i32, v: &i32| {
+            let (k, v) = (*k, *v);
+            let (in_flight, most_in_flight) = (&in_flight, &most_in_flight);
+            async move {
+                most_in_flight.fetch_max(in_flight.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst);
+                YieldOnce(false).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                match k % 10 {
+                    0 => MapTransformResult::Removed(0),
+                    _ => MapTransformResult::Transformed(v * v, 1),
+                }
+            }
+        };
+
+        let sequential = block_on(map.transform_async(|l: &i32, r: &i32| l + r, lookup, ParallelismStrategy::Sequential));
+        assert_eq!(most_in_flight.load(Ordering::SeqCst), 1);
+        assert_eq!(sequential.0.size(), 90);
+        assert_eq!(sequential.1, 90);
+
+        let parallel = block_on(map.transform_async(|l: &i32, r: &i32| l + r, lookup, ParallelismStrategy::default_par()));
+        assert!(most_in_flight.load(Ordering::SeqCst) > 1);
+        assert_eq!(parallel.0.size(), 90);
+        for i in 1..101 {
+            match i % 10 {
+                0 => assert!(parallel.0.find(&i).is_err()),
+                _ => assert_eq!(*parallel.0.find(&i).unwrap().1, i * i),
+            }
+        }
+    }
+
+    #[test]
+    fn map_fold_par_reduce() {
+        let mut map = DefaultHashTrieMap::<i32, i32>::new();
+        assert_eq!(block_on(map.par_reduce(|_, v| *v, |l, r| l + r, ParallelismStrategy::default_par())), None);
+
+        for i in 1..1001 {
+            map = map.insert(i, i, false).unwrap().0;
+        }
+
+        assert_eq!(map.fold(0, |sum, _, v| sum + v), 500500);
+        assert_eq!(map.fold(Vec::new(), |mut keys, k, _| { keys.push(*k); keys }).len(), 1000);
+        assert_eq!(block_on(map.par_reduce(|_, v| *v, |l, r| l + r, ParallelismStrategy::Sequential)), Some(500500));
+        assert_eq!(block_on(map.par_reduce(|_, v| *v, |l, r| l + r, ParallelismStrategy::default_par())), Some(500500));
+        assert_eq!(block_on(map.par_reduce(|k, _| *k, i32::max, ParallelismStrategy::default_par())), Some(1000));
+    }
+
+    #[test]
+    fn map_combinators() {
+        let mut map = DefaultHashTrieMap::<i32, i32>::new();
+        for i in 1..1001 {
+            map = map.insert(i, i, false).unwrap().0;
+        }
+        let par_strat = ParallelismStrategy::default_par();
+
+        let evens = block_on(map.filter(|k, _| k % 2 == 0, par_strat));
+        assert_eq!(evens.size(), 500);
+        assert!(block_on(map.filter(|_, _| true, par_strat)) == map);
+
+        let mut retained = block_on(map.filter(|_, _| true, par_strat));
+        block_on(retained.retain(|k, _| *k <= 10, par_strat));
+        assert_eq!(retained.size(), 10);
+
+        let doubled = block_on(map.map_values(|_, v| v * 2, par_strat));
+        assert_eq!(*doubled.find(&7).unwrap().1, 14);
+        assert!(block_on(map.map_values(|_, v| *v, par_strat)) == map);
+
+        let halved = block_on(map.filter_map_values(|_, v| match v % 2 {
+            0 => Some(v / 2),
+            _ => None,
+        }, par_strat));
+        assert_eq!(halved.size(), 500);
+        assert_eq!(*halved.find(&8).unwrap().1, 4);
+
+        for par_strat in [ParallelismStrategy::Sequential, ParallelismStrategy::MiddleIndex] {
+            let (small, large) = block_on(map.partition(|k, _| *k <= 300, par_strat));
+            assert_eq!(small.size(), 300);
+            assert_eq!(large.size(), 700);
+            assert!(small.find(&300).is_ok() && small.find(&301).is_err());
+            assert!(large.find(&301).is_ok() && large.find(&300).is_err());
+
+            let (all, none) = block_on(map.partition(|_, _| true, par_strat));
+            assert!(all == map);
+            assert_eq!(none.size(), 0);
+        }
+    }
+
+    #[test]
+    fn map_group_by_flatten() {
+        let mut map = DefaultHashTrieMap::<i32, i32>::new();
+        for i in 0..1000 {
+            map = map.insert(i, i * 2, false).unwrap().0;
+        }
+
+        let groups = map.group_by(|_, v| v % 3);
+        assert_eq!(groups.size(), 3);
+        assert_eq!(groups.find(&0).unwrap().1.size(), 334);
+        assert_eq!(*groups.find(&2).unwrap().1.find(&1).unwrap().1, 2);
+        assert!(groups.find(&2).unwrap().1.find(&3).is_err());
+
+        let flattened = groups.flatten();
+        assert_eq!(flattened.size(), 1000);
+        for i in 0..1000 {
+            assert_eq!(*flattened.find(&i).unwrap().1, i * 2);
+        }
+
+        let keys = map.group_keys_by(|k, _| *k < 100);
+        assert_eq!(keys.find(&true).unwrap().1.size(), 100);
+        assert_eq!(keys.find(&false).unwrap().1.size(), 900);
+        assert_eq!(keys.flatten().size(), 1000);
+    }
+
+    #[test]
+    fn map_set_cross_container() {
+        let mut map = DefaultHashTrieMap::<i32, i32>::new();
+        let mut set = DefaultHashTrieSet::<i32>::new();
+        for i in 0..1000 {
+            map = map.insert(i, i * 2, false).unwrap().0;
+            if i % 3 == 0 {
+                set = set.insert(i + 500, false).unwrap().0;
+            }
+        }
+
+        let keys = map.keys_set();
+        assert_eq!(keys.size(), 1000);
+        for i in 0..1000 {
+            assert!(keys.find(&i).is_ok());
+        }
+
+        let restricted = block_on(map.restrict_keys(&set, ParallelismStrategy::default_par()));
+        let without = block_on(map.without_keys(&set, ParallelismStrategy::Sequential));
+        assert_eq!(restricted.size(), 167);
+        assert_eq!(without.size(), 833);
+        for i in 0..1000 {
+            let in_set = i >= 500 && (i - 500) % 3 == 0;
+            assert_eq!(restricted.find(&i).is_ok(), in_set);
+            assert_eq!(without.find(&i).is_ok(), !in_set);
+        }
+        assert_eq!(*restricted.find(&503).unwrap().1, 1006);
+        assert_eq!(*without.find(&502).unwrap().1, 1004);
+
+        let empty = DefaultHashTrieSet::<i32>::new();
+        assert!(block_on(map.without_keys(&empty, ParallelismStrategy::default_par())) == map);
+        assert_eq!(block_on(map.restrict_keys(&empty, ParallelismStrategy::default_par())).size(), 0);
+
+        let doubled = set.to_map_with(|k| k * 2);
+        assert_eq!(doubled.size(), set.size());
+        assert_eq!(*doubled.find(&503).unwrap().1, 1006);
+        assert!(doubled.find(&502).is_err());
+
+        type DigestMap = HashTrieMap<u64, u32, i32, i32, fnv::FnvHasher, digest::ContentDigest>;
+        let mut digest_map = DigestMap::new();
+        let mut plain_keys = HashTrieSet::<u64, u32, i32, fnv::FnvHasher>::new();
+        for i in 0..1000 {
+            digest_map = digest_map.insert(i, i, false).unwrap().0;
+            if i % 2 == 0 {
+                plain_keys = plain_keys.insert(i, false).unwrap().0;
+            }
+        }
+        let evens = block_on(digest_map.restrict_keys(&plain_keys, ParallelismStrategy::default_par()));
+        let odds = block_on(digest_map.without_keys(&plain_keys, ParallelismStrategy::Sequential));
+        assert_eq!((evens.size(), odds.size()), (500, 500));
+        assert!(evens.find(&998).is_ok() && evens.find(&999).is_err());
+        assert!(odds.find(&999).is_ok() && odds.find(&998).is_err());
+        assert_eq!(evens.keys_set().size(), 500);
+
+        let mut colliding = HashTrieMap::<u64, u32, i32, i32, CollidingHasher>::new();
+        let mut colliding_keys = HashTrieSet::<u64, u32, i32, CollidingHasher>::new();
+        for i in 0..1000 {
+            colliding = colliding.insert(i, i, false).unwrap().0;
+            if i % 5 == 0 {
+                colliding_keys = colliding_keys.insert(i, false).unwrap().0;
+            }
+        }
+        let restricted = block_on(colliding.restrict_keys(&colliding_keys, ParallelismStrategy::default_par()));
+        let without = block_on(colliding.without_keys(&colliding_keys, ParallelismStrategy::Sequential));
+        assert_eq!((restricted.size(), without.size()), (200, 800));
+        for i in 0..1000 {
+            assert_eq!(restricted.find(&i).is_ok(), i % 5 == 0);
+            assert_eq!(without.find(&i).is_ok(), i % 5 != 0);
+        }
+        let colliding_doubled = colliding_keys.to_map_with(|k| k * 2);
+        assert_eq!(colliding_doubled.size(), 200);
+        assert_eq!(*colliding_doubled.find(&995).unwrap().1, 1990);
+        assert_eq!(colliding.keys_set().size(), 1000);
+    }
+
+    #[test]
+    fn map_joins() {
+        let mut left = DefaultHashTrieMap::<i32, i32>::new();
+        let mut right = DefaultHashTrieMap::<i32, String>::new();
+        for i in 0..1000 {
+            left = left.insert(i, i * 2, false).unwrap().0;
+            right = right.insert(i + 500, (i + 500).to_string(), false).unwrap().0;
+        }
+
+        for par_strat in [ParallelismStrategy::default_par(), ParallelismStrategy::Sequential] {
+            let inner = block_on(left.inner_join(&right, par_strat));
+            assert_eq!(inner.size(), 500);
+            assert_eq!(*inner.find(&700).unwrap().1, (1400, "700".to_string()));
+            assert!(inner.find(&499).is_err());
+            assert!(inner.find(&1000).is_err());
+
+            let joined = block_on(left.left_join(&right, par_strat));
+            assert_eq!(joined.size(), 1000);
+            assert_eq!(*joined.find(&700).unwrap().1, (1400, Some("700".to_string())));
+            assert_eq!(*joined.find(&499).unwrap().1, (998, None));
+            assert!(joined.find(&1000).is_err());
+
+            let outer = block_on(left.outer_join(&right, par_strat));
+            assert_eq!(outer.size(), 1500);
+            assert_eq!(*outer.find(&700).unwrap().1, (Some(1400), Some("700".to_string())));
+            assert_eq!(*outer.find(&499).unwrap().1, (Some(998), None));
+            assert_eq!(*outer.find(&1000).unwrap().1, (None, Some("1000".to_string())));
+        }
+
+        let empty = DefaultHashTrieMap::<i32, String>::new();
+        assert_eq!(block_on(left.inner_join(&empty, ParallelismStrategy::default_par())).size(), 0);
+        assert_eq!(block_on(empty.outer_join(&left, ParallelismStrategy::default_par())).size(), 1000);
+
+        let mut colliding_left = HashTrieMap::<u64, u32, i32, i32, CollidingHasher>::new();
+        let mut colliding_right = HashTrieMap::<u64, u32, i32, i32, CollidingHasher>::new();
+        for i in 0..1000 {
+            colliding_left = colliding_left.insert(i, i, false).unwrap().0;
+            if i % 2 == 0 {
+                colliding_right = colliding_right.insert(i + 1, -i, false).unwrap().0;
+            }
+        }
+        let colliding = block_on(colliding_left.outer_join(&colliding_right, ParallelismStrategy::default_par()));
+        assert_eq!(colliding.size(), 1000);
+        assert_eq!(*colliding.find(&1).unwrap().1, (Some(1), Some(0)));
+        assert_eq!(*colliding.find(&2).unwrap().1, (Some(2), None));
+        assert_eq!(block_on(colliding_left.inner_join(&colliding_right, ParallelismStrategy::Sequential)).size(), 500);
+    }
+
+    #[test]
+    fn map_transform_many() {
+        let mut shards = Vec::new();
+        for shard in 0..16 {
+            let mut map = DefaultHashTrieMap::<i32, i32>::new();
+            for i in 0..100 {
+                map = map.insert(shard * 50 + i, 1, false).unwrap().0;
+            }
+            shards.push(map);
+        }
+
+        for par_strat in [ParallelismStrategy::default_par(), ParallelismStrategy::Sequential] {
+            let (merged, (calls, conflicts)) = block_on(DefaultHashTrieMap::transform_many(&shards, |a: &(usize, usize), b: &(usize, usize)| (a.0 + b.0, a.1 + b.1), |entries: &[(&i32, &i32)]| {
+                assert!(entries.iter().all(|(key, _value)| **key == *entries[0].0));
+                MapTransformResult::Transformed(entries.iter().map(|(_key, value)| **value).sum(), (1, (entries.len() > 1) as usize))
+            }, par_strat));
+            assert_eq!(merged.size(), 850);
+            assert_eq!(calls, 850);
+            assert_eq!(conflicts, 750);
+            assert_eq!(*merged.find(&0).unwrap().1, 1);
+            assert_eq!(*merged.find(&75).unwrap().1, 2);
+            assert_eq!(*merged.find(&849).unwrap().1, 1);
+        }
+
+        let (removed, ()) = block_on(DefaultHashTrieMap::transform_many(&shards[..2], |_,_| (), |entries: &[(&i32, &i32)]| match entries.len() {
+            1 => MapTransformResult::Unchanged(()),
+            _ => MapTransformResult::Removed(()),
+        }, ParallelismStrategy::default_par()));
+        assert_eq!(removed.size(), 100);
+        assert!(removed.find(&50).is_err());
+        assert!(removed.find(&149).is_ok());
+
+        let (single, ()) = block_on(DefaultHashTrieMap::transform_many(&shards[..1], |_,_| (), |_: &[(&i32, &i32)]| MapTransformResult::Unchanged(()), ParallelismStrategy::default_par()));
+        assert!(single == shards[0]);
+        let (single, ()) = block_on(DefaultHashTrieMap::transform_many(&shards[..1], |_,_| (), |entries: &[(&i32, &i32)]| match *entries[0].0 % 2 {
+            0 => MapTransformResult::Removed(()),
+            _ => MapTransformResult::Unchanged(()),
+        }, ParallelismStrategy::default_par()));
+        assert_eq!(single.size(), 50);
+        let (empty, ()) = block_on(DefaultHashTrieMap::transform_many(&[], |_,_| (), |_: &[(&i32, &i32)]| MapTransformResult::Removed(()), ParallelismStrategy::default_par()));
+        assert_eq!(empty.size(), 0);
+
+        let colliding: Vec<HashTrieMap<u64, u32, i32, i32, CollidingHasher>> = (0..4).map(|shard| {
+            let mut map = HashTrieMap::<u64, u32, i32, i32, CollidingHasher>::new();
+            for i in 0..500 {
+                map = map.insert(shard * 100 + i, shard, false).unwrap().0;
+            }
+            map
+        }).collect();
+        let (merged, ()) = block_on(HashTrieMap::transform_many(&colliding, |_,_| (), |entries: &[(&i32, &i32)]| MapTransformResult::Transformed(*entries.last().unwrap().1, ()), ParallelismStrategy::default_par()));
+        assert_eq!(merged.size(), 800);
+        assert_eq!(*merged.find(&50).unwrap().1, 0);
+        assert_eq!(*merged.find(&450).unwrap().1, 3);
+    }
+
+    #[test]
+    fn map_batch() {
+        let mut map = DefaultHashTrieMap::<i32, i32>::new();
+        for i in 0..1000 {
+            map = map.insert(i, i, false).unwrap().0;
+        }
+
+        let inserted = map.insert_many((500..1500).map(|i| (i, -i)).chain([(1200, 0), (2000, 1)]));
+        assert_eq!(inserted.size(), 1501);
+        assert_eq!(*inserted.find(&499).unwrap().1, 499);
+        assert_eq!(*inserted.find(&500).unwrap().1, -500);
+        assert_eq!(*inserted.find(&1200).unwrap().1, 0);
+        assert_eq!(*inserted.find(&2000).unwrap().1, 1);
+        assert_eq!(map.size(), 1000);
+
+        let removed = inserted.remove_many((0..1500).step_by(3).chain([3000]));
+        assert_eq!(removed.size(), 1001);
+        for i in 0..1500 {
+            assert_eq!(removed.find(&i).is_ok(), i % 3 != 0);
+        }
+
+        let found = removed.get_many(&[1, 3, 1400, 1401, 2000, -1]);
+        assert_eq!(found.iter().map(|result| result.as_ref().ok().map(|(_key, value)| **value)).collect::<Vec<_>>(), vec!(Some(1), None, Some(-1400), None, Some(1), None));
+
+        assert!(map.insert_many(Vec::new()) == map);
+        assert_eq!(map.remove_many(0..1000).size(), 0);
+        assert_eq!(DefaultHashTrieMap::<i32, i32>::new().insert_many((0..1000).map(|i| (i, i))).size(), 1000);
+
+        let mut colliding = HashTrieMap::<u64, u32, i32, i32, CollidingHasher>::new();
+        for i in 0..1000 {
+            colliding = colliding.insert(i, i, false).unwrap().0;
+        }
+        let colliding = colliding.insert_many((900..1100).map(|i| (i, -i))).remove_many((0..1100).step_by(2));
+        assert_eq!(colliding.size(), 550);
+        assert_eq!(*colliding.find(&901).unwrap().1, -901);
+        assert_eq!(colliding.get_many(&[1, 2, 1099]).iter().filter(|result| result.is_ok()).count(), 2);
+    }
+
+    #[test]
+    fn map_try_transform() {
+        let mut map = DefaultHashTrieMap::<i32, i32>::new();
+        let mut other = DefaultHashTrieMap::<i32, i32>::new();
+
+        for i in 1..101 {
+            map = map.insert(i, i, false).unwrap().0;
+            other = other.insert(i + 50, i, false).unwrap().0;
+        }
+
+        let tsquared = block_on(map.try_transform(|l: &i32, r: &i32| l + r, |_, v| Ok::<_, ()>(MapTransformResult::Transformed(v * v, 1)), ParallelismStrategy::default_par())).unwrap();
+        assert_eq!(tsquared.1, 100);
+        for i in 1..101 {
+            assert_eq!(i * i, *tsquared.0.find(&i).unwrap().1);
+        }
+
+        let calls = AtomicUsize::new(0);
+        let failed = block_on(map.try_transform(|_,_| (), |k, _v| {
+            calls.fetch_add(1, Ordering::Relaxed);
+            match *k {
+                50 => Err(50),
+                _ => Ok(MapTransformResult::Unchanged(())),
+            }
+        }, ParallelismStrategy::Sequential));
+        assert_eq!(failed.err(), Some(50));
+        assert!(calls.load(Ordering::Relaxed) < 100);
+
+        let joined = block_on(map.try_transform_with_transformed(&other, |l: &i32, r: &i32| l + r,
+            |_, v, _, w| Ok::<_, ()>(MapJointTransformResult::Transformed(v + w, 1)),
+            |_, _| Ok(MapTransformResult::Unchanged(0)),
+            |_, _| Ok(MapTransformResult::Removed(0)),
+            ParallelismStrategy::default_par())).unwrap();
+        assert_eq!(joined.0.size(), 100);
+        assert_eq!(joined.1, 50);
+        assert_eq!(*joined.0.find(&10).unwrap().1, 10);
+        assert_eq!(*joined.0.find(&60).unwrap().1, 70);
+
+        let failed = block_on(map.try_transform_with_transformed(&other, |_,_| (),
+            |k, _, _, _| match *k {
+                75 => Err(75),
+                _ => Ok(MapJointTransformResult::UnchangedLR(())),
+            },
+            |_, _| Ok(MapTransformResult::Unchanged(())),
+            |_, _| Ok(MapTransformResult::Unchanged(())),
+            ParallelismStrategy::default_par()));
+        assert_eq!(failed.err(), Some(75));
+
+        let single = HashTrieMap::<u64, u32, i32, i32, CollidingHasher>::new().insert(0, 1, false).unwrap().0;
+        let mut colliding = HashTrieMap::<u64, u32, i32, i32, CollidingHasher>::new();
+        for i in 0..1000 {
+            colliding = colliding.insert(i, i, false).unwrap().0;
+        }
+        let joined = block_on(single.try_transform_with_transformed(&colliding, |l: &i32, r: &i32| l + r,
+            |_, v, _, w| Ok::<_, ()>(MapJointTransformResult::Transformed(v + w, 1)),
+            |_, _| Ok(MapTransformResult::Unchanged(0)),
+            |_, _| Ok(MapTransformResult::Unchanged(0)),
+            ParallelismStrategy::default_par())).unwrap();
+        assert_eq!(joined.0.size(), 1000);
+        assert_eq!(joined.1, 1);
+        for i in 0..1000 {
+            assert_eq!(*joined.0.find(&i).unwrap().1, if i == 0 {1} else {i});
+        }
+    }
+
     #[test]
     fn map_transmute() {
         let mut map = DefaultHashTrieMap::<i32, i32>::new();
@@ -332,6 +1496,37 @@ mod tests {
         }
     }
     
+    #[test]
+    fn map_joint_transformations_colliding() {
+        #[derive(Default)]
+        struct ZeroHasher;
+
+        impl core::hash::Hasher for ZeroHasher {
+            fn finish(&self) -> u64 {
+                0
+            }
+
+            fn write(&mut self, _bytes: &[u8]) {}
+        }
+
+        let mut mapa = HashTrieMap::<u64, u32, i32, i32, ZeroHasher>::new();
+        let mut mapb = HashTrieMap::<u64, u32, i32, i32, ZeroHasher>::new();
+
+        for i in 0..4 {
+            mapa = mapa.insert(i, i, false).unwrap().0;
+            mapb = mapb.insert(i + 2, i, false).unwrap().0;
+        }
+
+        let joined = block_on(mapa.transform_with_transformed(&mapb, |l,r| -> i32 {l.wrapping_add(*r)},
+            new_map_joint_transform_generic(|_,v:&i32,_,w| MapJointTransformResult::Transformed(v + w, 1)), new_map_transform_generic(|_,_| MapTransformResult::Unchanged(0)), new_map_transform_generic(|_,_| MapTransformResult::Unchanged(0)), ParallelismStrategy::default_par()));
+
+        assert_eq!(joined.0.size(), 6);
+        assert_eq!(joined.1, 2);
+        assert_eq!(*joined.0.find(&2).unwrap().1, 2);
+        assert_eq!(*joined.0.find(&3).unwrap().1, 4);
+        assert_eq!(*joined.0.find(&5).unwrap().1, 3);
+    }
+
     #[test]
     fn map_joint_transformations() {
         let mut mapa = DefaultHashTrieMap::<i32, i32>::new();
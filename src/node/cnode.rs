@@ -1,19 +1,25 @@
 use crate::{bit_indexed_array::*, flag::*, transformations::*, results::*, traits::*, ParallelismStrategy};
-use super::{lnode::{self, *}, mnode::*, snode::{self, *}};
-use alloc::{boxed::Box, borrow::Cow, fmt::Debug, sync::Arc, vec::Vec};
+use super::{leaf_hash::LeafHash, lnode::{self, *}, mnode::*, snode::{self, *}};
+use alloc::{boxed::Box, borrow::Cow, collections::BTreeSet, fmt::Debug, sync::Arc, vec::Vec};
 use async_recursion::async_recursion;
-use core::{ops::Range, ptr};
-use futures_util::join;
+use core::{future::Future, ops::Range, ptr};
+use futures_util::{join, try_join};
 
-#[derive(Debug)]
-pub(crate) struct CNode <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static> {
-    nodes: Arc<dyn BitIndexedArray::<F, MNode<H, F, K, V, M>, usize>>,
+pub(crate) struct CNode <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>> {
+    nodes: Arc<dyn BitIndexedArray::<F, MNode<H, F, K, V, M, A>, usize>>,
+    summary: A::Summary,
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> CNode<H, F, K, V, M> {
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> CNode<H, F, K, V, M, A> {
     #[must_use]
-    pub(super) fn new(nodes: Box<dyn BitIndexedArray::<F, MNode<H, F, K, V, M>, usize> + 'static>) -> Self {
-        Self { nodes: nodes.into() }
+    pub(super) fn new(nodes: Box<dyn BitIndexedArray::<F, MNode<H, F, K, V, M, A>, usize> + 'static>) -> Self {
+        let summary = A::measure_branch((0..F::max_ones()).filter_map(|index| nodes.at_bit_index(index).ok().map(|node| (index, node.summary()))));
+        Self { nodes: nodes.into(), summary }
+    }
+
+    #[must_use]
+    pub(super) fn summary(&self) -> &A::Summary {
+        &self.summary
     }
     
     #[must_use]
@@ -25,14 +31,14 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> CNode<H,
         match self.nodes.at(flag.as_ref().unwrap().flag.clone()) {
             Ok(node) => match node {
                 MNode::C(cnode) => cnode.find(key, flag.unwrap().next()),
-                MNode::L(lnode) => lnode.find(key),
-                MNode::S(snode) => snode.find(key),
+                MNode::L(lnode) => lnode.find(key, &flag.unwrap().hash_value()),
+                MNode::S(snode) => snode.find(key, &flag.unwrap().hash_value()),
             },
             Err(_) => FindResult::NotFound
         }
     }
 
-    pub(super) fn remove<'a, L: Key>(&'a self, key: &L, flag: Option<Flag<H, F>>) -> RemoveResult<'a, H, F, K, V, M> where K: PartialEq<L>, M: HasherBv<H, L>, <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    pub(super) fn remove<'a, L: Key>(&'a self, key: &L, flag: Option<Flag<H, F>>) -> RemoveResult<'a, H, F, K, V, M, A> where K: PartialEq<L>, M: HasherBv<H, L>, <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
         match self.nodes.at(flag.as_ref().unwrap().flag.clone()) {
             Ok(node) => match node.remove(key, flag.as_ref().unwrap().next()) {
                 RemoveResult::NotFound => RemoveResult::NotFound,
@@ -57,100 +63,856 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> CNode<H,
                     }
                 },
             },
-            Err(_) => RemoveResult::NotFound
+            Err(_) => RemoveResult::NotFound
+        }
+    }
+    
+    pub(super) fn visit<Op: Clone>(&self, op: Op) where Op: Fn(&K, &V) {
+        for node in self.nodes.as_ref() {
+            match node {
+                MNode::C(cnode) => cnode.visit(op.clone()),
+                MNode::L(lnode) => lnode.visit(op.clone()),
+                MNode::S(snode) => snode.visit(op.clone()),
+            }
+        }
+    }
+
+    pub(super) fn fold<T, Op>(&self, init: T, op: &mut Op) -> T where Op: FnMut(T, &K, &V) -> T, <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+        let mut folded = init;
+        for node in self.nodes.as_ref() {
+            folded = node.fold(folded, op);
+        }
+        folded
+    }
+
+    pub(super) fn nth(&self, index: usize) -> FindResult<'_, K, V> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+        let mut index = index;
+        for node in self.nodes.as_ref() {
+            if index < node.size() {
+                return node.nth(index);
+            }
+            index -= node.size();
+        }
+        FindResult::NotFound
+    }
+
+    pub(super) fn map_entries<X: Value, B: Measure<K, X>, Op: Fn(&K, &V) -> X>(&self, op: &Op) -> CNode<H, F, K, X, M, B> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+        let values: Vec<MNode<H, F, K, X, M, B>> = self.nodes.as_ref().into_iter().map(|node| node.map_entries(op)).collect();
+        CNode::new(new_bit_indexed_array(self.nodes.bits(), BitIndexedArrayVec::new(&values), self.size()).unwrap())
+    }
+
+    pub(super) fn entries_with_hash_prefix<'a>(&'a self, prefix: Option<Flag<H, F>>, bits: usize, entries: &mut Vec<(&'a K, &'a V)>) where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+        if bits == 0 {
+            for node in self.nodes.as_ref() {
+                node.entries_with_hash_prefix(None, 0, entries);
+            }
+            return;
+        }
+        let prefix = prefix.unwrap();
+        let width = F::log_b().min(bits);
+        let mask = (1 << width) - 1;
+        let wanted = prefix.index() & mask;
+        for index in 0..F::max_ones() {
+            if index & mask == wanted {
+                if let Ok(node) = self.nodes.at_bit_index(index) {
+                    node.entries_with_hash_prefix(if bits > width {prefix.next()} else {None}, bits - width, entries);
+                }
+            }
+        }
+    }
+
+    pub(super) fn find_many<'a, L: Key>(&'a self, queries: &[(usize, H, &L)], depth: usize, found: &mut [Option<(&'a K, &'a V)>]) where K: PartialEq<L>, <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+        let mut start = 0;
+        while start < queries.len() {
+            let index = Flag::<H, F>::new_at_depth(queries[start].1.clone(), depth).unwrap().index();
+            let end = start + queries[start..].iter().take_while(|(_position, hash_value, _key)| Flag::<H, F>::new_at_depth(hash_value.clone(), depth).unwrap().index() == index).count();
+            if let Ok(node) = self.nodes.at_bit_index(index) {
+                node.find_many(&queries[start..end], depth + 1, found);
+            }
+            start = end;
+        }
+    }
+
+    pub(super) fn child(&self, index: usize) -> Option<&MNode<H, F, K, V, M, A>> {
+        self.nodes.at_bit_index(index).ok()
+    }
+
+    pub(super) fn entries_with_hashes<'a>(&'a self, entries: &mut Vec<(&'a K, &'a V, H)>) where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+        for node in self.nodes.as_ref() {
+            node.entries_with_hashes(entries);
+        }
+    }
+
+    pub(super) fn measure_memory(&self, seen: &mut BTreeSet<usize>, usage: &mut MemoryUsage) where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+        if seen.insert(Arc::as_ptr(&self.nodes) as *const () as usize) {
+            usage.nodes += 1;
+            usage.bytes += core::mem::size_of_val(self.nodes.as_ref());
+            for node in self.nodes.as_ref() {
+                node.measure_memory(seen, usage);
+            }
+        }
+    }
+
+    pub(super) fn diff<'a, Same: Fn(&MNode<H, F, K, V, M, A>, &MNode<H, F, K, V, M, A>) -> bool>(&'a self, right: &'a Self, same: &Same, diffs: &mut Vec<MapDiff<'a, K, V>>) where V: PartialEq, <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+        for index in 0..F::max_ones() {
+            match (self.nodes.at_bit_index(index), right.nodes.at_bit_index(index)) {
+                (Ok(left), Ok(right)) => left.diff(right, same, diffs),
+                (Ok(left), Err(_)) => {
+                    let mut entries = Vec::new();
+                    left.entries_with_hash_prefix(None, 0, &mut entries);
+                    diffs.extend(entries.into_iter().map(|(key, value)| MapDiff::Removed(key, value)));
+                },
+                (Err(_), Ok(right)) => {
+                    let mut entries = Vec::new();
+                    right.entries_with_hash_prefix(None, 0, &mut entries);
+                    diffs.extend(entries.into_iter().map(|(key, value)| MapDiff::Added(key, value)));
+                },
+                (Err(_), Err(_)) => {},
+            }
+        }
+    }
+
+    pub(super) fn split_root(&self) -> Vec<Self> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+        let mut parts = Vec::new();
+        for index in 0..F::max_ones() {
+            if let Ok(node) = self.nodes.at_bit_index(index) {
+                parts.push(Self::new(new_bit_indexed_array(<F>::nth_bit(index).unwrap(), BitIndexedArrayVec::new(core::slice::from_ref(node)), node.size()).unwrap()));
+            }
+        }
+        parts
+    }
+
+    pub(super) fn concat_disjoint(&self, right: &Self, depth: usize) -> Self where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+        let mut size = 0;
+        let mut bits_t = F::default();
+        let mut values_t = Vec::default();
+
+        for index in 0..F::max_ones() {
+            let node = match (self.nodes.at_bit_index(index), right.nodes.at_bit_index(index)) {
+                (Ok(left), Ok(right)) => left.concat_disjoint(right, depth + 1),
+                (Ok(left), Err(_)) => left.clone(),
+                (Err(_), Ok(right)) => right.clone(),
+                (Err(_), Err(_)) => continue,
+            };
+            size += node.size();
+            bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
+            values_t.push(node);
+        }
+
+        Self::new(new_bit_indexed_array(bits_t, BitIndexedArrayVec::new(&values_t), size).unwrap())
+    }
+
+    pub(super) fn split_by_hash_prefix(&self, depth: usize, bits: usize) -> Vec<MNode<H, F, K, V, M, A>> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+        let width = F::log_b().min(bits);
+        let mask = (1 << width) - 1;
+        let mut parts = Vec::with_capacity(1 << bits);
+        parts.resize_with(1 << bits, || (0, F::default(), Vec::new()));
+
+        for index in 0..F::max_ones() {
+            if let Ok(node) = self.nodes.at_bit_index(index) {
+                for (high, part) in node.split_by_hash_prefix(depth + 1, bits - width).into_iter().enumerate() {
+                    if part.size() != 0 {
+                        let (size, bits_t, values_t) = &mut parts[(index & mask) | (high << width)];
+                        *size += part.size();
+                        *bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
+                        values_t.push(part);
+                    }
+                }
+            }
+        }
+
+        parts.into_iter().map(|(size, bits_t, mut values_t)| {
+            if depth != 0 && values_t.len() == 1 && !matches!(values_t[0], MNode::C(_)) {
+                values_t.pop().unwrap()
+            }
+            else {
+                MNode::C(Self::new(new_bit_indexed_array(bits_t, BitIndexedArrayVec::new(&values_t), size).unwrap()))
+            }
+        }).collect()
+    }
+
+}
+
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> CNode<H, F, K, V, M, A> {
+    pub(super) fn insert<'a, L: Key + Into<K>, W: Into<V>>(&'a self, key: L, value: W, flag: Option<Flag<H, F>>, replace: bool) -> CNodeInsertResult<'a, H, F, K, V, M, A>
+    where
+        K: HashLike<L>,
+        K: PartialEq<L>,
+        M: HasherBv<H, L>,
+        <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
+    {
+        match self.nodes.at(flag.as_ref().unwrap().flag.clone()) {
+            Ok(node) => match node {
+                MNode::C(cnode) => match cnode.insert(key, value, flag.as_ref().unwrap().next(), replace) {
+                    CNodeInsertResult::Found(key, value) => CNodeInsertResult::Found(key, value),
+                    CNodeInsertResult::InsertedC(cnode2, key, value, prev) => {
+                        let size = self.size() + cnode2.size() - cnode.size();
+                        CNodeInsertResult::InsertedC(Self::new(self.nodes.updated(flag.unwrap().flag, Cow::Owned(MNode::C(cnode2)), Cow::Owned(size)).unwrap()), key, value, prev)
+                    },
+                },
+                MNode::L(lnode) => match lnode::insert(&lnode, key, value, flag.as_ref().unwrap().next(), replace) {
+                    LNodeInsertResult::Found(key, value) => CNodeInsertResult::Found(key, value),
+                    LNodeInsertResult::InsertedC(cnode, key, value, prev) => CNodeInsertResult::InsertedC(Self::new(self.nodes.updated(flag.unwrap().flag, Cow::Owned(MNode::C(cnode)), Cow::Owned(self.size() + 1)).unwrap()), key, value, prev),
+                    LNodeInsertResult::InsertedL(lnode2, key, value, prev) => {
+                        let size = self.size() + lnode2.size() - lnode.size();
+                        CNodeInsertResult::InsertedC(Self::new(self.nodes.updated(flag.unwrap().flag, Cow::Owned(MNode::L(lnode2)), Cow::Owned(size)).unwrap()), key, value, prev)
+                    },
+                },
+                MNode::S(snode) => match snode::insert(&snode, key, value, flag.as_ref().unwrap().next(), replace) {
+                    InsertResult::Found(key, value) => CNodeInsertResult::Found(key, value),
+                    InsertResult::InsertedC(cnode, key, value, prev) => CNodeInsertResult::InsertedC(Self::new(self.nodes.updated(flag.unwrap().flag, Cow::Owned(MNode::C(cnode)), Cow::Owned(self.size() + 1)).unwrap()), key, value, prev),
+                    InsertResult::InsertedL(lnode, key, value, prev) => CNodeInsertResult::InsertedC(Self::new(self.nodes.updated(flag.unwrap().flag, Cow::Owned(MNode::L(lnode)), Cow::Owned(self.size() + 1)).unwrap()), key, value, prev),
+                    InsertResult::InsertedS(snode, key, value, prev) => CNodeInsertResult::InsertedC(Self::new(self.nodes.updated(flag.unwrap().flag, Cow::Owned(MNode::S(snode)), Cow::Owned(self.size())).unwrap()), key, value, prev),
+                },
+            },
+            Err(_) => {
+                let snode = SNode::new(key.into(), value.into(), LeafHash::new(flag.as_ref().unwrap().hash_value()));
+                let key: *const K = snode.key();
+                let value: *const V = snode.value();
+                CNodeInsertResult::InsertedC(Self::new(self.nodes.inserted(flag.unwrap().flag, Cow::Owned(MNode::S(snode)), Cow::Owned(self.size() + 1)).unwrap()), key, value, None)
+            }
+        }
+    }
+}
+
+#[async_recursion(?Send)]
+pub(super) async fn transform<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A, ReduceT, ReduceOp, Op>(this: &CNode<H, F, K, V, M, A>, reduce_op: ReduceOp, op: MapTransform<ReduceT, Op>, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, K, V, M, A, ReduceT>
+where
+    A: Measure<K, V>,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
+    Op: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
+    <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
+{
+    match op {
+        MapTransform::Generic(_) => {},
+        MapTransform::Unchanged(r) => return MNodeTransformResult::Unchanged(r),
+        MapTransform::Removed(r) => return MNodeTransformResult::Removed(r),
+    }
+
+    let (size, bits, mut values, unchanged, reduced) = {
+        match par_strat {
+            ParallelismStrategy::MiddleIndex => {
+                let op_clone = op.fork();
+
+                let left = transform_impl(this, reduce_op.clone(), op, 0..F::max_ones()/2, par_strat);
+                let right = transform_impl(this, reduce_op.clone(), op_clone, F::max_ones()/2..F::max_ones(), par_strat);
+
+                let (left, right) = join!(left, right);
+
+                let (lsize, lbits, mut lvalues, luc, rl) = left;
+                let (rsize, rbits, mut rvalues, ruc, rr) = right;
+
+                lvalues.append(&mut rvalues);
+        
+                (lsize + rsize, lbits.bit_merge(rbits).unwrap(), lvalues, luc && ruc, reduce_op.reduce(rl, rr))
+            },
+            ParallelismStrategy::Sequential => transform_impl(this, reduce_op, op, 0..F::max_ones(), par_strat).await
+        }
+    };
+
+    if unchanged {
+        MNodeTransformResult::Unchanged(reduced)
+    }
+    else {
+        match values.len() {
+            0 => MNodeTransformResult::Removed(reduced),
+            1 => match values.pop().unwrap() {
+                MNode::C(cnode) => MNodeTransformResult::C(CNode::new(new_bit_indexed_array(bits, BitIndexedArrayVec::new(&[MNode::C(cnode)]), size).unwrap()), reduced),
+                MNode::L(lnode) => MNodeTransformResult::L(lnode, reduced),
+                MNode::S(snode) => MNodeTransformResult::S(snode, reduced),
+            },
+            _ => MNodeTransformResult::C(CNode::new(new_bit_indexed_array(bits, BitIndexedArrayVec::new(&values), size).unwrap()), reduced),
+        }
+    }
+}
+
+
+async fn transform_impl<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>, ReduceT, ReduceOp, Op>(this: &CNode<H, F, K, V, M, A>, reduce_op: ReduceOp, op: MapTransform<ReduceT, Op>, range: Range<usize>, par_strat: ParallelismStrategy) -> (usize, F, Vec<MNode<H, F, K, V, M, A>>, bool, ReduceT)
+where
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
+    Op: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
+    <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
+{
+    let mut size = 0;
+    let mut bits_t = F::default();
+    let mut values_t = Vec::default();
+    let mut unchanged = true;
+    let mut reduced = ReduceT::default();
+
+    for index in range {
+        if let Ok(node) = this.nodes.at_bit_index(index) {
+            match node.transform(reduce_op.clone(), op.fork(), par_strat).await {
+                MNodeTransformResult::Unchanged(r) => {
+                    size += node.size();
+                    bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
+                    values_t.push(node.clone());
+                    reduced = reduce_op.reduce(reduced, r);
+                },
+                MNodeTransformResult::C(cnode, r) => {
+                    size += cnode.size();
+                    bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
+                    values_t.push(MNode::C(cnode));
+                    reduced = reduce_op.reduce(reduced, r);
+                    unchanged = false;
+                },
+                MNodeTransformResult::L(lnode, r) => {
+                    size += lnode.size();
+                    bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
+                    values_t.push(MNode::L(lnode));
+                    reduced = reduce_op.reduce(reduced, r);
+                    unchanged = false;
+                },
+                MNodeTransformResult::S(snode, r) => {
+                    size += 1;
+                    bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
+                    values_t.push(MNode::S(snode));
+                    reduced = reduce_op.reduce(reduced, r);
+                    unchanged = false;
+                },
+                MNodeTransformResult::Removed(r) => {
+                    reduced = reduce_op.reduce(reduced, r);
+                    unchanged = false;
+                },
+            }
+        }
+    }
+
+    (size, bits_t, values_t, unchanged, reduced)
+}
+
+pub(crate) struct JointOps<'a, ReduceOp, BothOp, LeftOp, RightOp> {
+    pub(crate) reduce_op: &'a ReduceOp,
+    pub(crate) both_op: &'a BothOp,
+    pub(crate) left_op: &'a LeftOp,
+    pub(crate) right_op: &'a RightOp,
+}
+
+impl <ReduceOp, BothOp, LeftOp, RightOp> Clone for JointOps<'_, ReduceOp, BothOp, LeftOp, RightOp> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl <ReduceOp, BothOp, LeftOp, RightOp> Copy for JointOps<'_, ReduceOp, BothOp, LeftOp, RightOp> {}
+
+type TransformOps<'a, ReduceT, ReduceOp, BothOp, LeftOp, RightOp> = JointOps<'a, ReduceOp, MapJointTransform<ReduceT, BothOp>, MapTransform<ReduceT, LeftOp>, MapTransform<ReduceT, RightOp>>;
+
+struct PartitionSide<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>> {
+    size: usize,
+    bits: F,
+    values: Vec<MNode<H, F, K, V, M, A>>,
+    unchanged: bool,
+}
+
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> PartitionSide<H, F, K, V, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    fn new() -> Self {
+        Self {size: 0, bits: F::default(), values: Vec::default(), unchanged: true}
+    }
+
+    fn push(&mut self, index: usize, node: &MNode<H, F, K, V, M, A>, result: MNodeTransformResult<H, F, K, V, M, A, ()>) {
+        let node = match result {
+            MNodeTransformResult::Unchanged(()) => node.clone(),
+            MNodeTransformResult::C(cnode, ()) => {
+                self.unchanged = false;
+                MNode::C(cnode)
+            },
+            MNodeTransformResult::L(lnode, ()) => {
+                self.unchanged = false;
+                MNode::L(lnode)
+            },
+            MNodeTransformResult::S(snode, ()) => {
+                self.unchanged = false;
+                MNode::S(snode)
+            },
+            MNodeTransformResult::Removed(()) => {
+                self.unchanged = false;
+                return;
+            },
+        };
+        self.push_node(index, node);
+    }
+
+    fn push_node(&mut self, index: usize, node: MNode<H, F, K, V, M, A>) {
+        self.size += node.size();
+        self.bits = self.bits.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
+        self.values.push(node);
+    }
+
+    fn append(mut self, mut other: Self) -> Self {
+        self.size += other.size;
+        self.bits = self.bits.bit_merge(other.bits).unwrap();
+        self.values.append(&mut other.values);
+        self.unchanged = self.unchanged && other.unchanged;
+        self
+    }
+
+    fn into_result(mut self) -> MNodeTransformResult<H, F, K, V, M, A, ()> {
+        if self.unchanged {
+            MNodeTransformResult::Unchanged(())
+        }
+        else {
+            match self.values.len() {
+                0 => MNodeTransformResult::Removed(()),
+                1 => match self.values.pop().unwrap() {
+                    MNode::C(cnode) => MNodeTransformResult::C(CNode::new(new_bit_indexed_array(self.bits, BitIndexedArrayVec::new(&[MNode::C(cnode)]), self.size).unwrap()), ()),
+                    MNode::L(lnode) => MNodeTransformResult::L(lnode, ()),
+                    MNode::S(snode) => MNodeTransformResult::S(snode, ()),
+                },
+                _ => MNodeTransformResult::C(CNode::new(new_bit_indexed_array(self.bits, BitIndexedArrayVec::new(&self.values), self.size).unwrap()), ()),
+            }
+        }
+    }
+
+    fn into_node(mut self) -> Option<MNode<H, F, K, V, M, A>> {
+        match self.values.len() {
+            0 => None,
+            1 if !matches!(self.values[0], MNode::C(_)) => self.values.pop(),
+            _ => Some(MNode::C(CNode::new(new_bit_indexed_array(self.bits, BitIndexedArrayVec::new(&self.values), self.size).unwrap()))),
+        }
+    }
+}
+
+#[async_recursion(?Send)]
+pub(super) async fn partition<H, F, K, V, M, A, Pred>(this: &CNode<H, F, K, V, M, A>, pred: &Pred, par_strat: ParallelismStrategy) -> (MNodeTransformResult<H, F, K, V, M, A, ()>, MNodeTransformResult<H, F, K, V, M, A, ()>)
+where
+    H: Hashword,
+    F: Flagword<H>,
+    K: Key,
+    V: Value,
+    M: HasherBv<H, K>,
+    A: Measure<K, V>,
+    Pred: Fn(&K, &V) -> bool + Send + Sync,
+    <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
+{
+    let (kept, rejected) = match par_strat {
+        ParallelismStrategy::MiddleIndex => {
+            let left = partition_impl(this, pred, 0..F::max_ones()/2, par_strat);
+            let right = partition_impl(this, pred, F::max_ones()/2..F::max_ones(), par_strat);
+
+            let ((lkept, lrejected), (rkept, rrejected)) = join!(left, right);
+
+            (lkept.append(rkept), lrejected.append(rrejected))
+        },
+        ParallelismStrategy::Sequential => partition_impl(this, pred, 0..F::max_ones(), par_strat).await
+    };
+
+    (kept.into_result(), rejected.into_result())
+}
+
+async fn partition_impl<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>, Pred>(this: &CNode<H, F, K, V, M, A>, pred: &Pred, range: Range<usize>, par_strat: ParallelismStrategy) -> (PartitionSide<H, F, K, V, M, A>, PartitionSide<H, F, K, V, M, A>)
+where
+    Pred: Fn(&K, &V) -> bool + Send + Sync,
+    <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
+{
+    let mut kept = PartitionSide::new();
+    let mut rejected = PartitionSide::new();
+
+    for index in range {
+        if let Ok(node) = this.nodes.at_bit_index(index) {
+            let (kept_result, rejected_result) = node.partition(pred, par_strat).await;
+            kept.push(index, node, kept_result);
+            rejected.push(index, node, rejected_result);
+        }
+    }
+
+    (kept, rejected)
+}
+
+#[async_recursion(?Send)]
+pub(super) async fn par_reduce<H, F, K, V, M, A, T, MapOp, ReduceOp>(this: &CNode<H, F, K, V, M, A>, map_op: &MapOp, reduce_op: &ReduceOp, par_strat: ParallelismStrategy) -> Option<T>
+where
+    H: Hashword,
+    F: Flagword<H>,
+    K: Key,
+    V: Value,
+    M: HasherBv<H, K>,
+    A: Measure<K, V>,
+    MapOp: Fn(&K, &V) -> T + Send + Sync,
+    ReduceOp: Fn(T, T) -> T + Send + Sync,
+    <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
+{
+    match par_strat {
+        ParallelismStrategy::MiddleIndex => {
+            let left = par_reduce_impl(this, map_op, reduce_op, 0..F::max_ones()/2, par_strat);
+            let right = par_reduce_impl(this, map_op, reduce_op, F::max_ones()/2..F::max_ones(), par_strat);
+
+            let (left, right) = join!(left, right);
+
+            reduce_options(left, right, reduce_op)
+        },
+        ParallelismStrategy::Sequential => par_reduce_impl(this, map_op, reduce_op, 0..F::max_ones(), par_strat).await
+    }
+}
+
+async fn par_reduce_impl<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>, T, MapOp, ReduceOp>(this: &CNode<H, F, K, V, M, A>, map_op: &MapOp, reduce_op: &ReduceOp, range: Range<usize>, par_strat: ParallelismStrategy) -> Option<T>
+where
+    MapOp: Fn(&K, &V) -> T + Send + Sync,
+    ReduceOp: Fn(T, T) -> T + Send + Sync,
+    <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
+{
+    let mut reduced = None;
+
+    for index in range {
+        if let Ok(node) = this.nodes.at_bit_index(index) {
+            reduced = reduce_options(reduced, node.par_reduce(map_op, reduce_op, par_strat).await, reduce_op);
+        }
+    }
+
+    reduced
+}
+
+pub(super) fn apply<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>>(this: Option<&MNode<H, F, K, V, M, A>>, entries: Vec<(H, K, Option<V>)>, depth: usize) -> Option<MNode<H, F, K, V, M, A>>
+where
+    <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
+{
+    let child = |index| this.and_then(|node| node.child_or_leaf(index, depth));
+    let mut side = PartitionSide::new();
+    let mut untouched = 0;
+    let mut entries = entries.into_iter().peekable();
+
+    while let Some(entry) = entries.next() {
+        let index = Flag::<H, F>::new_at_depth(entry.0.clone(), depth).unwrap().index();
+        let mut run = vec!(entry);
+        while let Some(entry) = entries.next_if(|(hash_value, _key, _value)| Flag::<H, F>::new_at_depth(hash_value.clone(), depth).unwrap().index() == index) {
+            run.push(entry);
+        }
+
+        for untouched_index in untouched..index {
+            if let Some(node) = child(untouched_index) {
+                side.push_node(untouched_index, node.clone());
+            }
+        }
+        if let Some(node) = MNode::apply(child(index), run, depth + 1) {
+            side.push_node(index, node);
+        }
+        untouched = index + 1;
+    }
+
+    for untouched_index in untouched..F::max_ones() {
+        if let Some(node) = child(untouched_index) {
+            side.push_node(untouched_index, node.clone());
+        }
+    }
+
+    side.into_node()
+}
+
+pub(super) fn merge3<H: Hashword, F: Flagword<H>, K: Key, V: Value + PartialEq, M: HasherBv<H, K>, A: Measure<K, V>, Conflict: Fn(&K, Option<&V>, Option<&V>, Option<&V>) -> Resolution<V>>(base: &MNode<H, F, K, V, M, A>, left: &MNode<H, F, K, V, M, A>, right: &MNode<H, F, K, V, M, A>, depth: usize, conflict: &Conflict) -> Option<MNode<H, F, K, V, M, A>> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    let mut side = PartitionSide::new();
+    let empty = MNode::default();
+
+    for index in 0..F::max_ones() {
+        let node = match (base.child_or_leaf(index, depth), left.child_or_leaf(index, depth), right.child_or_leaf(index, depth)) {
+            (None, None, None) => continue,
+            (b, l, r) => b.unwrap_or(&empty).merge3(l.unwrap_or(&empty), r.unwrap_or(&empty), depth + 1, conflict),
+        };
+        if node.size() != 0 {
+            side.push_node(index, node);
+        }
+    }
+
+    side.into_node()
+}
+
+#[async_recursion(?Send)]
+pub(super) async fn transform_many<H, F, K, V, M, A, ReduceT, ReduceOp, Op>(nodes: &[&MNode<H, F, K, V, M, A>], reduce_op: &ReduceOp, op: &Op, depth: usize, par_strat: ParallelismStrategy) -> (Option<MNode<H, F, K, V, M, A>>, ReduceT)
+where
+    H: Hashword,
+    F: Flagword<H>,
+    K: Key,
+    V: Value,
+    M: HasherBv<H, K>,
+    A: Measure<K, V>,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
+    Op: Fn(&[(&K, &V)]) -> MapTransformResult<V, ReduceT> + Send + Sync,
+    <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
+{
+    let (side, reduced) = match par_strat {
+        ParallelismStrategy::MiddleIndex => {
+            let lower = transform_many_impl(nodes, reduce_op, op, depth, 0..F::max_ones()/2, par_strat);
+            let upper = transform_many_impl(nodes, reduce_op, op, depth, F::max_ones()/2..F::max_ones(), par_strat);
+
+            let ((lside, lreduced), (uside, ureduced)) = join!(lower, upper);
+
+            (lside.append(uside), reduce_op.reduce(lreduced, ureduced))
+        },
+        ParallelismStrategy::Sequential => transform_many_impl(nodes, reduce_op, op, depth, 0..F::max_ones(), par_strat).await
+    };
+
+    (side.into_node(), reduced)
+}
+
+async fn transform_many_impl<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>, ReduceT, ReduceOp, Op>(nodes: &[&MNode<H, F, K, V, M, A>], reduce_op: &ReduceOp, op: &Op, depth: usize, range: Range<usize>, par_strat: ParallelismStrategy) -> (PartitionSide<H, F, K, V, M, A>, ReduceT)
+where
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
+    Op: Fn(&[(&K, &V)]) -> MapTransformResult<V, ReduceT> + Send + Sync,
+    <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
+{
+    let mut side = PartitionSide::new();
+    let mut reduced = ReduceT::default();
+
+    for index in range {
+        let children: Vec<&MNode<H, F, K, V, M, A>> = nodes.iter().filter_map(|node| node.child_or_leaf(index, depth)).collect();
+        if children.is_empty() {
+            continue;
+        }
+        let (child, child_reduced) = MNode::transform_many(&children, reduce_op, op, depth + 1, par_strat).await;
+        reduced = reduce_op.reduce(reduced, child_reduced);
+        if let Some(child) = child {
+            side.push_node(index, child);
+        }
+    }
+
+    (side, reduced)
+}
+
+#[async_recursion(?Send)]
+pub(super) async fn restrict_keys<H, F, K, V, M, A, B>(this: &CNode<H, F, K, V, M, A>, keys: &MNode<H, F, K, (), M, B>, keep: bool, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, K, V, M, A, ()>
+where
+    H: Hashword,
+    F: Flagword<H>,
+    K: Key,
+    V: Value,
+    M: HasherBv<H, K>,
+    A: Measure<K, V>,
+    B: Measure<K, ()>,
+    <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
+{
+    let side = match par_strat {
+        ParallelismStrategy::MiddleIndex => {
+            let lower = restrict_keys_impl(this, keys, keep, depth, 0..F::max_ones()/2, par_strat);
+            let upper = restrict_keys_impl(this, keys, keep, depth, F::max_ones()/2..F::max_ones(), par_strat);
+
+            let (lower, upper) = join!(lower, upper);
+
+            lower.append(upper)
+        },
+        ParallelismStrategy::Sequential => restrict_keys_impl(this, keys, keep, depth, 0..F::max_ones(), par_strat).await
+    };
+
+    side.into_result()
+}
+
+async fn restrict_keys_impl<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>, B: Measure<K, ()>>(this: &CNode<H, F, K, V, M, A>, keys: &MNode<H, F, K, (), M, B>, keep: bool, depth: usize, range: Range<usize>, par_strat: ParallelismStrategy) -> PartitionSide<H, F, K, V, M, A>
+where
+    <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
+{
+    let mut side = PartitionSide::new();
+
+    for index in range {
+        if let Ok(node) = this.nodes.at_bit_index(index) {
+            let result = match keys.child_or_leaf(index, depth) {
+                Some(keys) => node.restrict_keys(keys, keep, depth + 1, par_strat).await,
+                None if keep => MNodeTransformResult::Removed(()),
+                None => MNodeTransformResult::Unchanged(()),
+            };
+            side.push(index, node, result);
+        }
+    }
+
+    side
+}
+
+#[async_recursion(?Send)]
+pub(super) async fn join<H, F, K, V, W, M, A, B, X, C, Op>(this: &MNode<H, F, K, V, M, A>, right: &MNode<H, F, K, W, M, B>, op: &Op, keep: (bool, bool), depth: usize, par_strat: ParallelismStrategy) -> Option<MNode<H, F, K, X, M, C>>
+where
+    H: Hashword,
+    F: Flagword<H>,
+    K: Key,
+    V: Value,
+    W: Value,
+    M: HasherBv<H, K>,
+    A: Measure<K, V>,
+    B: Measure<K, W>,
+    X: Value,
+    C: Measure<K, X>,
+    Op: Fn(&K, Option<&V>, Option<&W>) -> X + Send + Sync,
+    <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
+{
+    let side = match par_strat {
+        ParallelismStrategy::MiddleIndex => {
+            let lower = join_impl(this, right, op, keep, depth, 0..F::max_ones()/2, par_strat);
+            let upper = join_impl(this, right, op, keep, depth, F::max_ones()/2..F::max_ones(), par_strat);
+
+            let (lower, upper) = join!(lower, upper);
+
+            lower.append(upper)
+        },
+        ParallelismStrategy::Sequential => join_impl(this, right, op, keep, depth, 0..F::max_ones(), par_strat).await
+    };
+
+    side.into_node()
+}
+
+async fn join_impl<H: Hashword, F: Flagword<H>, K: Key, V: Value, W: Value, M: HasherBv<H, K>, A: Measure<K, V>, B: Measure<K, W>, X: Value, C: Measure<K, X>, Op>(this: &MNode<H, F, K, V, M, A>, right: &MNode<H, F, K, W, M, B>, op: &Op, keep: (bool, bool), depth: usize, range: Range<usize>, par_strat: ParallelismStrategy) -> PartitionSide<H, F, K, X, M, C>
+where
+    Op: Fn(&K, Option<&V>, Option<&W>) -> X + Send + Sync,
+    <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
+{
+    let mut side = PartitionSide::new();
+
+    for index in range {
+        let node = match (this.child_or_leaf(index, depth), right.child_or_leaf(index, depth)) {
+            (Some(lnode), Some(rnode)) => lnode.join(rnode, op, keep, depth + 1, par_strat).await,
+            (Some(lnode), None) if keep.0 => Some(lnode.map_entries(&|key: &K, value: &V| op(key, Some(value), None))),
+            (None, Some(rnode)) if keep.1 => Some(rnode.map_entries(&|key: &K, value: &W| op(key, None, Some(value)))),
+            _ => None,
+        };
+        if let Some(node) = node {
+            side.push_node(index, node);
+        }
+    }
+
+    side
+}
+
+#[async_recursion(?Send)]
+pub(super) async fn transform_async<H, F, K, V, M, A, ReduceT, ReduceOp, Op, Fut>(this: &CNode<H, F, K, V, M, A>, reduce_op: ReduceOp, op: Op, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, K, V, M, A, ReduceT>
+where
+    H: Hashword,
+    F: Flagword<H>,
+    K: Key,
+    V: Value,
+    M: HasherBv<H, K>,
+    A: Measure<K, V>,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
+    Op: Fn(&K, &V) -> Fut + Clone + Send + Sync,
+    Fut: Future<Output = MapTransformResult<V, ReduceT>>,
+    <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
+{
+    let (size, bits, mut values, unchanged, reduced) = {
+        match par_strat {
+            ParallelismStrategy::MiddleIndex => {
+                let op_clone = op.clone();
+
+                let left = transform_async_impl(this, reduce_op.clone(), op, 0..F::max_ones()/2, par_strat);
+                let right = transform_async_impl(this, reduce_op.clone(), op_clone, F::max_ones()/2..F::max_ones(), par_strat);
+
+                let (left, right) = join!(left, right);
+
+                let (lsize, lbits, mut lvalues, luc, rl) = left;
+                let (rsize, rbits, mut rvalues, ruc, rr) = right;
+
+                lvalues.append(&mut rvalues);
+
+                (lsize + rsize, lbits.bit_merge(rbits).unwrap(), lvalues, luc && ruc, reduce_op.reduce(rl, rr))
+            },
+            ParallelismStrategy::Sequential => transform_async_impl(this, reduce_op, op, 0..F::max_ones(), par_strat).await
         }
+    };
+
+    if unchanged {
+        MNodeTransformResult::Unchanged(reduced)
     }
-    
-    pub(super) fn visit<Op: Clone>(&self, op: Op) where Op: Fn(&K, &V) {
-        for node in self.nodes.as_ref() {
-            match node {
-                MNode::C(cnode) => cnode.visit(op.clone()),
-                MNode::L(lnode) => lnode.visit(op.clone()),
-                MNode::S(snode) => snode.visit(op.clone()),
-            }
+    else {
+        match values.len() {
+            0 => MNodeTransformResult::Removed(reduced),
+            1 => match values.pop().unwrap() {
+                MNode::C(cnode) => MNodeTransformResult::C(CNode::new(new_bit_indexed_array(bits, BitIndexedArrayVec::new(&[MNode::C(cnode)]), size).unwrap()), reduced),
+                MNode::L(lnode) => MNodeTransformResult::L(lnode, reduced),
+                MNode::S(snode) => MNodeTransformResult::S(snode, reduced),
+            },
+            _ => MNodeTransformResult::C(CNode::new(new_bit_indexed_array(bits, BitIndexedArrayVec::new(&values), size).unwrap()), reduced),
         }
     }
-
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> CNode<H, F, K, V, M> {
-    pub(super) fn insert<'a, L: Key + Into<K>, W: Into<V>>(&'a self, key: L, value: W, flag: Option<Flag<H, F>>, replace: bool) -> CNodeInsertResult<'a, H, F, K, V, M>
-    where
-        K: HashLike<L>,
-        K: PartialEq<L>,
-        M: HasherBv<H, L>,
-        <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
-    {
-        match self.nodes.at(flag.as_ref().unwrap().flag.clone()) {
-            Ok(node) => match node {
-                MNode::C(cnode) => match cnode.insert(key, value, flag.as_ref().unwrap().next(), replace) {
-                    CNodeInsertResult::Found(key, value) => CNodeInsertResult::Found(key, value),
-                    CNodeInsertResult::InsertedC(cnode2, key, value, prev) => {
-                        let size = self.size() + cnode2.size() - cnode.size();
-                        CNodeInsertResult::InsertedC(Self::new(self.nodes.updated(flag.unwrap().flag, Cow::Owned(MNode::C(cnode2)), Cow::Owned(size)).unwrap()), key, value, prev)
-                    },
+async fn transform_async_impl<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>, ReduceT, ReduceOp, Op, Fut>(this: &CNode<H, F, K, V, M, A>, reduce_op: ReduceOp, op: Op, range: Range<usize>, par_strat: ParallelismStrategy) -> (usize, F, Vec<MNode<H, F, K, V, M, A>>, bool, ReduceT)
+where
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
+    Op: Fn(&K, &V) -> Fut + Clone + Send + Sync,
+    Fut: Future<Output = MapTransformResult<V, ReduceT>>,
+    <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
+{
+    let mut size = 0;
+    let mut bits_t = F::default();
+    let mut values_t = Vec::default();
+    let mut unchanged = true;
+    let mut reduced = ReduceT::default();
+
+    for index in range {
+        if let Ok(node) = this.nodes.at_bit_index(index) {
+            match node.transform_async(reduce_op.clone(), op.clone(), par_strat).await {
+                MNodeTransformResult::Unchanged(r) => {
+                    size += node.size();
+                    bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
+                    values_t.push(node.clone());
+                    reduced = reduce_op.reduce(reduced, r);
                 },
-                MNode::L(lnode) => match lnode::insert(&lnode, key, value, flag.as_ref().unwrap().next(), replace) {
-                    LNodeInsertResult::Found(key, value) => CNodeInsertResult::Found(key, value),
-                    LNodeInsertResult::InsertedC(cnode, key, value, prev) => CNodeInsertResult::InsertedC(Self::new(self.nodes.updated(flag.unwrap().flag, Cow::Owned(MNode::C(cnode)), Cow::Owned(self.size() + 1)).unwrap()), key, value, prev),
-                    LNodeInsertResult::InsertedL(lnode2, key, value, prev) => {
-                        let size = self.size() + lnode2.size() - lnode.size();
-                        CNodeInsertResult::InsertedC(Self::new(self.nodes.updated(flag.unwrap().flag, Cow::Owned(MNode::L(lnode2)), Cow::Owned(size)).unwrap()), key, value, prev)
-                    },
+                MNodeTransformResult::C(cnode, r) => {
+                    size += cnode.size();
+                    bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
+                    values_t.push(MNode::C(cnode));
+                    reduced = reduce_op.reduce(reduced, r);
+                    unchanged = false;
                 },
-                MNode::S(snode) => match snode::insert(&snode, key, value, flag.as_ref().unwrap().next(), replace) {
-                    InsertResult::Found(key, value) => CNodeInsertResult::Found(key, value),
-                    InsertResult::InsertedC(cnode, key, value, prev) => CNodeInsertResult::InsertedC(Self::new(self.nodes.updated(flag.unwrap().flag, Cow::Owned(MNode::C(cnode)), Cow::Owned(self.size() + 1)).unwrap()), key, value, prev),
-                    InsertResult::InsertedL(lnode, key, value, prev) => CNodeInsertResult::InsertedC(Self::new(self.nodes.updated(flag.unwrap().flag, Cow::Owned(MNode::L(lnode)), Cow::Owned(self.size() + 1)).unwrap()), key, value, prev),
-                    InsertResult::InsertedS(snode, key, value, prev) => CNodeInsertResult::InsertedC(Self::new(self.nodes.updated(flag.unwrap().flag, Cow::Owned(MNode::S(snode)), Cow::Owned(self.size())).unwrap()), key, value, prev),
+                MNodeTransformResult::L(lnode, r) => {
+                    size += lnode.size();
+                    bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
+                    values_t.push(MNode::L(lnode));
+                    reduced = reduce_op.reduce(reduced, r);
+                    unchanged = false;
+                },
+                MNodeTransformResult::S(snode, r) => {
+                    size += 1;
+                    bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
+                    values_t.push(MNode::S(snode));
+                    reduced = reduce_op.reduce(reduced, r);
+                    unchanged = false;
+                },
+                MNodeTransformResult::Removed(r) => {
+                    reduced = reduce_op.reduce(reduced, r);
+                    unchanged = false;
                 },
-            },
-            Err(_) => {
-                let snode = SNode::new(key.into(), value.into());
-                let key: *const K = snode.key();
-                let value: *const V = snode.value();
-                CNodeInsertResult::InsertedC(Self::new(self.nodes.inserted(flag.unwrap().flag, Cow::Owned(MNode::S(snode)), Cow::Owned(self.size() + 1)).unwrap()), key, value, None)
             }
         }
     }
+
+    (size, bits_t, values_t, unchanged, reduced)
 }
 
 #[async_recursion(?Send)]
-pub(super) async fn transform<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, Op>(this: &CNode<H, F, K, V, M>, reduce_op: ReduceOp, op: MapTransform<ReduceT, Op>, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, K, V, M, ReduceT>
+pub(super) async fn try_transform<H, F, K, V, M, A, ReduceT, ReduceOp, Op, E>(this: &CNode<H, F, K, V, M, A>, reduce_op: ReduceOp, op: Op, par_strat: ParallelismStrategy) -> Result<MNodeTransformResult<H, F, K, V, M, A, ReduceT>, E>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
-    Op: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
+    H: Hashword,
+    F: Flagword<H>,
+    K: Key,
+    V: Value,
+    M: HasherBv<H, K>,
+    A: Measure<K, V>,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
+    Op: Fn(&K, &V) -> Result<MapTransformResult<V, ReduceT>, E> + Clone + Send + Sync,
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
-    match op {
-        MapTransform::Generic(_) => {},
-        MapTransform::Unchanged(r) => return MNodeTransformResult::Unchanged(r),
-        MapTransform::Removed(r) => return MNodeTransformResult::Removed(r),
-    }
-
     let (size, bits, mut values, unchanged, reduced) = {
         match par_strat {
             ParallelismStrategy::MiddleIndex => {
                 let op_clone = op.clone();
 
-                let left = transform_impl(this, reduce_op.clone(), op, 0..F::max_ones()/2, par_strat);
-                let right = transform_impl(this, reduce_op.clone(), op_clone, F::max_ones()/2..F::max_ones(), par_strat);
+                let left = try_transform_impl(this, reduce_op.clone(), op, 0..F::max_ones()/2, par_strat);
+                let right = try_transform_impl(this, reduce_op.clone(), op_clone, F::max_ones()/2..F::max_ones(), par_strat);
 
-                let (left, right) = join!(left, right);
+                let (left, right) = try_join!(left, right)?;
 
                 let (lsize, lbits, mut lvalues, luc, rl) = left;
                 let (rsize, rbits, mut rvalues, ruc, rr) = right;
 
                 lvalues.append(&mut rvalues);
-        
-                (lsize + rsize, lbits.bit_merge(rbits).unwrap(), lvalues, luc && ruc, reduce_op(&rl, &rr))
+
+                (lsize + rsize, lbits.bit_merge(rbits).unwrap(), lvalues, luc && ruc, reduce_op.reduce(rl, rr))
             },
-            ParallelismStrategy::Sequential => transform_impl(this, reduce_op, op, 0..F::max_ones(), par_strat).await
+            ParallelismStrategy::Sequential => try_transform_impl(this, reduce_op, op, 0..F::max_ones(), par_strat).await?
         }
     };
 
-    if unchanged {
+    Ok(if unchanged {
         MNodeTransformResult::Unchanged(reduced)
     }
     else {
@@ -163,15 +925,14 @@ where
             },
             _ => MNodeTransformResult::C(CNode::new(new_bit_indexed_array(bits, BitIndexedArrayVec::new(&values), size).unwrap()), reduced),
         }
-    }
+    })
 }
 
-
-async fn transform_impl<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, Op>(this: &CNode<H, F, K, V, M>, reduce_op: ReduceOp, op: MapTransform<ReduceT, Op>, range: Range<usize>, par_strat: ParallelismStrategy) -> (usize, F, Vec<MNode<H, F, K, V, M>>, bool, ReduceT)
+async fn try_transform_impl<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>, ReduceT, ReduceOp, Op, E>(this: &CNode<H, F, K, V, M, A>, reduce_op: ReduceOp, op: Op, range: Range<usize>, par_strat: ParallelismStrategy) -> Result<(usize, F, Vec<MNode<H, F, K, V, M, A>>, bool, ReduceT), E>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
-    Op: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
+    Op: Fn(&K, &V) -> Result<MapTransformResult<V, ReduceT>, E> + Clone + Send + Sync,
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
     let mut size = 0;
@@ -182,49 +943,49 @@ where
 
     for index in range {
         if let Ok(node) = this.nodes.at_bit_index(index) {
-            match node.transform(reduce_op.clone(), op.clone(), par_strat).await {
+            match node.try_transform(reduce_op.clone(), op.clone(), par_strat).await? {
                 MNodeTransformResult::Unchanged(r) => {
                     size += node.size();
                     bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                     values_t.push(node.clone());
-                    reduced = reduce_op(&reduced, &r);
+                    reduced = reduce_op.reduce(reduced, r);
                 },
                 MNodeTransformResult::C(cnode, r) => {
                     size += cnode.size();
                     bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                     values_t.push(MNode::C(cnode));
-                    reduced = reduce_op(&reduced, &r);
+                    reduced = reduce_op.reduce(reduced, r);
                     unchanged = false;
                 },
                 MNodeTransformResult::L(lnode, r) => {
                     size += lnode.size();
                     bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                     values_t.push(MNode::L(lnode));
-                    reduced = reduce_op(&reduced, &r);
+                    reduced = reduce_op.reduce(reduced, r);
                     unchanged = false;
                 },
                 MNodeTransformResult::S(snode, r) => {
                     size += 1;
                     bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                     values_t.push(MNode::S(snode));
-                    reduced = reduce_op(&reduced, &r);
+                    reduced = reduce_op.reduce(reduced, r);
                     unchanged = false;
                 },
                 MNodeTransformResult::Removed(r) => {
-                    reduced = reduce_op(&reduced, &r);
+                    reduced = reduce_op.reduce(reduced, r);
                     unchanged = false;
                 },
             }
         }
     }
 
-    (size, bits_t, values_t, unchanged, reduced)
+    Ok((size, bits_t, values_t, unchanged, reduced))
 }
 
-pub(super) unsafe fn transmute<H: Hashword, F: Flagword<H>, K: Key, V: Value, S: Key, X: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, Op>(this: &CNode<H, F, K, V, M>, reduce_op: ReduceOp, op: MapTransmute<ReduceT, Op>) -> MNodeTransmuteResult<H, F, S, X, M, ReduceT>
+pub(super) unsafe fn transmute<H: Hashword, F: Flagword<H>, K: Key, V: Value, S: Key, X: Value, M: HasherBv<H, K>, A: Measure<K, V> + Measure<S, X>, ReduceT, ReduceOp, Op>(this: &CNode<H, F, K, V, M, A>, reduce_op: ReduceOp, op: MapTransmute<ReduceT, Op>) -> MNodeTransmuteResult<H, F, S, X, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     Op: Fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT> + Clone,
     K: HashLike<S>,
     K: PartialEq<S>,
@@ -242,27 +1003,27 @@ where
 
     for index in 0..<F>::max_ones() {
         if let Ok(node) = this.nodes.at_bit_index(index) {
-            match node.transmute(reduce_op.clone(), op.clone()) {
+            match node.transmute(reduce_op.clone(), op.fork()) {
                 MNodeTransmuteResult::C(cnode, r) => {
                     size += cnode.size();
                     bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                     values_t.push(MNode::C(cnode));
-                    reduced = reduce_op(&reduced, &r);
+                    reduced = reduce_op.reduce(reduced, r);
                 },
                 MNodeTransmuteResult::L(lnode, r) => {
                     size += lnode.size();
                     bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                     values_t.push(MNode::L(lnode));
-                    reduced = reduce_op(&reduced, &r);
+                    reduced = reduce_op.reduce(reduced, r);
                 },
                 MNodeTransmuteResult::S(snode, r) => {
                     size += 1;
                     bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                     values_t.push(MNode::S(snode));
-                    reduced = reduce_op(&reduced, &r);
+                    reduced = reduce_op.reduce(reduced, r);
                 },
                 MNodeTransmuteResult::Removed(r) => {
-                    reduced = reduce_op(&reduced, &r);
+                    reduced = reduce_op.reduce(reduced, r);
                 },
             }
         }
@@ -282,10 +1043,11 @@ where
 }
 
 #[async_recursion(?Send)]
-pub(crate) async fn transform_with_transformed<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M>, right: &MNode<H, F, K, V, M>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeJointTransformResult<H, F, K, V, M, ReduceT>
+pub(crate) async fn transform_with_transformed<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M, A>, right: &MNode<H, F, K, V, M, A>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeJointTransformResult<H, F, K, V, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    A: Measure<K, V>,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT> + Clone + Send + Sync,
     LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
@@ -299,10 +1061,11 @@ where
 }
 
 #[async_recursion(?Send)]
-pub(crate) async unsafe fn transform_with_transmuted<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M>, right: &MNode<H, F, L, W, M>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, K, V, M, ReduceT>
+pub(crate) async unsafe fn transform_with_transmuted<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, A, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M, A>, right: &MNode<H, F, L, W, M, A>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, K, V, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    A: Measure<K, V> + Measure<L, W>,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<V, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&L, &W) -> MapTransmuteResult<K, V, ReduceT> + Clone + Send + Sync,
@@ -321,10 +1084,11 @@ where
 }
 
 #[async_recursion(?Send)]
-pub(crate) async unsafe fn transmute_with_transformed<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M>, right: &MNode<H, F, L, W, M>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, L, W, M, ReduceT>
+pub(crate) async unsafe fn transmute_with_transformed<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, A, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M, A>, right: &MNode<H, F, L, W, M, A>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, L, W, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    A: Measure<K, V> + Measure<L, W>,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<W, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransmuteResult<L, W, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&L, &W) -> MapTransformResult<W, ReduceT> + Clone + Send + Sync,
@@ -342,10 +1106,10 @@ where
     }
 }
 
-pub(crate) unsafe fn transmute_with_transmuted<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, S: Key, X: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M>, right: &MNode<H, F, L, W, M>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransmuteResult<H, F, S, X, M, ReduceT>
+pub(crate) unsafe fn transmute_with_transmuted<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, S: Key, X: Value, M: HasherBv<H, K>, A: Measure<K, V> + Measure<L, W> + Measure<S, X>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M, A>, right: &MNode<H, F, L, W, M, A>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransmuteResult<H, F, S, X, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT> + Clone,
     RightOp: Fn(&L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
@@ -368,10 +1132,10 @@ where
     }
 }
 
-pub(crate) async fn transform_with_transformed_cnode<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M>, right: &CNode<H, F, K, V, M>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeJointTransformResult<H, F, K, V, M, ReduceT>
+pub(crate) async fn transform_with_transformed_cnode<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M, A>, right: &CNode<H, F, K, V, M, A>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeJointTransformResult<H, F, K, V, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT> + Clone + Send + Sync,
     LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
@@ -379,50 +1143,50 @@ where
 {
     match &both_op {
         MapJointTransform::Generic(_,_) => {},
-        MapJointTransform::UnchangedLR(r) => {
+        MapJointTransform::UnchangedLR(_) => {
             match &left_op {
                 MapTransform::Generic(_) => {}
                 MapTransform::Unchanged(_) => {
                     if let MapTransform::Removed(_) = &right_op {
-                        return MNodeJointTransformResult::UnchangedL(r.clone());
+                        return MNodeJointTransformResult::UnchangedL(ReduceT::default());
                     }
                 },
                 MapTransform::Removed(_) => {
                     if let MapTransform::Unchanged(_) = &right_op {
-                        return MNodeJointTransformResult::UnchangedR(r.clone());
+                        return MNodeJointTransformResult::UnchangedR(ReduceT::default());
                     }
                 },
             }
         },
-        MapJointTransform::UnchangedL(r) => {
+        MapJointTransform::UnchangedL(_) => {
             match &left_op {
                 MapTransform::Generic(_) => {}
                 MapTransform::Unchanged(_) => {
                     if let MapTransform::Removed(_) = &right_op {
-                        return MNodeJointTransformResult::UnchangedL(r.clone());
+                        return MNodeJointTransformResult::UnchangedL(ReduceT::default());
                     }
                 },
                 MapTransform::Removed(_) => {},
             }
         },
-        MapJointTransform::UnchangedR(r) => {
+        MapJointTransform::UnchangedR(_) => {
             match &left_op {
                 MapTransform::Generic(_) => {}
                 MapTransform::Unchanged(_) => {},
                 MapTransform::Removed(_) => {
                     if let MapTransform::Unchanged(_) = &right_op {
-                        return MNodeJointTransformResult::UnchangedR(r.clone());
+                        return MNodeJointTransformResult::UnchangedR(ReduceT::default());
                     }
                 },
             }
         },
-        MapJointTransform::Removed(r) => {
+        MapJointTransform::Removed(_) => {
             match &left_op {
                 MapTransform::Generic(_) => {}
                 MapTransform::Unchanged(_) => {},
                 MapTransform::Removed(_) => {
                     if let MapTransform::Removed(_) = &right_op {
-                        return MNodeJointTransformResult::Removed(r.clone());
+                        return MNodeJointTransformResult::Removed(ReduceT::default());
                     }
                 },
             }
@@ -432,12 +1196,10 @@ where
     let (size, bits, mut values, unchangedl, unchangedr, reduced) = {
         match par_strat {
             ParallelismStrategy::MiddleIndex => {
-                let both_op_clone = both_op.clone();
-                let left_op_clone = left_op.clone();
-                let right_op_clone = right_op.clone();
+                let ops = JointOps {reduce_op: &reduce_op, both_op: &both_op, left_op: &left_op, right_op: &right_op};
 
-                let left = transform_with_transformed_cnode_impl(this, right, reduce_op.clone(), both_op, left_op, right_op, depth, 0..F::max_ones()/2, par_strat);
-                let right = transform_with_transformed_cnode_impl(this, right, reduce_op.clone(), both_op_clone, left_op_clone, right_op_clone, depth, F::max_ones()/2..F::max_ones(), par_strat);
+                let left = transform_with_transformed_cnode_impl(this, right, ops, depth, 0..F::max_ones()/2, par_strat);
+                let right = transform_with_transformed_cnode_impl(this, right, ops, depth, F::max_ones()/2..F::max_ones(), par_strat);
 
                 let (left, right) = join!(left, right);
 
@@ -446,9 +1208,9 @@ where
 
                 lvalues.append(&mut rvalues);
         
-                (lsize + rsize, lbits.bit_merge(rbits).unwrap(), lvalues, lucl && rucl, lucr && rucr, reduce_op(&rl, &rr))
+                (lsize + rsize, lbits.bit_merge(rbits).unwrap(), lvalues, lucl && rucl, lucr && rucr, reduce_op.reduce(rl, rr))
             },
-            ParallelismStrategy::Sequential => transform_with_transformed_cnode_impl(this, right, reduce_op, both_op, left_op, right_op, depth, 0..F::max_ones(), par_strat).await
+            ParallelismStrategy::Sequential => transform_with_transformed_cnode_impl(this, right, JointOps {reduce_op: &reduce_op, both_op: &both_op, left_op: &left_op, right_op: &right_op}, depth, 0..F::max_ones(), par_strat).await
         }
     };
 
@@ -476,10 +1238,10 @@ where
     }
 }
 
-pub(crate) async fn transform_with_transformed_cnode_impl<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M>, right: &CNode<H, F, K, V, M>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, range: Range<usize>, par_strat: ParallelismStrategy) -> (usize, F, Vec<MNode<H, F, K, V, M>>, bool, bool, ReduceT)
+pub(crate) async fn transform_with_transformed_cnode_impl<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M, A>, right: &CNode<H, F, K, V, M, A>, ops: TransformOps<'_, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>, depth: usize, range: Range<usize>, par_strat: ParallelismStrategy) -> (usize, F, Vec<MNode<H, F, K, V, M, A>>, bool, bool, ReduceT)
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT> + Clone + Send + Sync,
     LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
@@ -500,10 +1262,10 @@ where
             left = Some(node);
             if let Ok(right) = right.nodes.at_bit_index(index) {
                 right_node = Some(right);
-                node.transform_with_transformed(right, reduce_op.clone(), both_op.clone(), left_op.clone(), right_op.clone(), depth + 1, par_strat).await
+                node.transform_with_transformed(right, ops.reduce_op.clone(), ops.both_op.fork(), ops.left_op.fork(), ops.right_op.fork(), depth + 1, par_strat).await
             }
             else {
-                match node.transform(reduce_op.clone(), left_op.clone(), par_strat).await {
+                match node.transform(ops.reduce_op.clone(), ops.left_op.fork(), par_strat).await {
                     MNodeTransformResult::Unchanged(reduced) => MNodeJointTransformResult::UnchangedL(reduced),
                     MNodeTransformResult::C(cnode, reduced) => MNodeJointTransformResult::C(cnode, reduced),
                     MNodeTransformResult::L(lnode, reduced) => MNodeJointTransformResult::L(lnode, reduced),
@@ -514,7 +1276,7 @@ where
         }
         else if let Ok(right) = right.nodes.at_bit_index(index) {
             right_node = Some(right);
-            match right.transform(reduce_op.clone(), right_op.clone(), par_strat).await {
+            match right.transform(ops.reduce_op.clone(), ops.right_op.fork(), par_strat).await {
                 MNodeTransformResult::Unchanged(reduced) => MNodeJointTransformResult::UnchangedR(reduced),
                 MNodeTransformResult::C(cnode, reduced) => MNodeJointTransformResult::C(cnode, reduced),
                 MNodeTransformResult::L(lnode, reduced) => MNodeJointTransformResult::L(lnode, reduced),
@@ -531,27 +1293,27 @@ where
                 size += left.unwrap().size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(left.unwrap().clone());
-                reduced = reduce_op(&reduced, &r);
+                reduced = ops.reduce_op.reduce(reduced, r);
             },
             MNodeJointTransformResult::UnchangedL(r) => {
                 size += left.unwrap().size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(left.unwrap().clone());
-                reduced = reduce_op(&reduced, &r);
+                reduced = ops.reduce_op.reduce(reduced, r);
                 unchangedr = false;
             },
             MNodeJointTransformResult::UnchangedR(r) => {
                 size += right_node.unwrap().size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(right_node.unwrap().clone());
-                reduced = reduce_op(&reduced, &r);
+                reduced = ops.reduce_op.reduce(reduced, r);
                 unchangedl = false;
             },
             MNodeJointTransformResult::C(cnode, r) => {
                 size += cnode.size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::C(cnode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = ops.reduce_op.reduce(reduced, r);
                 unchangedl = false;
                 unchangedr = false;
             },
@@ -559,7 +1321,7 @@ where
                 size += lnode.size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::L(lnode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = ops.reduce_op.reduce(reduced, r);
                 unchangedl = false;
                 unchangedr = false;
             },
@@ -567,12 +1329,12 @@ where
                 size += 1;
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::S(snode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = ops.reduce_op.reduce(reduced, r);
                 unchangedl = false;
                 unchangedr = false;
             },
             MNodeJointTransformResult::Removed(r) => {
-                reduced = reduce_op(&reduced, &r);
+                reduced = ops.reduce_op.reduce(reduced, r);
                 unchangedl = false;
                 unchangedr = false;
             },
@@ -582,10 +1344,176 @@ where
     (size, bits_t, values_t, unchangedl, unchangedr, reduced)
 }
 
-pub(crate) async unsafe fn transform_with_transmuted_cnode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M>, right: &CNode<H, F, L, W, M>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, K, V, M, ReduceT>
+#[async_recursion(?Send)]
+pub(crate) async fn try_transform_with_transformed<'a, H, F, K, V, M, A, ReduceT, ReduceOp, BothOp, LeftOp, RightOp, E>(this: &MNode<H, F, K, V, M, A>, right: &MNode<H, F, K, V, M, A>, ops: JointOps<'a, ReduceOp, BothOp, LeftOp, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> Result<MNodeJointTransformResult<H, F, K, V, M, A, ReduceT>, E>
+where
+    'a: 'async_recursion,
+    H: Hashword,
+    F: Flagword<H>,
+    K: Key,
+    V: Value,
+    M: HasherBv<H, K>,
+    A: Measure<K, V>,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
+    BothOp: Fn(&K, &V, &K, &V) -> Result<MapJointTransformResult<V, ReduceT>, E> + Clone + Send + Sync,
+    LeftOp: Fn(&K, &V) -> Result<MapTransformResult<V, ReduceT>, E> + Clone + Send + Sync,
+    RightOp: Fn(&K, &V) -> Result<MapTransformResult<V, ReduceT>, E> + Clone + Send + Sync,
+    <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
+{
+    let (size, bits, mut values, unchangedl, unchangedr, reduced) = {
+        match par_strat {
+            ParallelismStrategy::MiddleIndex => {
+                let left = try_transform_with_transformed_impl(this, right, ops, depth, 0..F::max_ones()/2, par_strat);
+                let right = try_transform_with_transformed_impl(this, right, ops, depth, F::max_ones()/2..F::max_ones(), par_strat);
+
+                let (left, right) = try_join!(left, right)?;
+
+                let (lsize, lbits, mut lvalues, lucl, lucr, rl) = left;
+                let (rsize, rbits, mut rvalues, rucl, rucr, rr) = right;
+
+                lvalues.append(&mut rvalues);
+
+                (lsize + rsize, lbits.bit_merge(rbits).unwrap(), lvalues, lucl && rucl, lucr && rucr, ops.reduce_op.reduce(rl, rr))
+            },
+            ParallelismStrategy::Sequential => try_transform_with_transformed_impl(this, right, ops, depth, 0..F::max_ones(), par_strat).await?
+        }
+    };
+
+    Ok(if unchangedl {
+        if unchangedr {
+            MNodeJointTransformResult::UnchangedLR(reduced)
+        }
+        else {
+            MNodeJointTransformResult::UnchangedL(reduced)
+        }
+    }
+    else if unchangedr {
+        MNodeJointTransformResult::UnchangedR(reduced)
+    }
+    else {
+        match values.len() {
+            0 => MNodeJointTransformResult::Removed(reduced),
+            1 => match values.pop().unwrap() {
+                MNode::C(cnode) => MNodeJointTransformResult::C(CNode::new(new_bit_indexed_array(bits, BitIndexedArrayVec::new(&[MNode::C(cnode)]), size).unwrap()), reduced),
+                MNode::L(lnode) => MNodeJointTransformResult::L(lnode, reduced),
+                MNode::S(snode) => MNodeJointTransformResult::S(snode, reduced),
+            },
+            _ => MNodeJointTransformResult::C(CNode::new(new_bit_indexed_array(bits, BitIndexedArrayVec::new(&values), size).unwrap()), reduced),
+        }
+    })
+}
+
+async fn try_transform_with_transformed_impl<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp, E>(this: &MNode<H, F, K, V, M, A>, right: &MNode<H, F, K, V, M, A>, ops: JointOps<'_, ReduceOp, BothOp, LeftOp, RightOp>, depth: usize, range: Range<usize>, par_strat: ParallelismStrategy) -> Result<(usize, F, Vec<MNode<H, F, K, V, M, A>>, bool, bool, ReduceT), E>
+where
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
+    BothOp: Fn(&K, &V, &K, &V) -> Result<MapJointTransformResult<V, ReduceT>, E> + Clone + Send + Sync,
+    LeftOp: Fn(&K, &V) -> Result<MapTransformResult<V, ReduceT>, E> + Clone + Send + Sync,
+    RightOp: Fn(&K, &V) -> Result<MapTransformResult<V, ReduceT>, E> + Clone + Send + Sync,
+    <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
+{
+    let mut size = 0;
+    let mut bits_t = F::default();
+    let mut values_t = Vec::default();
+    let mut reduced = ReduceT::default();
+    let mut unchangedl = true;
+    let mut unchangedr = true;
+
+    for index in range {
+        let mut left = None;
+        let mut right_node = None;
+
+        let transform_result = if let Some(node) = this.child_or_leaf(index, depth) {
+            left = Some(node);
+            if let Some(right) = right.child_or_leaf(index, depth) {
+                right_node = Some(right);
+                node.try_transform_with_transformed(right, ops, depth + 1, par_strat).await?
+            }
+            else {
+                match node.try_transform(ops.reduce_op.clone(), ops.left_op.clone(), par_strat).await? {
+                    MNodeTransformResult::Unchanged(reduced) => MNodeJointTransformResult::UnchangedL(reduced),
+                    MNodeTransformResult::C(cnode, reduced) => MNodeJointTransformResult::C(cnode, reduced),
+                    MNodeTransformResult::L(lnode, reduced) => MNodeJointTransformResult::L(lnode, reduced),
+                    MNodeTransformResult::S(snode, reduced) => MNodeJointTransformResult::S(snode, reduced),
+                    MNodeTransformResult::Removed(reduced) => MNodeJointTransformResult::Removed(reduced),
+                }
+            }
+        }
+        else if let Some(right) = right.child_or_leaf(index, depth) {
+            right_node = Some(right);
+            match right.try_transform(ops.reduce_op.clone(), ops.right_op.clone(), par_strat).await? {
+                MNodeTransformResult::Unchanged(reduced) => MNodeJointTransformResult::UnchangedR(reduced),
+                MNodeTransformResult::C(cnode, reduced) => MNodeJointTransformResult::C(cnode, reduced),
+                MNodeTransformResult::L(lnode, reduced) => MNodeJointTransformResult::L(lnode, reduced),
+                MNodeTransformResult::S(snode, reduced) => MNodeJointTransformResult::S(snode, reduced),
+                MNodeTransformResult::Removed(reduced) => MNodeJointTransformResult::Removed(reduced),
+            }
+        }
+        else {
+            continue;
+        };
+
+        match transform_result {
+            MNodeJointTransformResult::UnchangedLR(r) => {
+                size += left.unwrap().size();
+                bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
+                values_t.push(left.unwrap().clone());
+                reduced = ops.reduce_op.reduce(reduced, r);
+            },
+            MNodeJointTransformResult::UnchangedL(r) => {
+                size += left.unwrap().size();
+                bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
+                values_t.push(left.unwrap().clone());
+                reduced = ops.reduce_op.reduce(reduced, r);
+                unchangedr = false;
+            },
+            MNodeJointTransformResult::UnchangedR(r) => {
+                size += right_node.unwrap().size();
+                bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
+                values_t.push(right_node.unwrap().clone());
+                reduced = ops.reduce_op.reduce(reduced, r);
+                unchangedl = false;
+            },
+            MNodeJointTransformResult::C(cnode, r) => {
+                size += cnode.size();
+                bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
+                values_t.push(MNode::C(cnode));
+                reduced = ops.reduce_op.reduce(reduced, r);
+                unchangedl = false;
+                unchangedr = false;
+            },
+            MNodeJointTransformResult::L(lnode, r) => {
+                size += lnode.size();
+                bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
+                values_t.push(MNode::L(lnode));
+                reduced = ops.reduce_op.reduce(reduced, r);
+                unchangedl = false;
+                unchangedr = false;
+            },
+            MNodeJointTransformResult::S(snode, r) => {
+                size += 1;
+                bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
+                values_t.push(MNode::S(snode));
+                reduced = ops.reduce_op.reduce(reduced, r);
+                unchangedl = false;
+                unchangedr = false;
+            },
+            MNodeJointTransformResult::Removed(r) => {
+                reduced = ops.reduce_op.reduce(reduced, r);
+                unchangedl = false;
+                unchangedr = false;
+            },
+        }
+    }
+
+    Ok((size, bits_t, values_t, unchangedl, unchangedr, reduced))
+}
+
+pub(crate) async unsafe fn transform_with_transmuted_cnode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, A: Measure<K, V> + Measure<L, W>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M, A>, right: &CNode<H, F, L, W, M, A>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, K, V, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<V, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&L, &W) -> MapTransmuteResult<K, V, ReduceT> + Clone + Send + Sync,
@@ -608,14 +1536,14 @@ where
         let transform_result = if let Ok(node) = this.nodes.at_bit_index(index) {
             left = Some(node);
             if let Ok(right) = right.nodes.at_bit_index(index) {
-                node.transform_with_transmuted(right, reduce_op.clone(), both_op.clone(), left_op.clone(), right_op.clone(), depth + 1, par_strat).await
+                node.transform_with_transmuted(right, reduce_op.clone(), both_op.fork(), left_op.fork(), right_op.fork(), depth + 1, par_strat).await
             }
             else {
-                node.transform(reduce_op.clone(), left_op.clone(), par_strat).await
+                node.transform(reduce_op.clone(), left_op.fork(), par_strat).await
             }
         }
         else if let Ok(right) = right.nodes.at_bit_index(index) {
-            right.transmute(reduce_op.clone(), right_op.clone()).into()
+            right.transmute(reduce_op.clone(), right_op.fork()).into()
         }
         else {
             continue;
@@ -626,31 +1554,31 @@ where
                 size += left.unwrap().size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(left.unwrap().clone());
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
             },
             MNodeTransformResult::C(cnode, r) => {
                 size += cnode.size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::C(cnode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedl = false;
             },
             MNodeTransformResult::L(lnode, r) => {
                 size += lnode.size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::L(lnode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedl = false;
             },
             MNodeTransformResult::S(snode, r) => {
                 size += 1;
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::S(snode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedl = false;
             },
             MNodeTransformResult::Removed(r) => {
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedl = false;
             },
         }
@@ -672,10 +1600,10 @@ where
     }
 }
 
-pub(crate) async unsafe fn transmute_with_transformed_cnode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M>, right: &CNode<H, F, L, W, M>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, L, W, M, ReduceT>
+pub(crate) async unsafe fn transmute_with_transformed_cnode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, A: Measure<K, V> + Measure<L, W>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M, A>, right: &CNode<H, F, L, W, M, A>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, L, W, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<W, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransmuteResult<L, W, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&L, &W) -> MapTransformResult<W, ReduceT> + Clone + Send + Sync,
@@ -698,15 +1626,15 @@ where
         let transform_result = if let Ok(node) = this.nodes.at_bit_index(index) {
             if let Ok(right) = right.nodes.at_bit_index(index) {
                 right_node = Some(right);
-                node.transmute_with_transformed(right, reduce_op.clone(), both_op.clone(), left_op.clone(), right_op.clone(), depth + 1, par_strat).await
+                node.transmute_with_transformed(right, reduce_op.clone(), both_op.fork(), left_op.fork(), right_op.fork(), depth + 1, par_strat).await
             }
             else {
-                node.transmute(reduce_op.clone(), left_op.clone()).into()
+                node.transmute(reduce_op.clone(), left_op.fork()).into()
             }
         }
         else if let Ok(right) = right.nodes.at_bit_index(index) {
             right_node = Some(right);
-            right.transform(reduce_op.clone(), right_op.clone(), par_strat).await
+            right.transform(reduce_op.clone(), right_op.fork(), par_strat).await
         }
         else {
             continue;
@@ -717,31 +1645,31 @@ where
                 size += right_node.unwrap().size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(right_node.unwrap().clone());
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
             },
             MNodeTransformResult::C(cnode, r) => {
                 size += cnode.size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::C(cnode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedr = false;
             },
             MNodeTransformResult::L(lnode, r) => {
                 size += lnode.size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::L(lnode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedr = false;
             },
             MNodeTransformResult::S(snode, r) => {
                 size += 1;
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::S(snode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedr = false;
             },
             MNodeTransformResult::Removed(r) => {
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedr = false;
             },
         }
@@ -763,10 +1691,10 @@ where
     }
 }
 
-pub(crate) unsafe fn transmute_with_transmuted_cnode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, S: Key, W: Value, X: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M>, right: &CNode<H, F, L, W, M>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransmuteResult<H, F, S, X, M, ReduceT>
+pub(crate) unsafe fn transmute_with_transmuted_cnode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, S: Key, W: Value, X: Value, M: HasherBv<H, K>, A: Measure<K, V> + Measure<L, W> + Measure<S, X>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M, A>, right: &CNode<H, F, L, W, M, A>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransmuteResult<H, F, S, X, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT> + Clone,
     RightOp: Fn(&L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
@@ -790,14 +1718,14 @@ where
     for index in 0..<F>::max_ones() {
         let transmute_result = if let Ok(node) = this.nodes.at_bit_index(index) {
             if let Ok(right) = right.nodes.at_bit_index(index) {
-                node.transmute_with_transmuted(right, reduce_op.clone(), both_op.clone(), left_op.clone(), right_op.clone(), depth + 1)
+                node.transmute_with_transmuted(right, reduce_op.clone(), both_op.fork(), left_op.fork(), right_op.fork(), depth + 1)
             }
             else {
-                node.transmute(reduce_op.clone(), left_op.clone())
+                node.transmute(reduce_op.clone(), left_op.fork())
             }
         }
         else if let Ok(right) = right.nodes.at_bit_index(index) {
-            right.transmute(reduce_op.clone(), right_op.clone())
+            right.transmute(reduce_op.clone(), right_op.fork())
         }
         else {
             continue;
@@ -808,22 +1736,22 @@ where
                 size += cnode.size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::C(cnode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
             },
             MNodeTransmuteResult::L(lnode, r) => {
                 size += lnode.size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::L(lnode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
             },
             MNodeTransmuteResult::S(snode, r) => {
                 size += 1;
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::S(snode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
             },
             MNodeTransmuteResult::Removed(r) => {
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
             },
         }
     }
@@ -840,16 +1768,17 @@ where
 }
 
 #[async_recursion(?Send)]
-pub(crate) async fn transform_with_transformed_lnode<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M>, right: &Arc<LNode<K, V>>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeJointTransformResult<H, F, K, V, M, ReduceT>
+pub(crate) async fn transform_with_transformed_lnode<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M, A>, right: &Arc<LNode<H, K, V>>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeJointTransformResult<H, F, K, V, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    A: Measure<K, V>,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT> + Clone + Send + Sync,
     LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
-    let flag = Flag::<H, F>::new_at_depth(M::default().hash(right.key()), depth).unwrap().flag();
+    let flag = Flag::<H, F>::new_at_depth(right.hash_value::<M>(), depth).unwrap().flag();
 
     let mut size = 0;
     let mut bits_t = F::default();
@@ -864,10 +1793,10 @@ where
         let transform_result = if let Ok(node) = this.nodes.at_bit_index(index) {
             left = Some(node);
             if flag == F::nth_bit(index).unwrap() {
-                node.transform_with_transformed_lnode(right, reduce_op.clone(), both_op.clone(), left_op.clone(), right_op.clone(), depth + 1, par_strat).await
+                node.transform_with_transformed_lnode(right, reduce_op.clone(), both_op.fork(), left_op.fork(), right_op.fork(), depth + 1, par_strat).await
             }
             else {
-                match node.transform(reduce_op.clone(), left_op.clone(), par_strat).await {
+                match node.transform(reduce_op.clone(), left_op.fork(), par_strat).await {
                     MNodeTransformResult::Unchanged(reduced) => MNodeJointTransformResult::UnchangedL(reduced),
                     MNodeTransformResult::C(cnode, reduced) => MNodeJointTransformResult::C(cnode, reduced),
                     MNodeTransformResult::L(lnode, reduced) => MNodeJointTransformResult::L(lnode, reduced),
@@ -877,7 +1806,7 @@ where
             }
         }
         else if flag == F::nth_bit(index).unwrap() {
-            match lnode::transform(right, reduce_op.clone(), right_op.clone()) {
+            match lnode::transform(right, reduce_op.clone(), right_op.fork()) {
                 LNodeTransformResult::Unchanged(reduced) => MNodeJointTransformResult::UnchangedR(reduced),
                 LNodeTransformResult::L(lnode, reduced) => MNodeJointTransformResult::L(lnode, reduced),
                 LNodeTransformResult::S(snode, reduced) => MNodeJointTransformResult::S(snode, reduced),
@@ -893,27 +1822,27 @@ where
                 size += left.unwrap().size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(left.unwrap().clone());
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
             },
             MNodeJointTransformResult::UnchangedL(r) => {
                 size += left.unwrap().size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(left.unwrap().clone());
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedr = false;
             },
             MNodeJointTransformResult::UnchangedR(r) => {
                 size += right.size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::L(right.clone()));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedl = false;
             },
             MNodeJointTransformResult::C(cnode, r) => {
                 size += cnode.size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::C(cnode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedl = false;
                 unchangedr = false;
             },
@@ -921,7 +1850,7 @@ where
                 size += lnode.size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::L(lnode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedl = false;
                 unchangedr = false;
             },
@@ -929,12 +1858,12 @@ where
                 size += 1;
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::S(snode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedl = false;
                 unchangedr = false;
             },
             MNodeJointTransformResult::Removed(r) => {
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedl = false;
                 unchangedr = false;
             },
@@ -966,10 +1895,11 @@ where
 }
 
 #[async_recursion(?Send)]
-pub(crate) async unsafe fn transform_with_transmuted_lnode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M>, right: &Arc<LNode<L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, K, V, M, ReduceT>
+pub(crate) async unsafe fn transform_with_transmuted_lnode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, A, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M, A>, right: &Arc<LNode<H, L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, K, V, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    A: Measure<K, V> + Measure<L, W>,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<V, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&L, &W) -> MapTransmuteResult<K, V, ReduceT> + Clone + Send + Sync,
@@ -980,7 +1910,7 @@ where
     M: HasherBv<H, L>,
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
-    let flag = Flag::<H, F>::new_at_depth(M::default().hash(right.key()), depth).unwrap().flag();
+    let flag = Flag::<H, F>::new_at_depth(right.hash_value::<M>(), depth).unwrap().flag();
 
     let mut size = 0;
     let mut bits_t = F::default();
@@ -994,14 +1924,14 @@ where
         let transform_result = if let Ok(node) = this.nodes.at_bit_index(index) {
             left = Some(node);
             if flag == F::nth_bit(index).unwrap() {
-                node.transform_with_transmuted_lnode(right, reduce_op.clone(), both_op.clone(), left_op.clone(), right_op.clone(), depth + 1, par_strat).await
+                node.transform_with_transmuted_lnode(right, reduce_op.clone(), both_op.fork(), left_op.fork(), right_op.fork(), depth + 1, par_strat).await
             }
             else {
-                node.transform(reduce_op.clone(), left_op.clone(), par_strat).await
+                node.transform(reduce_op.clone(), left_op.fork(), par_strat).await
             }
         }
         else if flag == F::nth_bit(index).unwrap() {
-            lnode::transmute(right, reduce_op.clone(), right_op.clone()).into()
+            lnode::transmute(right, reduce_op.clone(), right_op.fork()).into()
         }
         else {
             continue;
@@ -1012,32 +1942,32 @@ where
                 size += left.unwrap().size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(left.unwrap().clone());
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedl = false;
             },
             MNodeTransformResult::C(cnode, r) => {
                 size += cnode.size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::C(cnode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedl = false;
             },
             MNodeTransformResult::L(lnode, r) => {
                 size += lnode.size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::L(lnode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedl = false;
             },
             MNodeTransformResult::S(snode, r) => {
                 size += 1;
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::S(snode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedl = false;
             },
             MNodeTransformResult::Removed(r) => {
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedl = false;
             },
         }
@@ -1059,10 +1989,10 @@ where
     }
 }
 
-pub(crate) unsafe fn transmute_with_transformed_lnode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M>, right: &Arc<LNode<L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize) -> MNodeTransformResult<H, F, L, W, M, ReduceT>
+pub(crate) unsafe fn transmute_with_transformed_lnode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, A: Measure<K, V> + Measure<L, W>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M, A>, right: &Arc<LNode<H, L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize) -> MNodeTransformResult<H, F, L, W, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<W, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransmuteResult<L, W, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&L, &W) -> MapTransformResult<W, ReduceT> + Clone + Send + Sync,
@@ -1073,7 +2003,7 @@ where
     M: HasherBv<H, L>,
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
-    let flag = Flag::<H, F>::new_at_depth(M::default().hash(right.key()), depth).unwrap().flag();
+    let flag = Flag::<H, F>::new_at_depth(right.hash_value::<M>(), depth).unwrap().flag();
 
     let mut size = 0;
     let mut bits_t = F::default();
@@ -1084,14 +2014,14 @@ where
     for index in 0..<F>::max_ones() {
         let transform_result = if let Ok(node) = this.nodes.at_bit_index(index) {
             if flag == F::nth_bit(index).unwrap() {
-                node.transmute_with_transformed_lnode(right, reduce_op.clone(), both_op.clone(), left_op.clone(), right_op.clone(), depth + 1)
+                node.transmute_with_transformed_lnode(right, reduce_op.clone(), both_op.fork(), left_op.fork(), right_op.fork(), depth + 1)
             }
             else {
-                node.transmute(reduce_op.clone(), left_op.clone()).into()
+                node.transmute(reduce_op.clone(), left_op.fork()).into()
             }
         }
         else if flag == F::nth_bit(index).unwrap() {
-            lnode::transform(right, reduce_op.clone(), right_op.clone()).into()
+            lnode::transform(right, reduce_op.clone(), right_op.fork()).into()
         }
         else {
             continue;
@@ -1102,32 +2032,32 @@ where
                 size += right.size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::L(right.clone()));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedr = false;
             },
             MNodeTransformResult::C(cnode, r) => {
                 size += cnode.size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::C(cnode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedr = false;
             },
             MNodeTransformResult::L(lnode, r) => {
                 size += lnode.size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::L(lnode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedr = false;
             },
             MNodeTransformResult::S(snode, r) => {
                 size += 1;
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::S(snode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedr = false;
             },
             MNodeTransformResult::Removed(r) => {
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedr = false;
             },
         }
@@ -1149,10 +2079,10 @@ where
     }
 }
 
-pub(crate) unsafe fn transmute_with_transmuted_lnode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, S: Key, W: Value, X: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M>, right: &Arc<LNode<L, W>>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransmuteResult<H, F, S, X, M, ReduceT>
+pub(crate) unsafe fn transmute_with_transmuted_lnode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, S: Key, W: Value, X: Value, M: HasherBv<H, K>, A: Measure<K, V> + Measure<L, W> + Measure<S, X>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M, A>, right: &Arc<LNode<H, L, W>>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransmuteResult<H, F, S, X, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT> + Clone,
     RightOp: Fn(&L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
@@ -1168,7 +2098,7 @@ where
     M: HasherBv<H, S>,
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
-    let flag = Flag::<H, F>::new_at_depth(M::default().hash(right.key()), depth).unwrap().flag();
+    let flag = Flag::<H, F>::new_at_depth(right.hash_value::<M>(), depth).unwrap().flag();
 
     let mut size = 0;
     let mut bits_t = F::default();
@@ -1178,14 +2108,14 @@ where
     for index in 0..<F>::max_ones() {
         let transmute_result = if let Ok(node) = this.nodes.at_bit_index(index) {
             if flag == F::nth_bit(index).unwrap() {
-                node.transmute_with_transmuted_lnode(right, reduce_op.clone(), both_op.clone(), left_op.clone(), right_op.clone(), depth + 1)
+                node.transmute_with_transmuted_lnode(right, reduce_op.clone(), both_op.fork(), left_op.fork(), right_op.fork(), depth + 1)
             }
             else {
-                node.transmute(reduce_op.clone(), left_op.clone())
+                node.transmute(reduce_op.clone(), left_op.fork())
             }
         }
         else if flag == F::nth_bit(index).unwrap() {
-            lnode::transmute(right, reduce_op.clone(), right_op.clone()).into()
+            lnode::transmute(right, reduce_op.clone(), right_op.fork()).into()
         }
         else {
             continue;
@@ -1196,22 +2126,22 @@ where
                 size += cnode.size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::C(cnode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
             },
             MNodeTransmuteResult::L(lnode, r) => {
                 size += lnode.size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::L(lnode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
             },
             MNodeTransmuteResult::S(snode, r) => {
                 size += 1;
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::S(snode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
             },
             MNodeTransmuteResult::Removed(r) => {
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
             },
         }
     }
@@ -1228,16 +2158,17 @@ where
 }
 
 #[async_recursion(?Send)]
-pub(crate) async fn transform_with_transformed_snode<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M>, right: &Arc<SNode<K, V>>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeJointTransformResult<H, F, K, V, M, ReduceT>
+pub(crate) async fn transform_with_transformed_snode<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M, A>, right: &Arc<SNode<H, K, V>>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeJointTransformResult<H, F, K, V, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    A: Measure<K, V>,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT> + Clone + Send + Sync,
     LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
-    let flag = Flag::<H, F>::new_at_depth(M::default().hash(right.key()), depth).unwrap().flag();
+    let flag = Flag::<H, F>::new_at_depth(right.hash_value::<M>(), depth).unwrap().flag();
 
     let mut size = 0;
     let mut bits_t = F::default();
@@ -1252,10 +2183,10 @@ where
         let transform_result = if let Ok(node) = this.nodes.at_bit_index(index) {
             left = Some(node);
             if flag == F::nth_bit(index).unwrap() {
-                node.transform_with_transformed_snode(right, reduce_op.clone(), both_op.clone(), left_op.clone(), right_op.clone(), depth + 1, par_strat).await
+                node.transform_with_transformed_snode(right, reduce_op.clone(), both_op.fork(), left_op.fork(), right_op.fork(), depth + 1, par_strat).await
             }
             else {
-                match node.transform(reduce_op.clone(), left_op.clone(), par_strat).await {
+                match node.transform(reduce_op.clone(), left_op.fork(), par_strat).await {
                     MNodeTransformResult::Unchanged(reduced) => MNodeJointTransformResult::UnchangedL(reduced),
                     MNodeTransformResult::C(cnode, reduced) => MNodeJointTransformResult::C(cnode, reduced),
                     MNodeTransformResult::L(lnode, reduced) => MNodeJointTransformResult::L(lnode, reduced),
@@ -1265,7 +2196,7 @@ where
             }
         }
         else if flag == F::nth_bit(index).unwrap() {
-            match snode::transform(right, right_op.clone()) {
+            match snode::transform(right, right_op.fork()) {
                 SNodeTransformResult::Unchanged(reduced) => MNodeJointTransformResult::UnchangedR(reduced),
                 SNodeTransformResult::S(snode, reduced) => MNodeJointTransformResult::S(snode, reduced),
                 SNodeTransformResult::Removed(reduced) => MNodeJointTransformResult::Removed(reduced),
@@ -1280,27 +2211,27 @@ where
                 size += left.unwrap().size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(left.unwrap().clone());
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
             },
             MNodeJointTransformResult::UnchangedL(r) => {
                 size += left.unwrap().size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(left.unwrap().clone());
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedr = false;
             },
             MNodeJointTransformResult::UnchangedR(r) => {
                 size += 1;
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::S(right.clone()));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedl = false;
             },
             MNodeJointTransformResult::C(cnode, r) => {
                 size += cnode.size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::C(cnode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedl = false;
                 unchangedr = false;
             },
@@ -1308,7 +2239,7 @@ where
                 size += lnode.size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::L(lnode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedl = false;
                 unchangedr = false;
             },
@@ -1316,12 +2247,12 @@ where
                 size += 1;
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::S(snode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedl = false;
                 unchangedr = false;
             },
             MNodeJointTransformResult::Removed(r) => {
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedl = false;
                 unchangedr = false;
             },
@@ -1353,10 +2284,11 @@ where
 }
 
 #[async_recursion(?Send)]
-pub(crate) async unsafe fn transform_with_transmuted_snode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M>, right: &Arc<SNode<L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, K, V, M, ReduceT>
+pub(crate) async unsafe fn transform_with_transmuted_snode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, A, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M, A>, right: &Arc<SNode<H, L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, K, V, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    A: Measure<K, V> + Measure<L, W>,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<V, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&L, &W) -> MapTransmuteResult<K, V, ReduceT> + Clone + Send + Sync,
@@ -1367,7 +2299,7 @@ where
     M: HasherBv<H, L>,
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
-    let flag = Flag::<H, F>::new_at_depth(M::default().hash(right.key()), depth).unwrap().flag();
+    let flag = Flag::<H, F>::new_at_depth(right.hash_value::<M>(), depth).unwrap().flag();
 
     let mut size = 0;
     let mut bits_t = F::default();
@@ -1381,14 +2313,14 @@ where
         let transform_result = if let Ok(node) = this.nodes.at_bit_index(index) {
             left = Some(node);
             if flag == F::nth_bit(index).unwrap() {
-                node.transform_with_transmuted_snode(right, reduce_op.clone(), both_op.clone(), left_op.clone(), right_op.clone(), depth + 1, par_strat).await
+                node.transform_with_transmuted_snode(right, reduce_op.clone(), both_op.fork(), left_op.fork(), right_op.fork(), depth + 1, par_strat).await
             }
             else {
-                node.transform(reduce_op.clone(), left_op.clone(), par_strat).await
+                node.transform(reduce_op.clone(), left_op.fork(), par_strat).await
             }
         }
         else if flag == F::nth_bit(index).unwrap() {
-            snode::transmute(right, right_op.clone()).into()
+            snode::transmute(right, right_op.fork()).into()
         }
         else {
             continue;
@@ -1399,31 +2331,31 @@ where
                 size += left.unwrap().size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(left.unwrap().clone());
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
             },
             MNodeTransformResult::C(cnode, r) => {
                 size += cnode.size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::C(cnode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedl = false;
             },
             MNodeTransformResult::L(lnode, r) => {
                 size += lnode.size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::L(lnode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedl = false;
             },
             MNodeTransformResult::S(snode, r) => {
                 size += 1;
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::S(snode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedl = false;
             },
             MNodeTransformResult::Removed(r) => {
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedl = false;
             },
         }
@@ -1445,10 +2377,10 @@ where
     }
 }
 
-pub(crate) unsafe fn transmute_with_transformed_snode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M>, right: &Arc<SNode<L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, L, W, M, ReduceT>
+pub(crate) unsafe fn transmute_with_transformed_snode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, A: Measure<K, V> + Measure<L, W>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M, A>, right: &Arc<SNode<H, L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, L, W, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<W, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransmuteResult<L, W, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&L, &W) -> MapTransformResult<W, ReduceT> + Clone + Send + Sync,
@@ -1459,7 +2391,7 @@ where
     M: HasherBv<H, L>,
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
-    let flag = Flag::<H, F>::new_at_depth(M::default().hash(right.key()), depth).unwrap().flag();
+    let flag = Flag::<H, F>::new_at_depth(right.hash_value::<M>(), depth).unwrap().flag();
 
     let mut size = 0;
     let mut bits_t = F::default();
@@ -1470,14 +2402,14 @@ where
     for index in 0..<F>::max_ones() {
         let transform_result = if let Ok(node) = this.nodes.at_bit_index(index) {
             if flag == F::nth_bit(index).unwrap() {
-                node.transmute_with_transformed_snode(right, reduce_op.clone(), both_op.clone(), left_op.clone(), right_op.clone(), depth + 1, par_strat)
+                node.transmute_with_transformed_snode(right, reduce_op.clone(), both_op.fork(), left_op.fork(), right_op.fork(), depth + 1, par_strat)
             }
             else {
-                node.transmute(reduce_op.clone(), left_op.clone()).into()
+                node.transmute(reduce_op.clone(), left_op.fork()).into()
             }
         }
         else if flag == F::nth_bit(index).unwrap() {
-            snode::transform(right, right_op.clone()).into()
+            snode::transform(right, right_op.fork()).into()
         }
         else {
             continue;
@@ -1488,31 +2420,31 @@ where
                 size += 1;
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::S(right.clone()));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
             },
             MNodeTransformResult::C(cnode, r) => {
                 size += cnode.size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::C(cnode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedr = false;
             },
             MNodeTransformResult::L(lnode, r) => {
                 size += lnode.size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::L(lnode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedr = false;
             },
             MNodeTransformResult::S(snode, r) => {
                 size += 1;
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::S(snode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedr = false;
             },
             MNodeTransformResult::Removed(r) => {
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
                 unchangedr = false;
             },
         }
@@ -1534,10 +2466,10 @@ where
     }
 }
 
-pub(crate) unsafe fn transmute_with_transmuted_snode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, S: Key, X: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M>, right: &Arc<SNode<L, W>>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransmuteResult<H, F, S, X, M, ReduceT>
+pub(crate) unsafe fn transmute_with_transmuted_snode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, S: Key, X: Value, M: HasherBv<H, K>, A: Measure<K, V> + Measure<L, W> + Measure<S, X>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &CNode<H, F, K, V, M, A>, right: &Arc<SNode<H, L, W>>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransmuteResult<H, F, S, X, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT> + Clone,
     RightOp: Fn(&L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
@@ -1553,7 +2485,7 @@ where
     M: HasherBv<H, S>,
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
-    let flag = Flag::<H, F>::new_at_depth(M::default().hash(right.key()), depth).unwrap().flag();
+    let flag = Flag::<H, F>::new_at_depth(right.hash_value::<M>(), depth).unwrap().flag();
 
     let mut size = 0;
     let mut bits_t = F::default();
@@ -1563,14 +2495,14 @@ where
     for index in 0..<F>::max_ones() {
         let transmute_result = if let Ok(node) = this.nodes.at_bit_index(index) {
             if flag == F::nth_bit(index).unwrap() {
-                node.transmute_with_transmuted_snode(right, reduce_op.clone(), both_op.clone(), left_op.clone(), right_op.clone(), depth + 1)
+                node.transmute_with_transmuted_snode(right, reduce_op.clone(), both_op.fork(), left_op.fork(), right_op.fork(), depth + 1)
             }
             else {
-                node.transmute(reduce_op.clone(), left_op.clone())
+                node.transmute(reduce_op.clone(), left_op.fork())
             }
         }
         else if flag == F::nth_bit(index).unwrap() {
-            snode::transmute(right, right_op.clone()).into()
+            snode::transmute(right, right_op.fork()).into()
         }
         else {
             continue;
@@ -1581,22 +2513,22 @@ where
                 size += cnode.size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::C(cnode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
             },
             MNodeTransmuteResult::L(lnode, r) => {
                 size += lnode.size();
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::L(lnode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
             },
             MNodeTransmuteResult::S(snode, r) => {
                 size += 1;
                 bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
                 values_t.push(MNode::S(snode));
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
             },
             MNodeTransmuteResult::Removed(r) => {
-                reduced = reduce_op(&reduced, &r);
+                reduced = reduce_op.reduce(reduced, r);
             },
         }
     }
@@ -1613,7 +2545,7 @@ where
 }
 
 #[must_use]
-pub(super) fn lift_to_cnode_and_insert<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>>(this: MNode<H, F, K, V, M>, this_flag: Flag<H, F>, right: MNode<H, F, K, V, M>, right_flag: Flag<H, F>) -> CNode<H, F, K, V, M> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+pub(super) fn lift_to_cnode_and_insert<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>>(this: MNode<H, F, K, V, M, A>, this_flag: Flag<H, F>, right: MNode<H, F, K, V, M, A>, right_flag: Flag<H, F>) -> CNode<H, F, K, V, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
     let size = this.size() + right.size();
     if this_flag.flag() == right_flag.flag() {
         CNode::new(new_bit_indexed_array(this_flag.flag(), BitIndexedArrayVec::new(&[MNode::C(lift_to_cnode_and_insert(this, this_flag.next().unwrap(), right, right_flag.next().unwrap()))]), size).unwrap())
@@ -1629,34 +2561,41 @@ pub(super) fn lift_to_cnode_and_insert<H: Hashword, F: Flagword<H>, K: Key, V: V
     }
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static> Clone for CNode<H, F, K, V, M> {
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>> Clone for CNode<H, F, K, V, M, A> {
     fn clone(&self) -> Self {
         Self {
-            nodes: self.nodes.clone()
+            nodes: self.nodes.clone(),
+            summary: self.summary.clone(),
         }
     }
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> Default for CNode<H, F, K, V, M> {
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>> Debug for CNode<H, F, K, V, M, A> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CNode").field("nodes", &self.nodes).field("summary", &self.summary).finish()
+    }
+}
+
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> Default for CNode<H, F, K, V, M, A> {
     fn default() -> Self {
         CNode::new(default_bit_indexed_array())
     }
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> Eq for CNode<H, F, K, V, M> {}
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> Eq for CNode<H, F, K, V, M, A> {}
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> PartialEq for CNode<H, F, K, V, M> {
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> PartialEq for CNode<H, F, K, V, M, A> {
     fn eq(&self, other: &Self) -> bool {
         ptr::eq(Arc::as_ptr(&self.nodes) as *const u8, Arc::as_ptr(&other.nodes) as *const u8)
     }
 }
 
-unsafe impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> Send for CNode<H, F, K, V, M> {}
+unsafe impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> Send for CNode<H, F, K, V, M, A> {}
 
-unsafe impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> Sync for CNode<H, F, K, V, M> {}
+unsafe impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> Sync for CNode<H, F, K, V, M, A> {}
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> From<CNode<H, F, K, V, M>> for MNode<H, F, K, V, M> {
-    fn from(other: CNode<H, F, K, V, M>) -> Self {
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> From<CNode<H, F, K, V, M, A>> for MNode<H, F, K, V, M, A> {
+    fn from(other: CNode<H, F, K, V, M, A>) -> Self {
         MNode::C(other)
     }
 }
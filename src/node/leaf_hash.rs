@@ -0,0 +1,51 @@
+use crate::traits::*;
+#[cfg(not(feature = "cache_hash"))]
+use core::marker::PhantomData;
+
+#[cfg(feature = "cache_hash")]
+#[derive(Clone)]
+pub(crate) struct LeafHash<H: Hashword> {
+    hash_value: H,
+}
+
+#[cfg(feature = "cache_hash")]
+impl <H: Hashword> LeafHash<H> {
+    #[must_use]
+    pub(super) fn new(hash_value: H) -> Self {
+        Self {hash_value}
+    }
+
+    #[must_use]
+    pub(super) fn get<K, M: HasherBv<H, K>>(&self, _key: &K) -> H {
+        self.hash_value.clone()
+    }
+
+    #[must_use]
+    pub(super) fn matches(&self, hash_value: &H) -> bool {
+        self.hash_value == *hash_value
+    }
+}
+
+#[cfg(not(feature = "cache_hash"))]
+#[derive(Clone)]
+pub(crate) struct LeafHash<H: Hashword> {
+    phantom: PhantomData<H>,
+}
+
+#[cfg(not(feature = "cache_hash"))]
+impl <H: Hashword> LeafHash<H> {
+    #[must_use]
+    pub(super) fn new(_hash_value: H) -> Self {
+        Self {phantom: PhantomData}
+    }
+
+    #[must_use]
+    pub(super) fn get<K, M: HasherBv<H, K>>(&self, key: &K) -> H {
+        M::default().hash(key)
+    }
+
+    #[must_use]
+    pub(super) fn matches(&self, _hash_value: &H) -> bool {
+        true
+    }
+}
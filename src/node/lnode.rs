@@ -1,24 +1,33 @@
 use crate::{ParallelismStrategy, flag::*, transformations::*, results::*, traits::*};
-use super::{cnode::{self}, mnode::*, snode::{self, *}};
-use alloc::{fmt::Debug, sync::*, vec::Vec};
+use super::{cnode::{self}, leaf_hash::LeafHash, mnode::*, snode::{self, *}};
+use alloc::{boxed::Box, collections::BTreeSet, fmt::Debug, sync::*, vec::Vec};
+use async_recursion::async_recursion;
+use core::future::Future;
 
 #[derive(Clone, Debug)]
-pub(crate) enum LNodeNext<K: Key, V: Value> {
-    L(Arc<LNode<K, V>>),
-    S(Arc<SNode<K, V>>),
+pub(crate) enum LNodeNext<H: Hashword, K: Key, V: Value> {
+    L(Arc<LNode<H, K, V>>),
+    S(Arc<SNode<H, K, V>>),
 }
 
-impl <K: Key, V: Value> LNodeNext<K, V> {
-    fn key(&self) -> &K {
+impl <H: Hashword, K: Key, V: Value> LNodeNext<H, K, V> {
+    fn leaf_hash(&self) -> LeafHash<H> {
         match self {
-            LNodeNext::L(lnode) => lnode.key(),
-            LNodeNext::S(snode) => snode.key(),
+            LNodeNext::L(lnode) => lnode.leaf_hash(),
+            LNodeNext::S(snode) => snode.leaf_hash(),
+        }
+    }
+
+    fn hash_value<M: HasherBv<H, K>>(&self) -> H {
+        match self {
+            LNodeNext::L(lnode) => lnode.hash_value::<M>(),
+            LNodeNext::S(snode) => snode.hash_value::<M>(),
         }
     }
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> From<LNodeNext<K, V>> for MNode<H, F, K, V, M> {
-    fn from(other: LNodeNext<K, V>) -> Self {
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> From<LNodeNext<H, K, V>> for MNode<H, F, K, V, M, A> {
+    fn from(other: LNodeNext<H, K, V>) -> Self {
         match other {
             LNodeNext::L(lnode) => MNode::L(lnode),
             LNodeNext::S(snode) => MNode::S(snode),
@@ -26,15 +35,26 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> From<LNo
     }
 }
 
-#[derive(Clone, Debug)]
-pub(crate) struct LNode<K: Key, V: Value> {
+#[derive(Clone)]
+pub(crate) struct LNode<H: Hashword, K: Key, V: Value> {
+    hash: LeafHash<H>,
     key: K,
     value: V,
-    next: LNodeNext<K, V>,
+    next: LNodeNext<H, K, V>,
     size: usize,
 }
 
-impl<K: Key, V: Value> LNode<K, V> {
+impl<H: Hashword, K: Key, V: Value> LNode<H, K, V> {
+    #[must_use]
+    pub(super) fn leaf_hash(&self) -> LeafHash<H> {
+        self.hash.clone()
+    }
+
+    #[must_use]
+    pub(super) fn hash_value<M: HasherBv<H, K>>(&self) -> H {
+        self.hash.get::<K, M>(&self.key)
+    }
+
     #[must_use]
     pub(super) fn key(&self) -> &K {
         &self.key
@@ -46,12 +66,13 @@ impl<K: Key, V: Value> LNode<K, V> {
     }
 
     #[must_use]
-    pub(super) fn new(key: K, value: V, next: LNodeNext<K, V>) -> Arc<Self> {
+    pub(super) fn new(key: K, value: V, next: LNodeNext<H, K, V>) -> Arc<Self> {
         let size = 1 + match &next {
             LNodeNext::L(lnode) => lnode.size,
             LNodeNext::S(_snode) => 1,
         };
         Arc::new(Self {
+            hash: next.leaf_hash(),
             key,
             value,
             next,
@@ -72,30 +93,75 @@ impl<K: Key, V: Value> LNode<K, V> {
         }
     }
 
-    pub(super) fn find<'a, L: Key>(&'a self, key: &L) -> FindResult<'a, K, V> where K: PartialEq<L> {
-        if self.key == *key {
+    pub(super) fn fold<T, Op>(&self, init: T, op: &mut Op) -> T where Op: FnMut(T, &K, &V) -> T {
+        let folded = op(init, &self.key, &self.value);
+        match &self.next {
+            LNodeNext::L(lnode) => lnode.fold(folded, op),
+            LNodeNext::S(snode) => snode.fold(folded, op),
+        }
+    }
+
+    #[must_use]
+    pub(super) fn summary<A: Measure<K, V>>(&self) -> A::Summary {
+        let mut summaries = Vec::new();
+        self.fold((), &mut |(), key, value| summaries.push(A::measure(key, value)));
+        A::measure_collisions(summaries.into_iter())
+    }
+
+    pub(super) fn nth(&self, index: usize) -> FindResult<'_, K, V> {
+        if index == 0 {
+            FindResult::Found(&self.key, &self.value)
+        }
+        else {
+            match &self.next {
+                LNodeNext::L(lnode) => lnode.nth(index - 1),
+                LNodeNext::S(snode) => snode.nth(index - 1),
+            }
+        }
+    }
+
+    pub(super) fn map_entries<X: Value, Op: Fn(&K, &V) -> X>(&self, op: &Op) -> Arc<LNode<H, K, X>> {
+        LNode::new(self.key.clone(), op(&self.key, &self.value), match &self.next {
+            LNodeNext::L(lnode) => LNodeNext::L(lnode.map_entries(op)),
+            LNodeNext::S(snode) => LNodeNext::S(snode.map_entries(op)),
+        })
+    }
+
+    pub(super) fn entries<'a>(&'a self, entries: &mut Vec<(&'a K, &'a V)>) {
+        entries.push((&self.key, &self.value));
+        match &self.next {
+            LNodeNext::L(lnode) => lnode.entries(entries),
+            LNodeNext::S(snode) => entries.push((snode.key(), snode.value())),
+        }
+    }
+
+    pub(super) fn find<'a, L: Key>(&'a self, key: &L, hash_value: &H) -> FindResult<'a, K, V> where K: PartialEq<L> {
+        if !self.hash.matches(hash_value) {
+            FindResult::NotFound
+        }
+        else if self.key == *key {
             FindResult::Found(self.key(), self.value())
         }
         else {
             match &self.next {
-                LNodeNext::L(lnode) => lnode.find(key),
-                LNodeNext::S(snode) => snode.find(key),
+                LNodeNext::L(lnode) => lnode.find(key, hash_value),
+                LNodeNext::S(snode) => snode.find(key, hash_value),
             }
         }
     }
     
 }
 
-pub(super) fn insert<'a, H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key + Into<K>, W: Into<V>, M: HasherBv<H, K>>(this: &'a Arc<LNode<K, V>>, key: L, value: W, key_flag: Option<Flag<H, F>>, replace: bool) -> LNodeInsertResult<'a, H, F, K, V, M>
+pub(super) fn insert<'a, H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key + Into<K>, W: Into<V>, M: HasherBv<H, K>, A: Measure<K, V>>(this: &'a Arc<LNode<H, K, V>>, key: L, value: W, key_flag: Option<Flag<H, F>>, replace: bool) -> LNodeInsertResult<'a, H, F, K, V, M, A>
 where
     K: HashLike<L>,
     K: PartialEq<L>,
     M: HasherBv<H, L>,
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
-    match this.find(&key) {
+    match this.find(&key, &key_flag.as_ref().unwrap().hash_value()) {
         FindResult::Found(k, v) => if replace {
-            match remove_from_lnode(this, &key) {
+            match remove_from_lnode(this, &key, &key_flag.as_ref().unwrap().hash_value()) {
                 LNodeRemoveResult::RemovedL(lnode, k, v) => {
                     let lnode = LNode::new(key.into(), value.into(), LNodeNext::L(lnode));
                     let key: *const K = lnode.key();
@@ -118,15 +184,19 @@ where
     }
 }
 
-pub(super) fn remove<'a, H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, M: HasherBv<H, K>>(this: &'a Arc<LNode<K, V>>, key: &L) -> RemoveResult<'a, H, F, K, V, M> where K: PartialEq<L> {
-    match remove_from_lnode(this, key) {
+pub(super) fn remove<'a, H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, M: HasherBv<H, K>, A: Measure<K, V>>(this: &'a Arc<LNode<H, K, V>>, key: &L, hash_value: &H) -> RemoveResult<'a, H, F, K, V, M, A> where K: PartialEq<L> {
+    if !this.hash.matches(hash_value) {
+        return RemoveResult::NotFound;
+    }
+
+    match remove_from_lnode(this, key, hash_value) {
         LNodeRemoveResult::NotFound => RemoveResult::NotFound,
         LNodeRemoveResult::RemovedL(lnode, key, value) => RemoveResult::RemovedL(lnode, key, value),
         LNodeRemoveResult::RemovedS(snode, key, value) => RemoveResult::RemovedS(snode, key, value),
     }
 }
 
-fn remove_from_lnode<'a, K: Key, V: Value, L: Key>(this: &'a Arc<LNode<K, V>>, key: &L) -> LNodeRemoveResult<'a, K, V> where K: PartialEq<L> {
+fn remove_from_lnode<'a, H: Hashword, K: Key, V: Value, L: Key>(this: &'a Arc<LNode<H, K, V>>, key: &L, hash_value: &H) -> LNodeRemoveResult<'a, H, K, V> where K: PartialEq<L> {
     if this.key == *key {
         match &this.next {
             LNodeNext::L(lnode) => LNodeRemoveResult::RemovedL(lnode.clone(), this.key(), this.value()),
@@ -135,23 +205,34 @@ fn remove_from_lnode<'a, K: Key, V: Value, L: Key>(this: &'a Arc<LNode<K, V>>, k
     }
     else {
         match &this.next {
-            LNodeNext::L(lnode) => match remove_from_lnode(lnode, key) {
+            LNodeNext::L(lnode) => match remove_from_lnode(lnode, key, hash_value) {
                 LNodeRemoveResult::NotFound => LNodeRemoveResult::NotFound,
                 LNodeRemoveResult::RemovedL(lnode, key, value) => LNodeRemoveResult::RemovedL(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::L(lnode)), key, value),
                 LNodeRemoveResult::RemovedS(snode, key, value) => LNodeRemoveResult::RemovedL(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::S(snode)), key, value),
             },
-            LNodeNext::S(snode) => match snode::remove(snode, key) {
+            LNodeNext::S(snode) => match snode::remove(snode, key, hash_value) {
                 SNodeRemoveResult::NotFound => LNodeRemoveResult::NotFound,
-                SNodeRemoveResult::RemovedZ(key, value) => LNodeRemoveResult::RemovedS(SNode::new(this.key.clone(), this.value.clone()), key, value),
+                SNodeRemoveResult::RemovedZ(key, value) => LNodeRemoveResult::RemovedS(SNode::new(this.key.clone(), this.value.clone(), this.leaf_hash()), key, value),
             }
         }
     }
 }
 
-pub(super) fn transform<K: Key, V: Value, ReduceT, ReduceOp, Op>(this: &Arc<LNode<K, V>>, reduce_op: ReduceOp, op: MapTransform<ReduceT, Op>) -> LNodeTransformResult<K, V, ReduceT>
+pub(super) fn measure_memory<H: Hashword, K: Key, V: Value>(this: &Arc<LNode<H, K, V>>, seen: &mut BTreeSet<usize>, usage: &mut MemoryUsage) {
+    if seen.insert(Arc::as_ptr(this) as usize) {
+        usage.nodes += 1;
+        usage.bytes += core::mem::size_of::<LNode<H, K, V>>();
+        match &this.next {
+            LNodeNext::L(lnode) => measure_memory(lnode, seen, usage),
+            LNodeNext::S(snode) => snode::measure_memory(snode, seen, usage),
+        }
+    }
+}
+
+pub(super) fn transform<H: Hashword, K: Key, V: Value, ReduceT, ReduceOp, Op>(this: &Arc<LNode<H, K, V>>, reduce_op: ReduceOp, op: MapTransform<ReduceT, Op>) -> LNodeTransformResult<H, K, V, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     Op: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
 {
     match op {
@@ -161,24 +242,61 @@ where
     }
 }
 
-fn transform_impl<K: Key, V: Value, ReduceT, ReduceOp, Op>(this: &Arc<LNode<K, V>>, reduce_op: ReduceOp, op: MapTransform<ReduceT, Op>) -> LNodeTransformResult<K, V, ReduceT>
+fn transform_impl<H: Hashword, K: Key, V: Value, ReduceT, ReduceOp, Op>(this: &Arc<LNode<H, K, V>>, reduce_op: ReduceOp, op: MapTransform<ReduceT, Op>) -> LNodeTransformResult<H, K, V, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     Op: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
 {
     let next = match &this.next {
-        LNodeNext::L(lnode) => transform(lnode, reduce_op.clone(), op.clone()),
-        LNodeNext::S(snode) => snode::transform(snode, op.clone()).into(),
+        LNodeNext::L(lnode) => transform(lnode, reduce_op.clone(), op.fork()),
+        LNodeNext::S(snode) => snode::transform(snode, op.fork()).into(),
     };
 
     transform_result(this, op.call(&this.key, &this.value), next, reduce_op)
 }
 
-pub(super) unsafe fn transmute<K: Key, V: Value, S: Key, X: Value, ReduceT, ReduceOp, Op>(this: &Arc<LNode<K, V>>, reduce_op: ReduceOp, op: MapTransmute<ReduceT, Op>) -> LNodeTransmuteResult<S, X, ReduceT>
+pub(super) fn try_transform<H: Hashword, K: Key, V: Value, ReduceT, ReduceOp, Op, E>(this: &Arc<LNode<H, K, V>>, reduce_op: ReduceOp, op: Op) -> Result<LNodeTransformResult<H, K, V, ReduceT>, E>
+where
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
+    Op: Fn(&K, &V) -> Result<MapTransformResult<V, ReduceT>, E> + Clone + Send + Sync,
+{
+    let result = op(&this.key, &this.value)?;
+
+    let next = match &this.next {
+        LNodeNext::L(lnode) => try_transform(lnode, reduce_op.clone(), op)?,
+        LNodeNext::S(snode) => snode::try_transform(snode, op)?.into(),
+    };
+
+    Ok(transform_result(this, result, next, reduce_op))
+}
+
+#[async_recursion(?Send)]
+pub(super) async fn transform_async<H, K, V, ReduceT, ReduceOp, Op, Fut>(this: &Arc<LNode<H, K, V>>, reduce_op: ReduceOp, op: Op) -> LNodeTransformResult<H, K, V, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    H: Hashword,
+    K: Key,
+    V: Value,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
+    Op: Fn(&K, &V) -> Fut + Clone + Send + Sync,
+    Fut: Future<Output = MapTransformResult<V, ReduceT>>,
+{
+    let result = op(&this.key, &this.value).await;
+
+    let next = match &this.next {
+        LNodeNext::L(lnode) => transform_async(lnode, reduce_op.clone(), op).await,
+        LNodeNext::S(snode) => snode::transform_async(snode, op).await.into(),
+    };
+
+    transform_result(this, result, next, reduce_op)
+}
+
+pub(super) unsafe fn transmute<H: Hashword, K: Key, V: Value, S: Key, X: Value, ReduceT, ReduceOp, Op>(this: &Arc<LNode<H, K, V>>, reduce_op: ReduceOp, op: MapTransmute<ReduceT, Op>) -> LNodeTransmuteResult<H, S, X, ReduceT>
+where
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     Op: Fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT> + Clone,
     K: HashLike<S>,
     K: PartialEq<S>,
@@ -189,26 +307,26 @@ where
     }
 }
 
-unsafe fn transmute_impl<K: Key, V: Value, S: Key, X: Value, ReduceT, ReduceOp, Op>(this: &Arc<LNode<K, V>>, reduce_op: ReduceOp, op: MapTransmute<ReduceT, Op>) -> LNodeTransmuteResult<S, X, ReduceT>
+unsafe fn transmute_impl<H: Hashword, K: Key, V: Value, S: Key, X: Value, ReduceT, ReduceOp, Op>(this: &Arc<LNode<H, K, V>>, reduce_op: ReduceOp, op: MapTransmute<ReduceT, Op>) -> LNodeTransmuteResult<H, S, X, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     Op: Fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT> + Clone,
     K: HashLike<S>,
     K: PartialEq<S>,
 {
     let next = match &this.next {
-        LNodeNext::L(lnode) => transmute(lnode, reduce_op.clone(), op.clone()),
-        LNodeNext::S(snode) => snode::transmute(snode, op.clone()).into(),
+        LNodeNext::L(lnode) => transmute(lnode, reduce_op.clone(), op.fork()),
+        LNodeNext::S(snode) => snode::transmute(snode, op.fork()).into(),
     };
 
-    transmute_result(op.call(&this.key, &this.value), next, reduce_op)
+    transmute_result(op.call(&this.key, &this.value), next, reduce_op, this.leaf_hash())
 }
 
-pub(crate) async fn transform_with_transformed<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<K, V>>, right: &MNode<H, F, K, V, M>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeJointTransformResult<H, F, K, V, M, ReduceT>
+pub(crate) async fn transform_with_transformed<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<H, K, V>>, right: &MNode<H, F, K, V, M, A>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeJointTransformResult<H, F, K, V, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT> + Clone + Send + Sync,
     LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
@@ -221,10 +339,10 @@ where
     }
 }
 
-pub(crate) unsafe fn transform_with_transmuted<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<K, V>>, right: &MNode<H, F, L, W, M>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransformResult<H, F, K, V, M, ReduceT>
+pub(crate) unsafe fn transform_with_transmuted<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, A: Measure<K, V> + Measure<L, W>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<H, K, V>>, right: &MNode<H, F, L, W, M, A>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransformResult<H, F, K, V, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<V, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&L, &W) -> MapTransmuteResult<K, V, ReduceT> + Clone + Send + Sync,
@@ -236,16 +354,16 @@ where
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
     match right {
-        MNode::C(cnode) => cnode::transmute_with_transformed_lnode(cnode, this, reduce_op, both_op.flip_transform_transmute(), right_op, left_op, depth),
+        MNode::C(cnode) => cnode::transmute_with_transformed_lnode(cnode, this, reduce_op, both_op.flip_joint(), right_op, left_op, depth),
         MNode::L(lnode) => transform_with_transmuted_lnode(this, lnode, reduce_op, both_op, left_op, right_op, depth),
         MNode::S(snode) => transform_with_transmuted_snode(this, snode, reduce_op, both_op, left_op, right_op, depth),
     }
 }
 
-pub(crate) async unsafe fn transmute_with_transformed<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<K, V>>, right: &MNode<H, F, L, W, M>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, L, W, M, ReduceT>
+pub(crate) async unsafe fn transmute_with_transformed<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, A: Measure<K, V> + Measure<L, W>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<H, K, V>>, right: &MNode<H, F, L, W, M, A>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, L, W, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<W, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransmuteResult<L, W, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&L, &W) -> MapTransformResult<W, ReduceT> + Clone + Send + Sync,
@@ -257,16 +375,16 @@ where
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
     match right {
-        MNode::C(cnode) => cnode::transform_with_transmuted_lnode(cnode, this, reduce_op, both_op.flip_transmute_transform(), right_op, left_op, depth, par_strat).await,
-        MNode::L(lnode) => transform_with_transmuted_lnode(lnode, this, reduce_op, both_op.flip_transmute_transform(), right_op, left_op, depth),
+        MNode::C(cnode) => cnode::transform_with_transmuted_lnode(cnode, this, reduce_op, both_op.flip_joint(), right_op, left_op, depth, par_strat).await,
+        MNode::L(lnode) => transform_with_transmuted_lnode(lnode, this, reduce_op, both_op.flip_joint(), right_op, left_op, depth),
         MNode::S(snode) => transmute_with_transformed_snode(this, snode, reduce_op, both_op, left_op, right_op, depth),
     }
 }
 
-pub(crate) unsafe fn transmute_with_transmuted<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, S: Key, W: Value, X: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<K, V>>, right: &MNode<H, F, L, W, M>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransmuteResult<H, F, S, X, M, ReduceT>
+pub(crate) unsafe fn transmute_with_transmuted<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, S: Key, W: Value, X: Value, M: HasherBv<H, K>, A: Measure<K, V> + Measure<L, W> + Measure<S, X>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<H, K, V>>, right: &MNode<H, F, L, W, M, A>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransmuteResult<H, F, S, X, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT> + Clone,
     RightOp: Fn(&L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
@@ -289,17 +407,17 @@ where
     }
 }
 
-pub(crate) fn transform_with_transformed_lnode<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<K, V>>, right: &Arc<LNode<K, V>>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize) -> MNodeJointTransformResult<H, F, K, V, M, ReduceT>
+pub(crate) fn transform_with_transformed_lnode<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<H, K, V>>, right: &Arc<LNode<H, K, V>>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize) -> MNodeJointTransformResult<H, F, K, V, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT> + Clone + Send + Sync,
     LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
-    let this_hash = M::default().hash(this.key());
-    let right_hash = M::default().hash(right.key());
+    let this_hash = this.hash_value::<M>();
+    let right_hash = right.hash_value::<M>();
 
     if this_hash == right_hash {
         let mut rights = vec!((&right.key, &right.value));
@@ -308,7 +426,7 @@ where
             match &r.next {
                 LNodeNext::L(lnode) => {
                     rights.push((&lnode.key, &lnode.value));
-                    r = right;
+                    r = lnode;
                 },
                 LNodeNext::S(snode) => {
                     rights.push((snode.key(), snode.value()));
@@ -317,30 +435,30 @@ where
             }
         }
     
-        let mut next = transform_with_transformed_lnode_impl(this, &mut rights, reduce_op.clone(), both_op, left_op, right_op.clone());
+        let mut next = transform_with_transformed_lnode_impl(this, &mut rights, reduce_op.clone(), both_op, left_op, right_op.fork());
     
         for (rk, rv) in rights {
             next = match right_op.call(rk, rv) {
                 MapTransformResult::Unchanged(rr) => match next {
-                    LNodeJointTransformResult::UnchangedLR(rn) | LNodeJointTransformResult::UnchangedL(rn) => LNodeJointTransformResult::L(LNode::new(rk.clone(), rv.clone(), LNodeNext::L(this.clone())), reduce_op(&rr, &rn)),
+                    LNodeJointTransformResult::UnchangedLR(rn) | LNodeJointTransformResult::UnchangedL(rn) => LNodeJointTransformResult::L(LNode::new(rk.clone(), rv.clone(), LNodeNext::L(this.clone())), reduce_op.reduce(rr, rn)),
                     LNodeJointTransformResult::UnchangedR(_rn) => panic!(), // Too difficult to really optimize over both sides for the time being
-                    LNodeJointTransformResult::L(lnode, rn) => LNodeJointTransformResult::L(LNode::new(rk.clone(), rv.clone(), LNodeNext::L(lnode)), reduce_op(&rr, &rn)),
-                    LNodeJointTransformResult::S(snode, rn) => LNodeJointTransformResult::L(LNode::new(rk.clone(), rv.clone(), LNodeNext::S(snode)), reduce_op(&rr, &rn)),
-                    LNodeJointTransformResult::Removed(rn) => LNodeJointTransformResult::S(SNode::new(rk.clone(), rv.clone()), reduce_op(&rr, &rn)),
+                    LNodeJointTransformResult::L(lnode, rn) => LNodeJointTransformResult::L(LNode::new(rk.clone(), rv.clone(), LNodeNext::L(lnode)), reduce_op.reduce(rr, rn)),
+                    LNodeJointTransformResult::S(snode, rn) => LNodeJointTransformResult::L(LNode::new(rk.clone(), rv.clone(), LNodeNext::S(snode)), reduce_op.reduce(rr, rn)),
+                    LNodeJointTransformResult::Removed(rn) => LNodeJointTransformResult::S(SNode::new(rk.clone(), rv.clone(), this.leaf_hash()), reduce_op.reduce(rr, rn)),
                 },
                 MapTransformResult::Transformed(rv, rr) => match next {
-                    LNodeJointTransformResult::UnchangedLR(rn) | LNodeJointTransformResult::UnchangedL(rn) => LNodeJointTransformResult::L(LNode::new(rk.clone(), rv, LNodeNext::L(this.clone())), reduce_op(&rr, &rn)),
+                    LNodeJointTransformResult::UnchangedLR(rn) | LNodeJointTransformResult::UnchangedL(rn) => LNodeJointTransformResult::L(LNode::new(rk.clone(), rv, LNodeNext::L(this.clone())), reduce_op.reduce(rr, rn)),
                     LNodeJointTransformResult::UnchangedR(_rn) => panic!(), // Too difficult to really optimize over both sides for the time being
-                    LNodeJointTransformResult::L(lnode, rn) => LNodeJointTransformResult::L(LNode::new(rk.clone(), rv, LNodeNext::L(lnode)), reduce_op(&rr, &rn)),
-                    LNodeJointTransformResult::S(snode, rn) => LNodeJointTransformResult::L(LNode::new(rk.clone(), rv, LNodeNext::S(snode)), reduce_op(&rr, &rn)),
-                    LNodeJointTransformResult::Removed(rn) => LNodeJointTransformResult::S(SNode::new(rk.clone(), rv), reduce_op(&rr, &rn)),
+                    LNodeJointTransformResult::L(lnode, rn) => LNodeJointTransformResult::L(LNode::new(rk.clone(), rv, LNodeNext::L(lnode)), reduce_op.reduce(rr, rn)),
+                    LNodeJointTransformResult::S(snode, rn) => LNodeJointTransformResult::L(LNode::new(rk.clone(), rv, LNodeNext::S(snode)), reduce_op.reduce(rr, rn)),
+                    LNodeJointTransformResult::Removed(rn) => LNodeJointTransformResult::S(SNode::new(rk.clone(), rv, this.leaf_hash()), reduce_op.reduce(rr, rn)),
                 },
                 MapTransformResult::Removed(rr) => match next {
-                    LNodeJointTransformResult::UnchangedLR(rn) | LNodeJointTransformResult::UnchangedL(rn) => LNodeJointTransformResult::UnchangedL(reduce_op(&rr, &rn)),
+                    LNodeJointTransformResult::UnchangedLR(rn) | LNodeJointTransformResult::UnchangedL(rn) => LNodeJointTransformResult::UnchangedL(reduce_op.reduce(rr, rn)),
                     LNodeJointTransformResult::UnchangedR(_rn) => panic!(), // Too difficult to really optimize over both sides for the time being
-                    LNodeJointTransformResult::L(lnode, rn) => LNodeJointTransformResult::L(lnode, reduce_op(&rr, &rn)),
-                    LNodeJointTransformResult::S(snode, rn) => LNodeJointTransformResult::S(snode, reduce_op(&rr, &rn)),
-                    LNodeJointTransformResult::Removed(rn) => LNodeJointTransformResult::Removed(reduce_op(&rr, &rn)),
+                    LNodeJointTransformResult::L(lnode, rn) => LNodeJointTransformResult::L(lnode, reduce_op.reduce(rr, rn)),
+                    LNodeJointTransformResult::S(snode, rn) => LNodeJointTransformResult::S(snode, reduce_op.reduce(rr, rn)),
+                    LNodeJointTransformResult::Removed(rn) => LNodeJointTransformResult::Removed(reduce_op.reduce(rr, rn)),
                 },
             };
         }
@@ -356,37 +474,37 @@ where
 
         match left {
             LNodeTransformResult::Unchanged(lr) => match right_result {
-                LNodeTransformResult::Unchanged(rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(this.clone().into(), this_flag, right.clone().into(), right_flag), reduce_op(&lr, &rr)),
-                LNodeTransformResult::L(rlnode, rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(this.clone().into(), this_flag, rlnode.into(), right_flag), reduce_op(&lr, &rr)),
-                LNodeTransformResult::S(rsnode, rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(this.clone().into(), this_flag, rsnode.into(), right_flag), reduce_op(&lr, &rr)),
-                LNodeTransformResult::Removed(rr) => MNodeJointTransformResult::UnchangedL(reduce_op(&lr, &rr)),
+                LNodeTransformResult::Unchanged(rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(this.clone().into(), this_flag, right.clone().into(), right_flag), reduce_op.reduce(lr, rr)),
+                LNodeTransformResult::L(rlnode, rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(this.clone().into(), this_flag, rlnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                LNodeTransformResult::S(rsnode, rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(this.clone().into(), this_flag, rsnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                LNodeTransformResult::Removed(rr) => MNodeJointTransformResult::UnchangedL(reduce_op.reduce(lr, rr)),
             },
             LNodeTransformResult::L(llnode, lr) => match right_result {
-                LNodeTransformResult::Unchanged(rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(llnode.into(), this_flag, right.clone().into(), right_flag), reduce_op(&lr, &rr)),
-                LNodeTransformResult::L(rlnode, rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(llnode.into(), this_flag, rlnode.into(), right_flag), reduce_op(&lr, &rr)),
-                LNodeTransformResult::S(rsnode, rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(llnode.into(), this_flag, rsnode.into(), right_flag), reduce_op(&lr, &rr)),
-                LNodeTransformResult::Removed(rr) => MNodeJointTransformResult::L(llnode, reduce_op(&lr, &rr)),
+                LNodeTransformResult::Unchanged(rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(llnode.into(), this_flag, right.clone().into(), right_flag), reduce_op.reduce(lr, rr)),
+                LNodeTransformResult::L(rlnode, rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(llnode.into(), this_flag, rlnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                LNodeTransformResult::S(rsnode, rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(llnode.into(), this_flag, rsnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                LNodeTransformResult::Removed(rr) => MNodeJointTransformResult::L(llnode, reduce_op.reduce(lr, rr)),
             },
             LNodeTransformResult::S(lsnode, lr) => match right_result {
-                LNodeTransformResult::Unchanged(rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, right.clone().into(), right_flag), reduce_op(&lr, &rr)),
-                LNodeTransformResult::L(rlnode, rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, rlnode.into(), right_flag), reduce_op(&lr, &rr)),
-                LNodeTransformResult::S(rsnode, rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, rsnode.into(), right_flag), reduce_op(&lr, &rr)),
-                LNodeTransformResult::Removed(rr) => MNodeJointTransformResult::S(lsnode, reduce_op(&lr, &rr)),
+                LNodeTransformResult::Unchanged(rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, right.clone().into(), right_flag), reduce_op.reduce(lr, rr)),
+                LNodeTransformResult::L(rlnode, rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, rlnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                LNodeTransformResult::S(rsnode, rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, rsnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                LNodeTransformResult::Removed(rr) => MNodeJointTransformResult::S(lsnode, reduce_op.reduce(lr, rr)),
             },
             LNodeTransformResult::Removed(lr) => match right_result {
-                LNodeTransformResult::Unchanged(rr) => MNodeJointTransformResult::UnchangedR(reduce_op(&lr, &rr)),
-                LNodeTransformResult::L(rlnode, rr) => MNodeJointTransformResult::L(rlnode, reduce_op(&lr, &rr)),
-                LNodeTransformResult::S(rsnode, rr) => MNodeJointTransformResult::S(rsnode, reduce_op(&lr, &rr)),
-                LNodeTransformResult::Removed(rr) => MNodeJointTransformResult::Removed(reduce_op(&lr, &rr)),
+                LNodeTransformResult::Unchanged(rr) => MNodeJointTransformResult::UnchangedR(reduce_op.reduce(lr, rr)),
+                LNodeTransformResult::L(rlnode, rr) => MNodeJointTransformResult::L(rlnode, reduce_op.reduce(lr, rr)),
+                LNodeTransformResult::S(rsnode, rr) => MNodeJointTransformResult::S(rsnode, reduce_op.reduce(lr, rr)),
+                LNodeTransformResult::Removed(rr) => MNodeJointTransformResult::Removed(reduce_op.reduce(lr, rr)),
             },
         }
     }
 }
 
-fn transform_with_transformed_lnode_impl<K: Key, V: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<K, V>>, rights: &mut Vec<(&K, &V)>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>) -> LNodeJointTransformResult<K, V, ReduceT>
+fn transform_with_transformed_lnode_impl<H: Hashword, K: Key, V: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<H, K, V>>, rights: &mut Vec<(&K, &V)>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>) -> LNodeJointTransformResult<H, K, V, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT> + Clone + Send + Sync,
     LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
@@ -394,14 +512,14 @@ where
     let mut next_node = None;
 
     let next = match &this.next {
-        LNodeNext::L(lnode) => transform_with_transformed_lnode_impl(lnode, rights, reduce_op.clone(), both_op.clone(), left_op.clone(), right_op.clone()),
+        LNodeNext::L(lnode) => transform_with_transformed_lnode_impl(lnode, rights, reduce_op.clone(), both_op.fork(), left_op.fork(), right_op.fork()),
         LNodeNext::S(snode) => {
             match rights.iter().position(|key_value| *snode.key() == *key_value.0).map(|index| rights.swap_remove(index)) {
                 Some((right_key, right_value)) => {
                     next_node = Some((right_key, right_value));
-                    snode::transform_with_transformed_snode_impl(snode, &SNode::new(right_key.clone(), right_value.clone()), reduce_op.clone(), both_op.clone(), left_op.clone(), right_op.clone())
+                    snode::transform_with_transformed_snode_impl(snode, &SNode::new(right_key.clone(), right_value.clone(), snode.leaf_hash()), reduce_op.clone(), both_op.fork(), left_op.fork(), right_op.fork())
                 },
-                None => match snode::transform(snode, left_op.clone()) {
+                None => match snode::transform(snode, left_op.fork()) {
                     SNodeTransformResult::Unchanged(reduced) => LNodeJointTransformResult::UnchangedL(reduced),
                     SNodeTransformResult::S(snode, reduced) => LNodeJointTransformResult::S(snode, reduced),
                     SNodeTransformResult::Removed(reduced) => LNodeJointTransformResult::Removed(reduced),
@@ -422,10 +540,10 @@ where
     joint_transform_with_joint_transformed_result(this, result, next_node, next, reduce_op)
 }
 
-pub(crate) unsafe fn transform_with_transmuted_lnode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<K, V>>, right: &Arc<LNode<L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransformResult<H, F, K, V, M, ReduceT>
+pub(crate) unsafe fn transform_with_transmuted_lnode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, A: Measure<K, V> + Measure<L, W>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<H, K, V>>, right: &Arc<LNode<H, L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransformResult<H, F, K, V, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<V, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&L, &W) -> MapTransmuteResult<K, V, ReduceT> + Clone + Send + Sync,
@@ -436,8 +554,8 @@ where
     M: HasherBv<H, L>,
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
-    let this_hash = M::default().hash(this.key());
-    let right_hash = M::default().hash(right.key());
+    let this_hash = this.hash_value::<M>();
+    let right_hash = right.hash_value::<M>();
 
     if this_hash == right_hash {
         let mut rights = vec!((&right.key, &right.value));
@@ -446,7 +564,7 @@ where
             match &r.next {
                 LNodeNext::L(lnode) => {
                     rights.push((&lnode.key, &lnode.value));
-                    r = right;
+                    r = lnode;
                 },
                 LNodeNext::S(snode) => {
                     rights.push((snode.key(), snode.value()));
@@ -455,21 +573,21 @@ where
             }
         }
     
-        let mut next = transform_with_transmuted_lnode_impl(this, &mut rights, reduce_op.clone(), both_op, left_op, right_op.clone());
+        let mut next = transform_with_transmuted_lnode_impl(this, &mut rights, reduce_op.clone(), both_op, left_op, right_op.fork());
     
         for (rk, rv) in rights {
             next = match right_op.call(rk, rv) {
                 MapTransmuteResult::Transmuted(rk, rv, rr) => match next {
-                    LNodeTransformResult::Unchanged(rn) => LNodeTransformResult::L(LNode::new(rk, rv, LNodeNext::L(this.clone())), reduce_op(&rr, &rn)),
-                    LNodeTransformResult::L(lnode, rn) => LNodeTransformResult::L(LNode::new(rk, rv, LNodeNext::L(lnode)), reduce_op(&rr, &rn)),
-                    LNodeTransformResult::S(snode, rn) => LNodeTransformResult::L(LNode::new(rk, rv, LNodeNext::S(snode)), reduce_op(&rr, &rn)),
-                    LNodeTransformResult::Removed(rn) => LNodeTransformResult::S(SNode::new(rk, rv), reduce_op(&rr, &rn)),
+                    LNodeTransformResult::Unchanged(rn) => LNodeTransformResult::L(LNode::new(rk, rv, LNodeNext::L(this.clone())), reduce_op.reduce(rr, rn)),
+                    LNodeTransformResult::L(lnode, rn) => LNodeTransformResult::L(LNode::new(rk, rv, LNodeNext::L(lnode)), reduce_op.reduce(rr, rn)),
+                    LNodeTransformResult::S(snode, rn) => LNodeTransformResult::L(LNode::new(rk, rv, LNodeNext::S(snode)), reduce_op.reduce(rr, rn)),
+                    LNodeTransformResult::Removed(rn) => LNodeTransformResult::S(SNode::new(rk, rv, this.leaf_hash()), reduce_op.reduce(rr, rn)),
                 },
                 MapTransmuteResult::Removed(rr) => match next {
-                    LNodeTransformResult::Unchanged(rn) => LNodeTransformResult::Unchanged(reduce_op(&rr, &rn)),
-                    LNodeTransformResult::L(lnode, rn) => LNodeTransformResult::L(lnode, reduce_op(&rr, &rn)),
-                    LNodeTransformResult::S(snode, rn) => LNodeTransformResult::S(snode, reduce_op(&rr, &rn)),
-                    LNodeTransformResult::Removed(rn) => LNodeTransformResult::Removed(reduce_op(&rr, &rn)),
+                    LNodeTransformResult::Unchanged(rn) => LNodeTransformResult::Unchanged(reduce_op.reduce(rr, rn)),
+                    LNodeTransformResult::L(lnode, rn) => LNodeTransformResult::L(lnode, reduce_op.reduce(rr, rn)),
+                    LNodeTransformResult::S(snode, rn) => LNodeTransformResult::S(snode, reduce_op.reduce(rr, rn)),
+                    LNodeTransformResult::Removed(rn) => LNodeTransformResult::Removed(reduce_op.reduce(rr, rn)),
                 },
             };
         }
@@ -485,33 +603,33 @@ where
 
         match left {
             LNodeTransformResult::Unchanged(lr) => match right {
-                LNodeTransmuteResult::L(rlnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(this.clone().into(), this_flag, rlnode.into(), right_flag), reduce_op(&lr, &rr)),
-                LNodeTransmuteResult::S(rsnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(this.clone().into(), this_flag, rsnode.into(), right_flag), reduce_op(&lr, &rr)),
-                LNodeTransmuteResult::Removed(rr) => MNodeTransformResult::Unchanged(reduce_op(&lr, &rr)),
+                LNodeTransmuteResult::L(rlnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(this.clone().into(), this_flag, rlnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                LNodeTransmuteResult::S(rsnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(this.clone().into(), this_flag, rsnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                LNodeTransmuteResult::Removed(rr) => MNodeTransformResult::Unchanged(reduce_op.reduce(lr, rr)),
             },
             LNodeTransformResult::L(llnode, lr) => match right {
-                LNodeTransmuteResult::L(rlnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(llnode.into(), this_flag, rlnode.into(), right_flag), reduce_op(&lr, &rr)),
-                LNodeTransmuteResult::S(rsnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(llnode.into(), this_flag, rsnode.into(), right_flag), reduce_op(&lr, &rr)),
-                LNodeTransmuteResult::Removed(rr) => MNodeTransformResult::L(llnode, reduce_op(&lr, &rr)),
+                LNodeTransmuteResult::L(rlnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(llnode.into(), this_flag, rlnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                LNodeTransmuteResult::S(rsnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(llnode.into(), this_flag, rsnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                LNodeTransmuteResult::Removed(rr) => MNodeTransformResult::L(llnode, reduce_op.reduce(lr, rr)),
             },
             LNodeTransformResult::S(lsnode, lr) => match right {
-                LNodeTransmuteResult::L(rlnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, rlnode.into(), right_flag), reduce_op(&lr, &rr)),
-                LNodeTransmuteResult::S(rsnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, rsnode.into(), right_flag), reduce_op(&lr, &rr)),
-                LNodeTransmuteResult::Removed(rr) => MNodeTransformResult::S(lsnode, reduce_op(&lr, &rr)),
+                LNodeTransmuteResult::L(rlnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, rlnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                LNodeTransmuteResult::S(rsnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, rsnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                LNodeTransmuteResult::Removed(rr) => MNodeTransformResult::S(lsnode, reduce_op.reduce(lr, rr)),
             },
             LNodeTransformResult::Removed(lr) => match right {
-                LNodeTransmuteResult::L(rlnode, rr) => MNodeTransformResult::L(rlnode, reduce_op(&lr, &rr)),
-                LNodeTransmuteResult::S(rsnode, rr) => MNodeTransformResult::S(rsnode, reduce_op(&lr, &rr)),
-                LNodeTransmuteResult::Removed(rr) => MNodeTransformResult::Removed(reduce_op(&lr, &rr)),
+                LNodeTransmuteResult::L(rlnode, rr) => MNodeTransformResult::L(rlnode, reduce_op.reduce(lr, rr)),
+                LNodeTransmuteResult::S(rsnode, rr) => MNodeTransformResult::S(rsnode, reduce_op.reduce(lr, rr)),
+                LNodeTransmuteResult::Removed(rr) => MNodeTransformResult::Removed(reduce_op.reduce(lr, rr)),
             },
         }
     }
 }
 
-unsafe fn transform_with_transmuted_lnode_impl<K: Key, V: Value, L: Key, W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<K, V>>, rights: &mut Vec<(&L, &W)>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>) -> LNodeTransformResult<K, V, ReduceT>
+unsafe fn transform_with_transmuted_lnode_impl<H: Hashword, K: Key, V: Value, L: Key, W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<H, K, V>>, rights: &mut Vec<(&L, &W)>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>) -> LNodeTransformResult<H, K, V, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<V, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&L, &W) -> MapTransmuteResult<K, V, ReduceT> + Clone + Send + Sync,
@@ -521,27 +639,27 @@ where
     L: PartialEq<K>,
 {
     let next = match &this.next {
-        LNodeNext::L(lnode) => transform_with_transmuted_lnode_impl(lnode, rights, reduce_op.clone(), both_op.clone(), left_op.clone(), right_op.clone()),
+        LNodeNext::L(lnode) => transform_with_transmuted_lnode_impl(lnode, rights, reduce_op.clone(), both_op.fork(), left_op.fork(), right_op.fork()),
         LNodeNext::S(snode) => {
             match rights.iter().position(|key_value| *snode.key() == *key_value.0).map(|index| rights.swap_remove(index)) {
-                Some((right_key, right_value)) => snode::transform_with_transmuted_snode_impl(snode, right_key, right_value, reduce_op.clone(), both_op.clone(), left_op.clone(), right_op.clone()),
-                None => snode::transform(snode, left_op.clone()).into(),
+                Some((right_key, right_value)) => snode::transform_with_transmuted_snode_impl(snode, right_key, right_value, reduce_op.clone(), both_op.fork(), left_op.fork(), right_op.fork()),
+                None => snode::transform(snode, left_op.fork()).into(),
             }
         },
     };
 
     let result = match rights.iter().position(|key_value| this.key == *key_value.0).map(|index| rights.swap_remove(index)) {
-        Some((right_key, right_value)) => both_op.call_transform_transmute(&this.key, &this.value, right_key, right_value),
+        Some((right_key, right_value)) => both_op.call_joint(&this.key, &this.value, right_key, right_value),
         None => left_op.call(&this.key, &this.value),
     };
 
     transform_result(this, result, next, reduce_op)
 }
 
-pub(crate) unsafe fn transmute_with_transmuted_lnode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, S: Key, X: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<K, V>>, right: &Arc<LNode<L, W>>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransmuteResult<H, F, S, X, M, ReduceT>
+pub(crate) unsafe fn transmute_with_transmuted_lnode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, S: Key, X: Value, M: HasherBv<H, K>, A: Measure<K, V> + Measure<L, W> + Measure<S, X>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<H, K, V>>, right: &Arc<LNode<H, L, W>>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransmuteResult<H, F, S, X, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT> + Clone,
     RightOp: Fn(&L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
@@ -557,8 +675,8 @@ where
     M: HasherBv<H, S>,
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
-    let this_hash = M::default().hash(this.key());
-    let right_hash = M::default().hash(right.key());
+    let this_hash = this.hash_value::<M>();
+    let right_hash = right.hash_value::<M>();
 
     if this_hash == right_hash {
         let mut rights = vec!((&right.key, &right.value));
@@ -567,7 +685,7 @@ where
             match &r.next {
                 LNodeNext::L(lnode) => {
                     rights.push((&lnode.key, &lnode.value));
-                    r = right;
+                    r = lnode;
                 },
                 LNodeNext::S(snode) => {
                     rights.push((snode.key(), snode.value()));
@@ -576,19 +694,19 @@ where
             }
         }
     
-        let mut next = transmute_with_transmuted_lnode_impl(this, &mut rights, reduce_op.clone(), both_op, left_op, right_op.clone());
+        let mut next = transmute_with_transmuted_lnode_impl(this, &mut rights, reduce_op.clone(), both_op, left_op, right_op.fork());
     
         for (rk, rv) in rights {
             next = match right_op.call(rk, rv) {
                 MapTransmuteResult::Transmuted(rk, rv, rr) => match next {
-                    LNodeTransmuteResult::L(lnode, rn) => LNodeTransmuteResult::L(LNode::new(rk, rv, LNodeNext::L(lnode)), reduce_op(&rr, &rn)),
-                    LNodeTransmuteResult::S(snode, rn) => LNodeTransmuteResult::L(LNode::new(rk, rv, LNodeNext::S(snode)), reduce_op(&rr, &rn)),
-                    LNodeTransmuteResult::Removed(rn) => LNodeTransmuteResult::S(SNode::new(rk, rv), reduce_op(&rr, &rn)),
+                    LNodeTransmuteResult::L(lnode, rn) => LNodeTransmuteResult::L(LNode::new(rk, rv, LNodeNext::L(lnode)), reduce_op.reduce(rr, rn)),
+                    LNodeTransmuteResult::S(snode, rn) => LNodeTransmuteResult::L(LNode::new(rk, rv, LNodeNext::S(snode)), reduce_op.reduce(rr, rn)),
+                    LNodeTransmuteResult::Removed(rn) => LNodeTransmuteResult::S(SNode::new(rk, rv, this.leaf_hash()), reduce_op.reduce(rr, rn)),
                 },
                 MapTransmuteResult::Removed(rr) => match next {
-                    LNodeTransmuteResult::L(lnode, rn) => LNodeTransmuteResult::L(lnode, reduce_op(&rr, &rn)),
-                    LNodeTransmuteResult::S(snode, rn) => LNodeTransmuteResult::S(snode, reduce_op(&rr, &rn)),
-                    LNodeTransmuteResult::Removed(rn) => LNodeTransmuteResult::Removed(reduce_op(&rr, &rn)),
+                    LNodeTransmuteResult::L(lnode, rn) => LNodeTransmuteResult::L(lnode, reduce_op.reduce(rr, rn)),
+                    LNodeTransmuteResult::S(snode, rn) => LNodeTransmuteResult::S(snode, reduce_op.reduce(rr, rn)),
+                    LNodeTransmuteResult::Removed(rn) => LNodeTransmuteResult::Removed(reduce_op.reduce(rr, rn)),
                 },
             };
         }
@@ -604,28 +722,28 @@ where
 
         match this {
             LNodeTransmuteResult::L(llnode, lr) => match right {
-                LNodeTransmuteResult::L(rlnode, rr) => MNodeTransmuteResult::C(cnode::lift_to_cnode_and_insert(llnode.into(), this_flag, rlnode.into(), right_flag), reduce_op(&lr, &rr)),
-                LNodeTransmuteResult::S(rsnode, rr) => MNodeTransmuteResult::C(cnode::lift_to_cnode_and_insert(llnode.into(), this_flag, rsnode.into(), right_flag), reduce_op(&lr, &rr)),
-                LNodeTransmuteResult::Removed(rr) => MNodeTransmuteResult::L(llnode, reduce_op(&lr, &rr)),
+                LNodeTransmuteResult::L(rlnode, rr) => MNodeTransmuteResult::C(cnode::lift_to_cnode_and_insert(llnode.into(), this_flag, rlnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                LNodeTransmuteResult::S(rsnode, rr) => MNodeTransmuteResult::C(cnode::lift_to_cnode_and_insert(llnode.into(), this_flag, rsnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                LNodeTransmuteResult::Removed(rr) => MNodeTransmuteResult::L(llnode, reduce_op.reduce(lr, rr)),
             },
             LNodeTransmuteResult::S(lsnode, lr) => match right {
-                LNodeTransmuteResult::L(rlnode, rr) => MNodeTransmuteResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, rlnode.into(), right_flag), reduce_op(&lr, &rr)),
-                LNodeTransmuteResult::S(rsnode, rr) => MNodeTransmuteResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, rsnode.into(), right_flag), reduce_op(&lr, &rr)),
-                LNodeTransmuteResult::Removed(rr) => MNodeTransmuteResult::S(lsnode, reduce_op(&lr, &rr)),
+                LNodeTransmuteResult::L(rlnode, rr) => MNodeTransmuteResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, rlnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                LNodeTransmuteResult::S(rsnode, rr) => MNodeTransmuteResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, rsnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                LNodeTransmuteResult::Removed(rr) => MNodeTransmuteResult::S(lsnode, reduce_op.reduce(lr, rr)),
             },
             LNodeTransmuteResult::Removed(lr) => match right {
-                LNodeTransmuteResult::L(rlnode, rr) => MNodeTransmuteResult::L(rlnode, reduce_op(&lr, &rr)),
-                LNodeTransmuteResult::S(rsnode, rr) => MNodeTransmuteResult::S(rsnode, reduce_op(&lr, &rr)),
-                LNodeTransmuteResult::Removed(rr) => MNodeTransmuteResult::Removed(reduce_op(&lr, &rr)),
+                LNodeTransmuteResult::L(rlnode, rr) => MNodeTransmuteResult::L(rlnode, reduce_op.reduce(lr, rr)),
+                LNodeTransmuteResult::S(rsnode, rr) => MNodeTransmuteResult::S(rsnode, reduce_op.reduce(lr, rr)),
+                LNodeTransmuteResult::Removed(rr) => MNodeTransmuteResult::Removed(reduce_op.reduce(lr, rr)),
             },
         }
     }
 }
 
-unsafe fn transmute_with_transmuted_lnode_impl<K: Key, V: Value, L: Key, W: Value, S: Key, X: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<K, V>>, rights: &mut Vec<(&L, &W)>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>) -> LNodeTransmuteResult<S, X, ReduceT>
+unsafe fn transmute_with_transmuted_lnode_impl<H: Hashword, K: Key, V: Value, L: Key, W: Value, S: Key, X: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<H, K, V>>, rights: &mut Vec<(&L, &W)>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>) -> LNodeTransmuteResult<H, S, X, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT> + Clone,
     RightOp: Fn(&L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
@@ -639,11 +757,11 @@ where
     L: PartialEq<S>,
 {
     let next = match &this.next {
-        LNodeNext::L(lnode) => transmute_with_transmuted_lnode_impl(lnode, rights, reduce_op.clone(), both_op.clone(), left_op.clone(), right_op.clone()),
+        LNodeNext::L(lnode) => transmute_with_transmuted_lnode_impl(lnode, rights, reduce_op.clone(), both_op.fork(), left_op.fork(), right_op.fork()),
         LNodeNext::S(snode) => {
             match rights.iter().position(|key_value| *snode.key() == *key_value.0).map(|index| rights.swap_remove(index)) {
-                Some((right_key, right_value)) => snode::transmute_with_transmuted_values(snode.key(), snode.value(), right_key, right_value, reduce_op.clone(), both_op.clone(), left_op.clone(), right_op.clone()),
-                None => snode::transmute(snode, left_op.clone()).into(),
+                Some((right_key, right_value)) => snode::transmute_with_transmuted_values(snode.key(), snode.value(), right_key, right_value, snode.leaf_hash(), reduce_op.clone(), both_op.fork(), left_op.fork(), right_op.fork()),
+                None => snode::transmute(snode, left_op.fork()).into(),
             }
         },
     };
@@ -653,20 +771,20 @@ where
         None => left_op.call(&this.key, &this.value),
     };
 
-    transmute_result(result, next, reduce_op)
+    transmute_result(result, next, reduce_op, this.leaf_hash())
 }
 
-pub(crate) fn transform_with_transformed_snode<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<K, V>>, right: &Arc<SNode<K, V>>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize) -> MNodeJointTransformResult<H, F, K, V, M, ReduceT>
+pub(crate) fn transform_with_transformed_snode<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<H, K, V>>, right: &Arc<SNode<H, K, V>>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize) -> MNodeJointTransformResult<H, F, K, V, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT> + Clone + Send + Sync,
     LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
-    let this_hash = M::default().hash(this.key());
-    let right_hash = M::default().hash(right.key());
+    let this_hash = this.hash_value::<M>();
+    let right_hash = right.hash_value::<M>();
 
     if this_hash == right_hash {
         transform_with_transformed_snode_impl(this, right, reduce_op, both_op, left_op, right_op).into()
@@ -680,33 +798,33 @@ where
 
         match left {
             LNodeTransformResult::Unchanged(lr) => match r {
-                SNodeTransformResult::Unchanged(rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(this.clone().into(), this_flag, right.clone().into(), right_flag), reduce_op(&lr, &rr)),
-                SNodeTransformResult::S(rsnode, rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(this.clone().into(), this_flag, rsnode.into(), right_flag), reduce_op(&lr, &rr)),
-                SNodeTransformResult::Removed(rr) => MNodeJointTransformResult::UnchangedL(reduce_op(&lr, &rr)),
+                SNodeTransformResult::Unchanged(rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(this.clone().into(), this_flag, right.clone().into(), right_flag), reduce_op.reduce(lr, rr)),
+                SNodeTransformResult::S(rsnode, rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(this.clone().into(), this_flag, rsnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                SNodeTransformResult::Removed(rr) => MNodeJointTransformResult::UnchangedL(reduce_op.reduce(lr, rr)),
             },
             LNodeTransformResult::L(llnode, lr) => match r {
-                SNodeTransformResult::Unchanged(rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(llnode.into(), this_flag, right.clone().into(), right_flag), reduce_op(&lr, &rr)),
-                SNodeTransformResult::S(rsnode, rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(llnode.into(), this_flag, rsnode.into(), right_flag), reduce_op(&lr, &rr)),
-                SNodeTransformResult::Removed(rr) => MNodeJointTransformResult::L(llnode, reduce_op(&lr, &rr)),
+                SNodeTransformResult::Unchanged(rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(llnode.into(), this_flag, right.clone().into(), right_flag), reduce_op.reduce(lr, rr)),
+                SNodeTransformResult::S(rsnode, rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(llnode.into(), this_flag, rsnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                SNodeTransformResult::Removed(rr) => MNodeJointTransformResult::L(llnode, reduce_op.reduce(lr, rr)),
             },
             LNodeTransformResult::S(lsnode, lr) => match r {
-                SNodeTransformResult::Unchanged(rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, right.clone().into(), right_flag), reduce_op(&lr, &rr)),
-                SNodeTransformResult::S(rsnode, rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, rsnode.into(), right_flag), reduce_op(&lr, &rr)),
-                SNodeTransformResult::Removed(rr) => MNodeJointTransformResult::S(lsnode, reduce_op(&lr, &rr)),
+                SNodeTransformResult::Unchanged(rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, right.clone().into(), right_flag), reduce_op.reduce(lr, rr)),
+                SNodeTransformResult::S(rsnode, rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, rsnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                SNodeTransformResult::Removed(rr) => MNodeJointTransformResult::S(lsnode, reduce_op.reduce(lr, rr)),
             },
             LNodeTransformResult::Removed(lr) => match r {
-                SNodeTransformResult::Unchanged(rr) => MNodeJointTransformResult::UnchangedR(reduce_op(&lr, &rr)),
-                SNodeTransformResult::S(rsnode, rr) => MNodeJointTransformResult::S(rsnode, reduce_op(&lr, &rr)),
-                SNodeTransformResult::Removed(rr) => MNodeJointTransformResult::Removed(reduce_op(&lr, &rr)),
+                SNodeTransformResult::Unchanged(rr) => MNodeJointTransformResult::UnchangedR(reduce_op.reduce(lr, rr)),
+                SNodeTransformResult::S(rsnode, rr) => MNodeJointTransformResult::S(rsnode, reduce_op.reduce(lr, rr)),
+                SNodeTransformResult::Removed(rr) => MNodeJointTransformResult::Removed(reduce_op.reduce(lr, rr)),
             },
         }
     }
 }
 
-pub(crate) fn transform_with_transformed_snode_impl<K: Key, V: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<K, V>>, right: &Arc<SNode<K, V>>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>) -> LNodeJointTransformResult<K, V, ReduceT>
+pub(crate) fn transform_with_transformed_snode_impl<H: Hashword, K: Key, V: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<H, K, V>>, right: &Arc<SNode<H, K, V>>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>) -> LNodeJointTransformResult<H, K, V, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT> + Clone + Send + Sync,
     LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
@@ -721,18 +839,18 @@ where
     }
     else {
         let next = match &this.next {
-            LNodeNext::L(lnode) => transform_with_transformed_snode_impl(lnode, right, reduce_op.clone(), both_op, left_op.clone(), right_op),
-            LNodeNext::S(snode) => snode::transform_with_transformed_snode_impl(snode, right, reduce_op.clone(), both_op, left_op.clone(), right_op),
+            LNodeNext::L(lnode) => transform_with_transformed_snode_impl(lnode, right, reduce_op.clone(), both_op, left_op.fork(), right_op),
+            LNodeNext::S(snode) => snode::transform_with_transformed_snode_impl(snode, right, reduce_op.clone(), both_op, left_op.fork(), right_op),
         };
 
         transform_with_joint_transformed_result(this, left_op.call(this.key(), this.value()), right, next, reduce_op)
     }
 }
 
-pub(crate) unsafe fn transform_with_transmuted_snode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<K, V>>, right: &Arc<SNode<L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransformResult<H, F, K, V, M, ReduceT>
+pub(crate) unsafe fn transform_with_transmuted_snode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, A: Measure<K, V> + Measure<L, W>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<H, K, V>>, right: &Arc<SNode<H, L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransformResult<H, F, K, V, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<V, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&L, &W) -> MapTransmuteResult<K, V, ReduceT> + Clone + Send + Sync,
@@ -743,8 +861,8 @@ where
     M: HasherBv<H, L>,
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
-    let this_hash = M::default().hash(this.key());
-    let right_hash = M::default().hash(right.key());
+    let this_hash = this.hash_value::<M>();
+    let right_hash = right.hash_value::<M>();
 
     if this_hash == right_hash {
         transform_with_transmuted_snode_impl(this, right, reduce_op, both_op, left_op, right_op).into()
@@ -758,29 +876,29 @@ where
 
         match left {
             LNodeTransformResult::Unchanged(lr) => match right {
-                SNodeTransmuteResult::S(rsnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(this.clone().into(), this_flag, rsnode.into(), right_flag), reduce_op(&lr, &rr)),
-                SNodeTransmuteResult::Removed(rr) => MNodeTransformResult::Unchanged(reduce_op(&lr, &rr)),
+                SNodeTransmuteResult::S(rsnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(this.clone().into(), this_flag, rsnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                SNodeTransmuteResult::Removed(rr) => MNodeTransformResult::Unchanged(reduce_op.reduce(lr, rr)),
             },
             LNodeTransformResult::L(llnode, lr) => match right {
-                SNodeTransmuteResult::S(rsnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(llnode.into(), this_flag, rsnode.into(), right_flag), reduce_op(&lr, &rr)),
-                SNodeTransmuteResult::Removed(rr) => MNodeTransformResult::L(llnode, reduce_op(&lr, &rr)),
+                SNodeTransmuteResult::S(rsnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(llnode.into(), this_flag, rsnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                SNodeTransmuteResult::Removed(rr) => MNodeTransformResult::L(llnode, reduce_op.reduce(lr, rr)),
             },
             LNodeTransformResult::S(lsnode, lr) => match right {
-                SNodeTransmuteResult::S(rsnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, rsnode.into(), right_flag), reduce_op(&lr, &rr)),
-                SNodeTransmuteResult::Removed(rr) => MNodeTransformResult::S(lsnode, reduce_op(&lr, &rr)),
+                SNodeTransmuteResult::S(rsnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, rsnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                SNodeTransmuteResult::Removed(rr) => MNodeTransformResult::S(lsnode, reduce_op.reduce(lr, rr)),
             },
             LNodeTransformResult::Removed(lr) => match right {
-                SNodeTransmuteResult::S(rsnode, rr) => MNodeTransformResult::S(rsnode, reduce_op(&lr, &rr)),
-                SNodeTransmuteResult::Removed(rr) => MNodeTransformResult::Removed(reduce_op(&lr, &rr)),
+                SNodeTransmuteResult::S(rsnode, rr) => MNodeTransformResult::S(rsnode, reduce_op.reduce(lr, rr)),
+                SNodeTransmuteResult::Removed(rr) => MNodeTransformResult::Removed(reduce_op.reduce(lr, rr)),
             },
         }
     }
 }
 
-pub(crate) unsafe fn transform_with_transmuted_snode_impl<K: Key, V: Value, L: Key, W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<K, V>>, right: &Arc<SNode<L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>) -> LNodeTransformResult<K, V, ReduceT>
+pub(crate) unsafe fn transform_with_transmuted_snode_impl<H: Hashword, K: Key, V: Value, L: Key, W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<H, K, V>>, right: &Arc<SNode<H, L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>) -> LNodeTransformResult<H, K, V, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<V, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&L, &W) -> MapTransmuteResult<K, V, ReduceT> + Clone + Send + Sync,
@@ -795,22 +913,22 @@ where
             LNodeNext::S(snode) => snode::transform(snode, left_op).into(),
         };
 
-        transform_with_transformed_result(this, both_op.call_transform_transmute(this.key(), this.value(), right.key(), right.value()), next, reduce_op)
+        transform_with_transformed_result(this, both_op.call_joint(this.key(), this.value(), right.key(), right.value()), next, reduce_op)
     }
     else {
         let next = match &this.next {
-            LNodeNext::L(lnode) => transform_with_transmuted_snode_impl(lnode, right, reduce_op.clone(), both_op, left_op.clone(), right_op),
-            LNodeNext::S(snode) => snode::transform_with_transmuted_snode_impl(snode, right.key(), right.value(), reduce_op.clone(), both_op, left_op.clone(), right_op),
+            LNodeNext::L(lnode) => transform_with_transmuted_snode_impl(lnode, right, reduce_op.clone(), both_op, left_op.fork(), right_op),
+            LNodeNext::S(snode) => snode::transform_with_transmuted_snode_impl(snode, right.key(), right.value(), reduce_op.clone(), both_op, left_op.fork(), right_op),
         };
 
         transform_with_transformed_result(this, left_op.call(this.key(), this.value()), next, reduce_op)
     }
 }
 
-pub(crate) unsafe fn transmute_with_transformed_snode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<K, V>>, right: &Arc<SNode<L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize) -> MNodeTransformResult<H, F, L, W, M, ReduceT>
+pub(crate) unsafe fn transmute_with_transformed_snode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, A: Measure<K, V> + Measure<L, W>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<H, K, V>>, right: &Arc<SNode<H, L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize) -> MNodeTransformResult<H, F, L, W, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<W, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransmuteResult<L, W, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&L, &W) -> MapTransformResult<W, ReduceT> + Clone + Send + Sync,
@@ -821,8 +939,8 @@ where
     M: HasherBv<H, L>,
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
-    let this_hash = M::default().hash(this.key());
-    let right_hash = M::default().hash(right.key());
+    let this_hash = this.hash_value::<M>();
+    let right_hash = right.hash_value::<M>();
 
     if this_hash == right_hash {
         transmute_with_transformed_snode_impl(this, right, reduce_op, both_op, left_op, right_op).into()
@@ -836,28 +954,28 @@ where
 
         match this {
             LNodeTransmuteResult::L(llnode, lr) => match r {
-                SNodeTransformResult::Unchanged(rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(llnode.into(), this_flag, right.clone().into(), right_flag), reduce_op(&lr, &rr)),
-                SNodeTransformResult::S(rsnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(llnode.into(), this_flag, rsnode.into(), right_flag), reduce_op(&lr, &rr)),
-                SNodeTransformResult::Removed(rr) => MNodeTransformResult::L(llnode, reduce_op(&lr, &rr)),
+                SNodeTransformResult::Unchanged(rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(llnode.into(), this_flag, right.clone().into(), right_flag), reduce_op.reduce(lr, rr)),
+                SNodeTransformResult::S(rsnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(llnode.into(), this_flag, rsnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                SNodeTransformResult::Removed(rr) => MNodeTransformResult::L(llnode, reduce_op.reduce(lr, rr)),
             },
             LNodeTransmuteResult::S(lsnode, lr) => match r {
-                SNodeTransformResult::Unchanged(rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, right.clone().into(), right_flag), reduce_op(&lr, &rr)),
-                SNodeTransformResult::S(rsnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, rsnode.into(), right_flag), reduce_op(&lr, &rr)),
-                SNodeTransformResult::Removed(rr) => MNodeTransformResult::S(lsnode, reduce_op(&lr, &rr)),
+                SNodeTransformResult::Unchanged(rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, right.clone().into(), right_flag), reduce_op.reduce(lr, rr)),
+                SNodeTransformResult::S(rsnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, rsnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                SNodeTransformResult::Removed(rr) => MNodeTransformResult::S(lsnode, reduce_op.reduce(lr, rr)),
             },
             LNodeTransmuteResult::Removed(lr) => match r {
-                SNodeTransformResult::Unchanged(rr) => MNodeTransformResult::Unchanged(reduce_op(&lr, &rr)),
-                SNodeTransformResult::S(rsnode, rr) => MNodeTransformResult::S(rsnode, reduce_op(&lr, &rr)),
-                SNodeTransformResult::Removed(rr) => MNodeTransformResult::Removed(reduce_op(&lr, &rr)),
+                SNodeTransformResult::Unchanged(rr) => MNodeTransformResult::Unchanged(reduce_op.reduce(lr, rr)),
+                SNodeTransformResult::S(rsnode, rr) => MNodeTransformResult::S(rsnode, reduce_op.reduce(lr, rr)),
+                SNodeTransformResult::Removed(rr) => MNodeTransformResult::Removed(reduce_op.reduce(lr, rr)),
             },
         }
     }
 }
 
-pub(crate) unsafe fn transmute_with_transformed_snode_impl<K: Key, V: Value, L: Key, W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<K, V>>, right: &Arc<SNode<L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>) -> LNodeTransformResult<L, W, ReduceT>
+pub(crate) unsafe fn transmute_with_transformed_snode_impl<H: Hashword, K: Key, V: Value, L: Key, W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<H, K, V>>, right: &Arc<SNode<H, L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>) -> LNodeTransformResult<H, L, W, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<W, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransmuteResult<L, W, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&L, &W) -> MapTransformResult<W, ReduceT> + Clone + Send + Sync,
@@ -872,22 +990,22 @@ where
             LNodeNext::S(snode) => snode::transmute(snode, left_op).into(),
         };
 
-        transform_with_transmuted_result(right, both_op.call_transmute_transform(this.key(), this.value(), right.key(), right.value()), next, reduce_op)
+        transform_with_transmuted_result(right, both_op.call_joint(this.key(), this.value(), right.key(), right.value()), next, reduce_op)
     }
     else {
         let next = match &this.next {
-            LNodeNext::L(lnode) => transmute_with_transformed_snode_impl(lnode, right, reduce_op.clone(), both_op, left_op.clone(), right_op),
-            LNodeNext::S(snode) => snode::transmute_with_transformed_snode_impl(snode, right, reduce_op.clone(), both_op, left_op.clone(), right_op),
+            LNodeNext::L(lnode) => transmute_with_transformed_snode_impl(lnode, right, reduce_op.clone(), both_op, left_op.fork(), right_op),
+            LNodeNext::S(snode) => snode::transmute_with_transformed_snode_impl(snode, right, reduce_op.clone(), both_op, left_op.fork(), right_op),
         };
 
         transmute_with_transformed_result(right, left_op.call(this.key(), this.value()), next, reduce_op)
     }
 }
 
-pub(crate) unsafe fn transmute_with_transmuted_snode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, S: Key, X: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<K, V>>, right: &Arc<SNode<L, W>>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransmuteResult<H, F, S, X, M, ReduceT>
+pub(crate) unsafe fn transmute_with_transmuted_snode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, S: Key, X: Value, M: HasherBv<H, K>, A: Measure<K, V> + Measure<L, W> + Measure<S, X>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<H, K, V>>, right: &Arc<SNode<H, L, W>>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransmuteResult<H, F, S, X, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT> + Clone,
     RightOp: Fn(&L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
@@ -903,8 +1021,8 @@ where
     M: HasherBv<H, S>,
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
-    let this_hash = M::default().hash(this.key());
-    let right_hash = M::default().hash(right.key());
+    let this_hash = this.hash_value::<M>();
+    let right_hash = right.hash_value::<M>();
 
     if this_hash == right_hash {
         transmute_with_transmuted_snode_impl(this, right, reduce_op, both_op, left_op, right_op).into()
@@ -918,25 +1036,25 @@ where
 
         match this {
             LNodeTransmuteResult::L(llnode, lr) => match right {
-                SNodeTransmuteResult::S(rsnode, rr) => MNodeTransmuteResult::C(cnode::lift_to_cnode_and_insert(llnode.into(), this_flag, rsnode.into(), right_flag), reduce_op(&lr, &rr)),
-                SNodeTransmuteResult::Removed(rr) => MNodeTransmuteResult::L(llnode, reduce_op(&lr, &rr)),
+                SNodeTransmuteResult::S(rsnode, rr) => MNodeTransmuteResult::C(cnode::lift_to_cnode_and_insert(llnode.into(), this_flag, rsnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                SNodeTransmuteResult::Removed(rr) => MNodeTransmuteResult::L(llnode, reduce_op.reduce(lr, rr)),
             },
             LNodeTransmuteResult::S(lsnode, lr) => match right {
-                SNodeTransmuteResult::S(rsnode, rr) => MNodeTransmuteResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, rsnode.into(), right_flag), reduce_op(&lr, &rr)),
-                SNodeTransmuteResult::Removed(rr) => MNodeTransmuteResult::S(lsnode, reduce_op(&lr, &rr)),
+                SNodeTransmuteResult::S(rsnode, rr) => MNodeTransmuteResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, rsnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                SNodeTransmuteResult::Removed(rr) => MNodeTransmuteResult::S(lsnode, reduce_op.reduce(lr, rr)),
             },
             LNodeTransmuteResult::Removed(lr) => match right {
-                SNodeTransmuteResult::S(rsnode, rr) => MNodeTransmuteResult::S(rsnode, reduce_op(&lr, &rr)),
-                SNodeTransmuteResult::Removed(rr) => MNodeTransmuteResult::Removed(reduce_op(&lr, &rr)),
+                SNodeTransmuteResult::S(rsnode, rr) => MNodeTransmuteResult::S(rsnode, reduce_op.reduce(lr, rr)),
+                SNodeTransmuteResult::Removed(rr) => MNodeTransmuteResult::Removed(reduce_op.reduce(lr, rr)),
             },
         }
     }
 }
 
-pub(crate) unsafe fn transmute_with_transmuted_snode_impl<K: Key, V: Value, L: Key, S: Key, W: Value, X: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<K, V>>, right: &Arc<SNode<L, W>>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>) -> LNodeTransmuteResult<S, X, ReduceT>
+pub(crate) unsafe fn transmute_with_transmuted_snode_impl<H: Hashword, K: Key, V: Value, L: Key, S: Key, W: Value, X: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<LNode<H, K, V>>, right: &Arc<SNode<H, L, W>>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>) -> LNodeTransmuteResult<H, S, X, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT> + Clone,
     RightOp: Fn(&L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
@@ -956,269 +1074,269 @@ where
         };
         let this = both_op.call_transmute(this.key(), this.value(), right.key(), right.value());
 
-        transmute_result(this, next, reduce_op)
+        transmute_result(this, next, reduce_op, right.leaf_hash())
     }
     else {
         let next = match &this.next {
-            LNodeNext::L(lnode) => transmute_with_transmuted_snode_impl(lnode, right, reduce_op.clone(), both_op, left_op.clone(), right_op),
-            LNodeNext::S(snode) => snode::transmute_with_transmuted_values(snode.key(), snode.value(), right.key(), right.value(), reduce_op.clone(), both_op, left_op.clone(), right_op),
+            LNodeNext::L(lnode) => transmute_with_transmuted_snode_impl(lnode, right, reduce_op.clone(), both_op, left_op.fork(), right_op),
+            LNodeNext::S(snode) => snode::transmute_with_transmuted_values(snode.key(), snode.value(), right.key(), right.value(), snode.leaf_hash(), reduce_op.clone(), both_op, left_op.fork(), right_op),
         };
         let this = left_op.call(this.key(), this.value());
 
-        transmute_result(this, next, reduce_op)
+        transmute_result(this, next, reduce_op, right.leaf_hash())
     }
 }
 
-pub(super) fn joint_transform_with_joint_transformed_result<K: Key, V: Value, ReduceT, ReduceOp>(this: &Arc<LNode<K, V>>, result: MapJointTransformResult<V, ReduceT>, that: Option<(&K, &V)>, next: LNodeJointTransformResult<K, V, ReduceT>, reduce_op: ReduceOp) -> LNodeJointTransformResult<K, V, ReduceT>
+pub(super) fn joint_transform_with_joint_transformed_result<H: Hashword, K: Key, V: Value, ReduceT, ReduceOp>(this: &Arc<LNode<H, K, V>>, result: MapJointTransformResult<V, ReduceT>, that: Option<(&K, &V)>, next: LNodeJointTransformResult<H, K, V, ReduceT>, reduce_op: ReduceOp) -> LNodeJointTransformResult<H, K, V, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>
 {
     match result {
         MapJointTransformResult::UnchangedLR(lr) => match next {
-            LNodeJointTransformResult::UnchangedLR(rr) => LNodeJointTransformResult::UnchangedLR(reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::UnchangedL(rr) => LNodeJointTransformResult::UnchangedL(reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::UnchangedR(rr) => LNodeJointTransformResult::UnchangedR(reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::L(lnode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::L(lnode)), reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::S(snode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::S(snode)), reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::Removed(rr) => LNodeJointTransformResult::S(SNode::new(this.key.clone(), this.value.clone()), reduce_op(&lr, &rr)),
+            LNodeJointTransformResult::UnchangedLR(rr) => LNodeJointTransformResult::UnchangedLR(reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::UnchangedL(rr) => LNodeJointTransformResult::UnchangedL(reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::UnchangedR(rr) => LNodeJointTransformResult::UnchangedR(reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::L(lnode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::L(lnode)), reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::S(snode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::S(snode)), reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::Removed(rr) => LNodeJointTransformResult::S(SNode::new(this.key.clone(), this.value.clone(), this.leaf_hash()), reduce_op.reduce(lr, rr)),
         },
         MapJointTransformResult::UnchangedL(lr) => match next {
-            LNodeJointTransformResult::UnchangedLR(rr) | LNodeJointTransformResult::UnchangedL(rr) => LNodeJointTransformResult::UnchangedL(reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::UnchangedR(rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::S(SNode::new(that.as_ref().unwrap().0.clone(), that.as_ref().unwrap().1.clone()))), reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::L(lnode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::L(lnode)), reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::S(snode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::S(snode)), reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::Removed(rr) => LNodeJointTransformResult::S(SNode::new(this.key.clone(), this.value.clone()), reduce_op(&lr, &rr)),
+            LNodeJointTransformResult::UnchangedLR(rr) | LNodeJointTransformResult::UnchangedL(rr) => LNodeJointTransformResult::UnchangedL(reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::UnchangedR(rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::S(SNode::new(that.as_ref().unwrap().0.clone(), that.as_ref().unwrap().1.clone(), this.leaf_hash()))), reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::L(lnode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::L(lnode)), reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::S(snode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::S(snode)), reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::Removed(rr) => LNodeJointTransformResult::S(SNode::new(this.key.clone(), this.value.clone(), this.leaf_hash()), reduce_op.reduce(lr, rr)),
         },
         MapJointTransformResult::UnchangedR(lr) => match next {
-            LNodeJointTransformResult::UnchangedLR(rr) | LNodeJointTransformResult::UnchangedR(rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::S(SNode::new(that.as_ref().unwrap().0.clone(), that.as_ref().unwrap().1.clone()))), reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::UnchangedL(rr) => LNodeJointTransformResult::L(LNode::new(that.as_ref().unwrap().0.clone(), that.as_ref().unwrap().1.clone(), this.next.clone()), reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::L(lnode, rr) => LNodeJointTransformResult::L(LNode::new(that.as_ref().unwrap().0.clone(), that.as_ref().unwrap().1.clone(), LNodeNext::L(lnode)), reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::S(snode, rr) => LNodeJointTransformResult::L(LNode::new(that.as_ref().unwrap().0.clone(), that.as_ref().unwrap().1.clone(), LNodeNext::S(snode)), reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::Removed(rr) => LNodeJointTransformResult::UnchangedR(reduce_op(&lr, &rr)),
+            LNodeJointTransformResult::UnchangedLR(rr) | LNodeJointTransformResult::UnchangedR(rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::S(SNode::new(that.as_ref().unwrap().0.clone(), that.as_ref().unwrap().1.clone(), this.leaf_hash()))), reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::UnchangedL(rr) => LNodeJointTransformResult::L(LNode::new(that.as_ref().unwrap().0.clone(), that.as_ref().unwrap().1.clone(), this.next.clone()), reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::L(lnode, rr) => LNodeJointTransformResult::L(LNode::new(that.as_ref().unwrap().0.clone(), that.as_ref().unwrap().1.clone(), LNodeNext::L(lnode)), reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::S(snode, rr) => LNodeJointTransformResult::L(LNode::new(that.as_ref().unwrap().0.clone(), that.as_ref().unwrap().1.clone(), LNodeNext::S(snode)), reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::Removed(rr) => LNodeJointTransformResult::UnchangedR(reduce_op.reduce(lr, rr)),
         },
         MapJointTransformResult::Transformed(lv, lr) => match next {
-            LNodeJointTransformResult::UnchangedLR(rr) | LNodeJointTransformResult::UnchangedL(rr) => LNodeJointTransformResult::L(LNode::new(this.key().clone(), lv, this.next.clone()), reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::UnchangedR(rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::S(SNode::new(that.as_ref().unwrap().0.clone(), that.as_ref().unwrap().1.clone()))), reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::L(lnode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), lv, LNodeNext::L(lnode)), reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::S(snode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), lv, LNodeNext::S(snode)), reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::Removed(rr) => LNodeJointTransformResult::S(SNode::new(this.key.clone(), lv), reduce_op(&lr, &rr)),
+            LNodeJointTransformResult::UnchangedLR(rr) | LNodeJointTransformResult::UnchangedL(rr) => LNodeJointTransformResult::L(LNode::new(this.key().clone(), lv, this.next.clone()), reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::UnchangedR(rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::S(SNode::new(that.as_ref().unwrap().0.clone(), that.as_ref().unwrap().1.clone(), this.leaf_hash()))), reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::L(lnode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), lv, LNodeNext::L(lnode)), reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::S(snode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), lv, LNodeNext::S(snode)), reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::Removed(rr) => LNodeJointTransformResult::S(SNode::new(this.key.clone(), lv, this.leaf_hash()), reduce_op.reduce(lr, rr)),
         },
         MapJointTransformResult::Removed(lr) => match next {
             LNodeJointTransformResult::UnchangedLR(rr) | LNodeJointTransformResult::UnchangedL(rr) => match &this.next {
-                LNodeNext::L(lnode) => LNodeJointTransformResult::L(lnode.clone(), reduce_op(&lr, &rr)),
-                LNodeNext::S(snode) => LNodeJointTransformResult::S(snode.clone(), reduce_op(&lr, &rr)),
+                LNodeNext::L(lnode) => LNodeJointTransformResult::L(lnode.clone(), reduce_op.reduce(lr, rr)),
+                LNodeNext::S(snode) => LNodeJointTransformResult::S(snode.clone(), reduce_op.reduce(lr, rr)),
             },
-            LNodeJointTransformResult::UnchangedR(rr) => LNodeJointTransformResult::S(SNode::new(that.as_ref().unwrap().0.clone(), that.as_ref().unwrap().1.clone()), reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::L(lnode, rr) => LNodeJointTransformResult::L(lnode, reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::S(snode, rr) => LNodeJointTransformResult::S(snode, reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::Removed(rr) => LNodeJointTransformResult::Removed(reduce_op(&lr, &rr)),
+            LNodeJointTransformResult::UnchangedR(rr) => LNodeJointTransformResult::S(SNode::new(that.as_ref().unwrap().0.clone(), that.as_ref().unwrap().1.clone(), this.leaf_hash()), reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::L(lnode, rr) => LNodeJointTransformResult::L(lnode, reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::S(snode, rr) => LNodeJointTransformResult::S(snode, reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::Removed(rr) => LNodeJointTransformResult::Removed(reduce_op.reduce(lr, rr)),
         },
     }
 }
 
-pub(super) fn joint_transform_with_transformed_result<K: Key, V: Value, ReduceT, ReduceOp>(this: &Arc<LNode<K, V>>, result: MapJointTransformResult<V, ReduceT>, that: &Arc<SNode<K, V>>, next: LNodeTransformResult<K, V, ReduceT>, reduce_op: ReduceOp) -> LNodeJointTransformResult<K, V, ReduceT>
+pub(super) fn joint_transform_with_transformed_result<H: Hashword, K: Key, V: Value, ReduceT, ReduceOp>(this: &Arc<LNode<H, K, V>>, result: MapJointTransformResult<V, ReduceT>, that: &Arc<SNode<H, K, V>>, next: LNodeTransformResult<H, K, V, ReduceT>, reduce_op: ReduceOp) -> LNodeJointTransformResult<H, K, V, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>
 {
     match result {
         MapJointTransformResult::UnchangedLR(lr) | MapJointTransformResult::UnchangedL(lr) => match next {
-            LNodeTransformResult::Unchanged(rr) => LNodeJointTransformResult::UnchangedL(reduce_op(&lr, &rr)),
-            LNodeTransformResult::L(lnode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::L(lnode)), reduce_op(&lr, &rr)),
-            LNodeTransformResult::S(snode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::S(snode)), reduce_op(&lr, &rr)),
-            LNodeTransformResult::Removed(rr) => LNodeJointTransformResult::S(SNode::new(this.key.clone(), this.value.clone()), reduce_op(&lr, &rr)),
+            LNodeTransformResult::Unchanged(rr) => LNodeJointTransformResult::UnchangedL(reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::L(lnode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::L(lnode)), reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::S(snode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::S(snode)), reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::Removed(rr) => LNodeJointTransformResult::S(SNode::new(this.key.clone(), this.value.clone(), this.leaf_hash()), reduce_op.reduce(lr, rr)),
         },
         MapJointTransformResult::UnchangedR(lr) => match next {
-            LNodeTransformResult::Unchanged(rr) => LNodeJointTransformResult::L(LNode::new(that.key().clone(), that.value().clone(), this.next.clone()), reduce_op(&lr, &rr)),
-            LNodeTransformResult::L(lnode, rr) => LNodeJointTransformResult::L(LNode::new(that.key().clone(), that.value().clone(), LNodeNext::L(lnode)), reduce_op(&lr, &rr)),
-            LNodeTransformResult::S(snode, rr) => LNodeJointTransformResult::L(LNode::new(that.key().clone(), that.value().clone(), LNodeNext::S(snode)), reduce_op(&lr, &rr)),
-            LNodeTransformResult::Removed(rr) => LNodeJointTransformResult::UnchangedR(reduce_op(&lr, &rr)),
+            LNodeTransformResult::Unchanged(rr) => LNodeJointTransformResult::L(LNode::new(that.key().clone(), that.value().clone(), this.next.clone()), reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::L(lnode, rr) => LNodeJointTransformResult::L(LNode::new(that.key().clone(), that.value().clone(), LNodeNext::L(lnode)), reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::S(snode, rr) => LNodeJointTransformResult::L(LNode::new(that.key().clone(), that.value().clone(), LNodeNext::S(snode)), reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::Removed(rr) => LNodeJointTransformResult::UnchangedR(reduce_op.reduce(lr, rr)),
         },
         MapJointTransformResult::Transformed(lv, lr) => match next {
-            LNodeTransformResult::Unchanged(rr) => LNodeJointTransformResult::L(LNode::new(this.key().clone(), lv, this.next.clone()), reduce_op(&lr, &rr)),
-            LNodeTransformResult::L(lnode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), lv, LNodeNext::L(lnode)), reduce_op(&lr, &rr)),
-            LNodeTransformResult::S(snode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), lv, LNodeNext::S(snode)), reduce_op(&lr, &rr)),
-            LNodeTransformResult::Removed(rr) => LNodeJointTransformResult::S(SNode::new(this.key.clone(), lv), reduce_op(&lr, &rr)),
+            LNodeTransformResult::Unchanged(rr) => LNodeJointTransformResult::L(LNode::new(this.key().clone(), lv, this.next.clone()), reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::L(lnode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), lv, LNodeNext::L(lnode)), reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::S(snode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), lv, LNodeNext::S(snode)), reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::Removed(rr) => LNodeJointTransformResult::S(SNode::new(this.key.clone(), lv, this.leaf_hash()), reduce_op.reduce(lr, rr)),
         },
         MapJointTransformResult::Removed(lr) => match next {
             LNodeTransformResult::Unchanged(rr) => match &this.next {
-                LNodeNext::L(lnode) => LNodeJointTransformResult::L(lnode.clone(), reduce_op(&lr, &rr)),
-                LNodeNext::S(snode) => LNodeJointTransformResult::S(snode.clone(), reduce_op(&lr, &rr)),
+                LNodeNext::L(lnode) => LNodeJointTransformResult::L(lnode.clone(), reduce_op.reduce(lr, rr)),
+                LNodeNext::S(snode) => LNodeJointTransformResult::S(snode.clone(), reduce_op.reduce(lr, rr)),
             },
-            LNodeTransformResult::L(lnode, rr) => LNodeJointTransformResult::L(lnode, reduce_op(&lr, &rr)),
-            LNodeTransformResult::S(snode, rr) => LNodeJointTransformResult::S(snode, reduce_op(&lr, &rr)),
-            LNodeTransformResult::Removed(rr) => LNodeJointTransformResult::Removed(reduce_op(&lr, &rr)),
+            LNodeTransformResult::L(lnode, rr) => LNodeJointTransformResult::L(lnode, reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::S(snode, rr) => LNodeJointTransformResult::S(snode, reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::Removed(rr) => LNodeJointTransformResult::Removed(reduce_op.reduce(lr, rr)),
         },
     }
 }
 
-pub(super) fn transform_with_transformed_result<K: Key, V: Value, ReduceT, ReduceOp>(this: &Arc<LNode<K, V>>, result: MapTransformResult<V, ReduceT>, next: LNodeTransformResult<K, V, ReduceT>, reduce_op: ReduceOp) -> LNodeTransformResult<K, V, ReduceT>
+pub(super) fn transform_with_transformed_result<H: Hashword, K: Key, V: Value, ReduceT, ReduceOp>(this: &Arc<LNode<H, K, V>>, result: MapTransformResult<V, ReduceT>, next: LNodeTransformResult<H, K, V, ReduceT>, reduce_op: ReduceOp) -> LNodeTransformResult<H, K, V, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>
 {
     match result {
         MapTransformResult::Unchanged(lr) => match next {
-            LNodeTransformResult::Unchanged(rr) => LNodeTransformResult::Unchanged(reduce_op(&lr, &rr)),
-            LNodeTransformResult::L(lnode, rr) => LNodeTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::L(lnode)), reduce_op(&lr, &rr)),
-            LNodeTransformResult::S(snode, rr) => LNodeTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::S(snode)), reduce_op(&lr, &rr)),
-            LNodeTransformResult::Removed(rr) => LNodeTransformResult::S(SNode::new(this.key.clone(), this.value.clone()), reduce_op(&lr, &rr)),
+            LNodeTransformResult::Unchanged(rr) => LNodeTransformResult::Unchanged(reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::L(lnode, rr) => LNodeTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::L(lnode)), reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::S(snode, rr) => LNodeTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::S(snode)), reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::Removed(rr) => LNodeTransformResult::S(SNode::new(this.key.clone(), this.value.clone(), this.leaf_hash()), reduce_op.reduce(lr, rr)),
         },
         MapTransformResult::Transformed(lv, lr) => match next {
-            LNodeTransformResult::Unchanged(rr) => LNodeTransformResult::L(LNode::new(this.key().clone(), lv, this.next.clone()), reduce_op(&lr, &rr)),
-            LNodeTransformResult::L(lnode, rr) => LNodeTransformResult::L(LNode::new(this.key.clone(), lv, LNodeNext::L(lnode)), reduce_op(&lr, &rr)),
-            LNodeTransformResult::S(snode, rr) => LNodeTransformResult::L(LNode::new(this.key.clone(), lv, LNodeNext::S(snode)), reduce_op(&lr, &rr)),
-            LNodeTransformResult::Removed(rr) => LNodeTransformResult::S(SNode::new(this.key.clone(), lv), reduce_op(&lr, &rr)),
+            LNodeTransformResult::Unchanged(rr) => LNodeTransformResult::L(LNode::new(this.key().clone(), lv, this.next.clone()), reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::L(lnode, rr) => LNodeTransformResult::L(LNode::new(this.key.clone(), lv, LNodeNext::L(lnode)), reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::S(snode, rr) => LNodeTransformResult::L(LNode::new(this.key.clone(), lv, LNodeNext::S(snode)), reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::Removed(rr) => LNodeTransformResult::S(SNode::new(this.key.clone(), lv, this.leaf_hash()), reduce_op.reduce(lr, rr)),
         },
         MapTransformResult::Removed(lr) => match next {
             LNodeTransformResult::Unchanged(rr) => match &this.next {
-                LNodeNext::L(lnode) => LNodeTransformResult::L(lnode.clone(), reduce_op(&lr, &rr)),
-                LNodeNext::S(snode) => LNodeTransformResult::S(snode.clone(), reduce_op(&lr, &rr)),
+                LNodeNext::L(lnode) => LNodeTransformResult::L(lnode.clone(), reduce_op.reduce(lr, rr)),
+                LNodeNext::S(snode) => LNodeTransformResult::S(snode.clone(), reduce_op.reduce(lr, rr)),
             },
-            LNodeTransformResult::L(lnode, rr) => LNodeTransformResult::L(lnode, reduce_op(&lr, &rr)),
-            LNodeTransformResult::S(snode, rr) => LNodeTransformResult::S(snode, reduce_op(&lr, &rr)),
-            LNodeTransformResult::Removed(rr) => LNodeTransformResult::Removed(reduce_op(&lr, &rr)),
+            LNodeTransformResult::L(lnode, rr) => LNodeTransformResult::L(lnode, reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::S(snode, rr) => LNodeTransformResult::S(snode, reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::Removed(rr) => LNodeTransformResult::Removed(reduce_op.reduce(lr, rr)),
         },
     }
 }
 
-pub(super) fn transform_with_joint_transformed_result<K: Key, V: Value, ReduceT, ReduceOp>(this: &Arc<LNode<K, V>>, result: MapTransformResult<V, ReduceT>, that: &Arc<SNode<K, V>>, next: LNodeJointTransformResult<K, V, ReduceT>, reduce_op: ReduceOp) -> LNodeJointTransformResult<K, V, ReduceT>
+pub(super) fn transform_with_joint_transformed_result<H: Hashword, K: Key, V: Value, ReduceT, ReduceOp>(this: &Arc<LNode<H, K, V>>, result: MapTransformResult<V, ReduceT>, that: &Arc<SNode<H, K, V>>, next: LNodeJointTransformResult<H, K, V, ReduceT>, reduce_op: ReduceOp) -> LNodeJointTransformResult<H, K, V, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>
 {
     match result {
         MapTransformResult::Unchanged(lr) => match next {
-            LNodeJointTransformResult::UnchangedLR(rr) | LNodeJointTransformResult::UnchangedL(rr) => LNodeJointTransformResult::UnchangedL(reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::UnchangedR(rr) => LNodeJointTransformResult::L(LNode::new(that.key().clone(), that.value().clone(), LNodeNext::L(this.clone())), reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::L(lnode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::L(lnode)), reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::S(snode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::S(snode)), reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::Removed(rr) => LNodeJointTransformResult::S(SNode::new(this.key.clone(), this.value.clone()), reduce_op(&lr, &rr)),
+            LNodeJointTransformResult::UnchangedLR(rr) | LNodeJointTransformResult::UnchangedL(rr) => LNodeJointTransformResult::UnchangedL(reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::UnchangedR(rr) => LNodeJointTransformResult::L(LNode::new(that.key().clone(), that.value().clone(), LNodeNext::L(this.clone())), reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::L(lnode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::L(lnode)), reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::S(snode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::S(snode)), reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::Removed(rr) => LNodeJointTransformResult::S(SNode::new(this.key.clone(), this.value.clone(), this.leaf_hash()), reduce_op.reduce(lr, rr)),
         },
         MapTransformResult::Transformed(lv, lr) => match next {
-            LNodeJointTransformResult::UnchangedLR(rr) | LNodeJointTransformResult::UnchangedL(rr) => LNodeJointTransformResult::L(LNode::new(this.key().clone(), lv, this.next.clone()), reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::UnchangedR(rr) => LNodeJointTransformResult::L(LNode::new(this.key().clone(), lv, LNodeNext::S(that.clone())), reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::L(lnode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), lv, LNodeNext::L(lnode)), reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::S(snode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), lv, LNodeNext::S(snode)), reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::Removed(rr) => LNodeJointTransformResult::S(SNode::new(this.key.clone(), lv), reduce_op(&lr, &rr)),
+            LNodeJointTransformResult::UnchangedLR(rr) | LNodeJointTransformResult::UnchangedL(rr) => LNodeJointTransformResult::L(LNode::new(this.key().clone(), lv, this.next.clone()), reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::UnchangedR(rr) => LNodeJointTransformResult::L(LNode::new(this.key().clone(), lv, LNodeNext::S(that.clone())), reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::L(lnode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), lv, LNodeNext::L(lnode)), reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::S(snode, rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), lv, LNodeNext::S(snode)), reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::Removed(rr) => LNodeJointTransformResult::S(SNode::new(this.key.clone(), lv, this.leaf_hash()), reduce_op.reduce(lr, rr)),
         },
         MapTransformResult::Removed(lr) => match next {
             LNodeJointTransformResult::UnchangedLR(rr) | LNodeJointTransformResult::UnchangedL(rr) => match &this.next {
-                LNodeNext::L(lnode) => LNodeJointTransformResult::L(lnode.clone(), reduce_op(&lr, &rr)),
-                LNodeNext::S(snode) => LNodeJointTransformResult::S(snode.clone(), reduce_op(&lr, &rr)),
+                LNodeNext::L(lnode) => LNodeJointTransformResult::L(lnode.clone(), reduce_op.reduce(lr, rr)),
+                LNodeNext::S(snode) => LNodeJointTransformResult::S(snode.clone(), reduce_op.reduce(lr, rr)),
             },
-            LNodeJointTransformResult::UnchangedR(rr) => LNodeJointTransformResult::UnchangedR(reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::L(lnode, rr) => LNodeJointTransformResult::L(lnode, reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::S(snode, rr) => LNodeJointTransformResult::S(snode, reduce_op(&lr, &rr)),
-            LNodeJointTransformResult::Removed(rr) => LNodeJointTransformResult::Removed(reduce_op(&lr, &rr)),
+            LNodeJointTransformResult::UnchangedR(rr) => LNodeJointTransformResult::UnchangedR(reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::L(lnode, rr) => LNodeJointTransformResult::L(lnode, reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::S(snode, rr) => LNodeJointTransformResult::S(snode, reduce_op.reduce(lr, rr)),
+            LNodeJointTransformResult::Removed(rr) => LNodeJointTransformResult::Removed(reduce_op.reduce(lr, rr)),
         },
     }
 }
 
-pub(super) fn transform_with_transmuted_result<K: Key, V: Value, ReduceT, ReduceOp>(this: &Arc<SNode<K, V>>, result: MapTransformResult<V, ReduceT>, next: LNodeTransmuteResult<K, V, ReduceT>, reduce_op: ReduceOp) -> LNodeTransformResult<K, V, ReduceT>
+pub(super) fn transform_with_transmuted_result<H: Hashword, K: Key, V: Value, ReduceT, ReduceOp>(this: &Arc<SNode<H, K, V>>, result: MapTransformResult<V, ReduceT>, next: LNodeTransmuteResult<H, K, V, ReduceT>, reduce_op: ReduceOp) -> LNodeTransformResult<H, K, V, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>
 {
     match result {
         MapTransformResult::Unchanged(lr) => match next {
-            LNodeTransmuteResult::L(lnode, rr) => LNodeTransformResult::L(LNode::new(this.key().clone(), this.value().clone(), LNodeNext::L(lnode)), reduce_op(&lr, &rr)),
-            LNodeTransmuteResult::S(snode, rr) => LNodeTransformResult::L(LNode::new(this.key().clone(), this.value().clone(), LNodeNext::S(snode)), reduce_op(&lr, &rr)),
-            LNodeTransmuteResult::Removed(rr) => LNodeTransformResult::S(SNode::new(this.key().clone(), this.value().clone()), reduce_op(&lr, &rr)),
+            LNodeTransmuteResult::L(lnode, rr) => LNodeTransformResult::L(LNode::new(this.key().clone(), this.value().clone(), LNodeNext::L(lnode)), reduce_op.reduce(lr, rr)),
+            LNodeTransmuteResult::S(snode, rr) => LNodeTransformResult::L(LNode::new(this.key().clone(), this.value().clone(), LNodeNext::S(snode)), reduce_op.reduce(lr, rr)),
+            LNodeTransmuteResult::Removed(rr) => LNodeTransformResult::S(SNode::new(this.key().clone(), this.value().clone(), this.leaf_hash()), reduce_op.reduce(lr, rr)),
         },
         MapTransformResult::Transformed(lv, lr) => match next {
-            LNodeTransmuteResult::L(lnode, rr) => LNodeTransformResult::L(LNode::new(this.key().clone(), lv, LNodeNext::L(lnode)), reduce_op(&lr, &rr)),
-            LNodeTransmuteResult::S(snode, rr) => LNodeTransformResult::L(LNode::new(this.key().clone(), lv, LNodeNext::S(snode)), reduce_op(&lr, &rr)),
-            LNodeTransmuteResult::Removed(rr) => LNodeTransformResult::S(SNode::new(this.key().clone(), lv), reduce_op(&lr, &rr)),
+            LNodeTransmuteResult::L(lnode, rr) => LNodeTransformResult::L(LNode::new(this.key().clone(), lv, LNodeNext::L(lnode)), reduce_op.reduce(lr, rr)),
+            LNodeTransmuteResult::S(snode, rr) => LNodeTransformResult::L(LNode::new(this.key().clone(), lv, LNodeNext::S(snode)), reduce_op.reduce(lr, rr)),
+            LNodeTransmuteResult::Removed(rr) => LNodeTransformResult::S(SNode::new(this.key().clone(), lv, this.leaf_hash()), reduce_op.reduce(lr, rr)),
         },
         MapTransformResult::Removed(lr) => match next {
-            LNodeTransmuteResult::L(lnode, rr) => LNodeTransformResult::L(lnode, reduce_op(&lr, &rr)),
-            LNodeTransmuteResult::S(snode, rr) => LNodeTransformResult::S(snode, reduce_op(&lr, &rr)),
-            LNodeTransmuteResult::Removed(rr) => LNodeTransformResult::Removed(reduce_op(&lr, &rr)),
+            LNodeTransmuteResult::L(lnode, rr) => LNodeTransformResult::L(lnode, reduce_op.reduce(lr, rr)),
+            LNodeTransmuteResult::S(snode, rr) => LNodeTransformResult::S(snode, reduce_op.reduce(lr, rr)),
+            LNodeTransmuteResult::Removed(rr) => LNodeTransformResult::Removed(reduce_op.reduce(lr, rr)),
         },
     }
 }
 
-pub(super) fn transmute_with_transformed_result<K: Key, V: Value, ReduceT, ReduceOp>(this: &Arc<SNode<K, V>>, result: MapTransmuteResult<K, V, ReduceT>, next: LNodeTransformResult<K, V, ReduceT>, reduce_op: ReduceOp) -> LNodeTransformResult<K, V, ReduceT>
+pub(super) fn transmute_with_transformed_result<H: Hashword, K: Key, V: Value, ReduceT, ReduceOp>(this: &Arc<SNode<H, K, V>>, result: MapTransmuteResult<K, V, ReduceT>, next: LNodeTransformResult<H, K, V, ReduceT>, reduce_op: ReduceOp) -> LNodeTransformResult<H, K, V, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>
 {
     match result {
         MapTransmuteResult::Transmuted(lk, lv, lr) => match next {
-            LNodeTransformResult::Unchanged(rr) => LNodeTransformResult::L(LNode::new(lk, lv, LNodeNext::S(this.clone())), reduce_op(&lr, &rr)),
-            LNodeTransformResult::L(lnode, rr) => LNodeTransformResult::L(LNode::new(lk, lv, LNodeNext::L(lnode)), reduce_op(&lr, &rr)),
-            LNodeTransformResult::S(snode, rr) => LNodeTransformResult::L(LNode::new(lk, lv, LNodeNext::S(snode)), reduce_op(&lr, &rr)),
-            LNodeTransformResult::Removed(rr) => LNodeTransformResult::S(SNode::new(lk, lv), reduce_op(&lr, &rr)),
+            LNodeTransformResult::Unchanged(rr) => LNodeTransformResult::L(LNode::new(lk, lv, LNodeNext::S(this.clone())), reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::L(lnode, rr) => LNodeTransformResult::L(LNode::new(lk, lv, LNodeNext::L(lnode)), reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::S(snode, rr) => LNodeTransformResult::L(LNode::new(lk, lv, LNodeNext::S(snode)), reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::Removed(rr) => LNodeTransformResult::S(SNode::new(lk, lv, this.leaf_hash()), reduce_op.reduce(lr, rr)),
         },
         MapTransmuteResult::Removed(lr) => match next {
-            LNodeTransformResult::Unchanged(rr) => LNodeTransformResult::S(SNode::new(this.key().clone(), this.value().clone()), reduce_op(&lr, &rr)),
-            LNodeTransformResult::L(lnode, rr) => LNodeTransformResult::L(lnode, reduce_op(&lr, &rr)),
-            LNodeTransformResult::S(snode, rr) => LNodeTransformResult::S(snode, reduce_op(&lr, &rr)),
-            LNodeTransformResult::Removed(rr) => LNodeTransformResult::Removed(reduce_op(&lr, &rr)),
+            LNodeTransformResult::Unchanged(rr) => LNodeTransformResult::S(SNode::new(this.key().clone(), this.value().clone(), this.leaf_hash()), reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::L(lnode, rr) => LNodeTransformResult::L(lnode, reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::S(snode, rr) => LNodeTransformResult::S(snode, reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::Removed(rr) => LNodeTransformResult::Removed(reduce_op.reduce(lr, rr)),
         },
     }
 }
 
-pub(super) fn transform_result<K: Key, V: Value, ReduceT, ReduceOp>(this: &Arc<LNode<K, V>>, result: MapTransformResult<V, ReduceT>, next: LNodeTransformResult<K, V, ReduceT>, reduce_op: ReduceOp) -> LNodeTransformResult<K, V, ReduceT>
+pub(super) fn transform_result<H: Hashword, K: Key, V: Value, ReduceT, ReduceOp>(this: &Arc<LNode<H, K, V>>, result: MapTransformResult<V, ReduceT>, next: LNodeTransformResult<H, K, V, ReduceT>, reduce_op: ReduceOp) -> LNodeTransformResult<H, K, V, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>
 {
     match result {
         MapTransformResult::Unchanged(lr) => match next {
-            LNodeTransformResult::Unchanged(rr) => LNodeTransformResult::Unchanged(reduce_op(&lr, &rr)),
-            LNodeTransformResult::L(lnode, rr) => LNodeTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::L(lnode)), reduce_op(&lr, &rr)),
-            LNodeTransformResult::S(snode, rr) => LNodeTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::S(snode)), reduce_op(&lr, &rr)),
-            LNodeTransformResult::Removed(rr) => LNodeTransformResult::S(SNode::new(this.key.clone(), this.value.clone()), reduce_op(&lr, &rr)),
+            LNodeTransformResult::Unchanged(rr) => LNodeTransformResult::Unchanged(reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::L(lnode, rr) => LNodeTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::L(lnode)), reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::S(snode, rr) => LNodeTransformResult::L(LNode::new(this.key.clone(), this.value.clone(), LNodeNext::S(snode)), reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::Removed(rr) => LNodeTransformResult::S(SNode::new(this.key.clone(), this.value.clone(), this.leaf_hash()), reduce_op.reduce(lr, rr)),
         },
         MapTransformResult::Transformed(lv, lr) => match next {
-            LNodeTransformResult::Unchanged(rr) => LNodeTransformResult::L(LNode::new(this.key.clone(), lv, this.next.clone()), reduce_op(&lr, &rr)),
-            LNodeTransformResult::L(lnode, rr) => LNodeTransformResult::L(LNode::new(this.key.clone(), lv, LNodeNext::L(lnode)), reduce_op(&lr, &rr)),
-            LNodeTransformResult::S(snode, rr) => LNodeTransformResult::L(LNode::new(this.key.clone(), lv, LNodeNext::S(snode)), reduce_op(&lr, &rr)),
-            LNodeTransformResult::Removed(rr) => LNodeTransformResult::S(SNode::new(this.key.clone(), lv), reduce_op(&lr, &rr)),
+            LNodeTransformResult::Unchanged(rr) => LNodeTransformResult::L(LNode::new(this.key.clone(), lv, this.next.clone()), reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::L(lnode, rr) => LNodeTransformResult::L(LNode::new(this.key.clone(), lv, LNodeNext::L(lnode)), reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::S(snode, rr) => LNodeTransformResult::L(LNode::new(this.key.clone(), lv, LNodeNext::S(snode)), reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::Removed(rr) => LNodeTransformResult::S(SNode::new(this.key.clone(), lv, this.leaf_hash()), reduce_op.reduce(lr, rr)),
         },
         MapTransformResult::Removed(lr) => match next {
             LNodeTransformResult::Unchanged(rr) => match &this.next {
-                LNodeNext::L(lnode) => LNodeTransformResult::L(lnode.clone(), reduce_op(&lr, &rr)),
-                LNodeNext::S(snode) => LNodeTransformResult::S(snode.clone(), reduce_op(&lr, &rr)),
+                LNodeNext::L(lnode) => LNodeTransformResult::L(lnode.clone(), reduce_op.reduce(lr, rr)),
+                LNodeNext::S(snode) => LNodeTransformResult::S(snode.clone(), reduce_op.reduce(lr, rr)),
             },
-            LNodeTransformResult::L(lnode, rr) => LNodeTransformResult::L(lnode, reduce_op(&lr, &rr)),
-            LNodeTransformResult::S(snode, rr) => LNodeTransformResult::S(snode, reduce_op(&lr, &rr)),
-            LNodeTransformResult::Removed(rr) => LNodeTransformResult::Removed(reduce_op(&lr, &rr)),
+            LNodeTransformResult::L(lnode, rr) => LNodeTransformResult::L(lnode, reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::S(snode, rr) => LNodeTransformResult::S(snode, reduce_op.reduce(lr, rr)),
+            LNodeTransformResult::Removed(rr) => LNodeTransformResult::Removed(reduce_op.reduce(lr, rr)),
         },
     }
 }
 
-pub(super) unsafe fn transmute_result<S: Key, X: Value, ReduceT, ReduceOp>(result: MapTransmuteResult<S, X, ReduceT>, next: LNodeTransmuteResult<S, X, ReduceT>, reduce_op: ReduceOp) -> LNodeTransmuteResult<S, X, ReduceT>
+pub(super) unsafe fn transmute_result<H: Hashword, S: Key, X: Value, ReduceT, ReduceOp>(result: MapTransmuteResult<S, X, ReduceT>, next: LNodeTransmuteResult<H, S, X, ReduceT>, reduce_op: ReduceOp, hash: LeafHash<H>) -> LNodeTransmuteResult<H, S, X, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>
 {
     match result {
         MapTransmuteResult::Transmuted(lk, lv, lr) => match next {
-            LNodeTransmuteResult::L(lnode, rr) => LNodeTransmuteResult::L(LNode::new(lk, lv, LNodeNext::L(lnode)), reduce_op(&lr, &rr)),
-            LNodeTransmuteResult::S(snode, rr) => LNodeTransmuteResult::L(LNode::new(lk, lv, LNodeNext::S(snode)), reduce_op(&lr, &rr)),
-            LNodeTransmuteResult::Removed(rr) => LNodeTransmuteResult::S(SNode::new(lk, lv), reduce_op(&lr, &rr)),
+            LNodeTransmuteResult::L(lnode, rr) => LNodeTransmuteResult::L(LNode::new(lk, lv, LNodeNext::L(lnode)), reduce_op.reduce(lr, rr)),
+            LNodeTransmuteResult::S(snode, rr) => LNodeTransmuteResult::L(LNode::new(lk, lv, LNodeNext::S(snode)), reduce_op.reduce(lr, rr)),
+            LNodeTransmuteResult::Removed(rr) => LNodeTransmuteResult::S(SNode::new(lk, lv, hash), reduce_op.reduce(lr, rr)),
         },
         MapTransmuteResult::Removed(lr) => match next {
-            LNodeTransmuteResult::L(lnode, rr) => LNodeTransmuteResult::L(lnode, reduce_op(&lr, &rr)),
-            LNodeTransmuteResult::S(snode, rr) => LNodeTransmuteResult::S(snode, reduce_op(&lr, &rr)),
-            LNodeTransmuteResult::Removed(rr) => LNodeTransmuteResult::Removed(reduce_op(&lr, &rr)),
+            LNodeTransmuteResult::L(lnode, rr) => LNodeTransmuteResult::L(lnode, reduce_op.reduce(lr, rr)),
+            LNodeTransmuteResult::S(snode, rr) => LNodeTransmuteResult::S(snode, reduce_op.reduce(lr, rr)),
+            LNodeTransmuteResult::Removed(rr) => LNodeTransmuteResult::Removed(reduce_op.reduce(lr, rr)),
         },
     }
 }
 
 #[must_use]
-pub(super) fn lift_to_cnode_and_insert<'a, H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key + Into<K>, W: Into<V>, M: HasherBv<H, K>>(this: LNodeNext<K, V>, key: L, value: W, key_flag: Flag<H, F>) -> LNodeInsertResult<'a, H, F, K, V, M>
+pub(super) fn lift_to_cnode_and_insert<'a, H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key + Into<K>, W: Into<V>, M: HasherBv<H, K>, A: Measure<K, V>>(this: LNodeNext<H, K, V>, key: L, value: W, key_flag: Flag<H, F>) -> LNodeInsertResult<'a, H, F, K, V, M, A>
 where
     K: HashLike<L>,
     K: PartialEq<L>,
     M: HasherBv<H, L>,
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
-    let this_hash = M::default().hash(this.key());
+    let this_hash = this.hash_value::<M>();
     if this_hash == key_flag.hash_value() {
         let lnode = LNode::new(key.into(), value.into(), this);
         let key: *const K = lnode.key();
@@ -1228,15 +1346,21 @@ where
     else {
         let this_flag = Flag::new_at_depth(this_hash, key_flag.depth()).unwrap();
 
-        let snode = SNode::new(key.into(), value.into());
+        let snode = SNode::new(key.into(), value.into(), LeafHash::new(key_flag.hash_value()));
         let key: *const K = snode.key();
         let value: *const V = snode.value();
         LNodeInsertResult::InsertedC(cnode::lift_to_cnode_and_insert(this.into(), this_flag, snode.into(), key_flag), key, value, None)
     }
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> From<Arc<LNode<K, V>>> for MNode<H, F, K, V, M> {
-    fn from(other: Arc<LNode<K, V>>) -> Self {
+impl <H: Hashword, K: Key, V: Value> Debug for LNode<H, K, V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("LNode").field("key", &self.key).field("value", &self.value).field("next", &self.next).field("size", &self.size).finish()
+    }
+}
+
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> From<Arc<LNode<H, K, V>>> for MNode<H, F, K, V, M, A> {
+    fn from(other: Arc<LNode<H, K, V>>) -> Self {
         MNode::L(other)
     }
 }
@@ -1248,7 +1372,7 @@ mod tests {
     macro_rules! lnode {
         ( $keyvalue1:expr, $keyvalue2:expr ) => {
             {
-                LNode::new($keyvalue1.0, $keyvalue1.1, LNodeNext::S(SNode::new($keyvalue2.0, $keyvalue2.1)))
+                LNode::new($keyvalue1.0, $keyvalue1.1, LNodeNext::S(SNode::new($keyvalue2.0, $keyvalue2.1, LeafHash::new(0_u64))))
             }
         };
         ( $keyvalue:expr, $($rest:expr),+ ) => {
@@ -1262,10 +1386,12 @@ mod tests {
     fn lnode_insert_3() {
         let node = lnode!((3, ()), (2, ()), (1, ()));
         assert_eq!(node.size, 3);
-        assert_found_eq!(node.find(&1), (1, ()));
-        assert_found_eq!(node.find(&2), (2, ()));
-        assert_found_eq!(node.find(&3), (3, ()));
-        assert_found_none!(node.find(&4));
+        assert_found_eq!(node.find(&1, &0), (1, ()));
+        assert_found_eq!(node.find(&2, &0), (2, ()));
+        assert_found_eq!(node.find(&3, &0), (3, ()));
+        assert_found_none!(node.find(&4, &0));
+        #[cfg(feature = "cache_hash")]
+        assert_found_none!(node.find(&1, &1));
     }
 
 }
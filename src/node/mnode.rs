@@ -1,15 +1,18 @@
-use crate::{flag::*, transformations::*, results::*, traits::*, ParallelismStrategy};
-use super::{cnode::{self, *}, lnode::{self, LNode}, snode::{self, SNode}};
-use alloc::{fmt::Debug, sync::Arc};
-
-#[derive(Debug)]
-pub(crate) enum MNode <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static> {
-    C(CNode<H, F, K, V, M>),
-    L(Arc<LNode<K, V>>),
-    S(Arc<SNode<K, V>>),
+use crate::{bit_indexed_array::*, flag::*, transformations::*, results::*, traits::*, ParallelismStrategy};
+use super::{cnode::{self, *}, leaf_hash::LeafHash, lnode::{self, LNode, LNodeNext}, snode::{self, SNode}};
+use alloc::{collections::BTreeSet, fmt::Debug, sync::Arc, vec::Vec};
+use core::{future::Future, hash::Hasher};
+
+pub(crate) enum MNode <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>> {
+    C(CNode<H, F, K, V, M, A>),
+    L(Arc<LNode<H, K, V>>),
+    S(Arc<SNode<H, K, V>>),
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> MNode<H, F, K, V, M> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+// Seeds the second hash `build_leaf` uses to group keys whose full hash values collide.
+const REHASH_KEY: u64 = 0x9e3779b97f4a7c15;
+
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> MNode<H, F, K, V, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
     #[must_use]
     pub(crate) fn size(&self) -> usize {
         match self {
@@ -25,12 +28,12 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> MNode<H,
     {
         match self {
             Self::C(cnode) => cnode.find(key, flag),
-            Self::L(lnode) => lnode.find(key),
-            Self::S(snode) => snode.find(key),
+            Self::L(lnode) => lnode.find(key, &flag.unwrap().hash_value()),
+            Self::S(snode) => snode.find(key, &flag.unwrap().hash_value()),
         }
     }
 
-    pub(crate) fn insert<L: Key + Into<K>, W: Into<V>>(&self, key: L, value: W, flag: Option<Flag<H, F>>, replace: bool) -> InsertResult<H, F, K, V, M>
+    pub(crate) fn insert<L: Key + Into<K>, W: Into<V>>(&self, key: L, value: W, flag: Option<Flag<H, F>>, replace: bool) -> InsertResult<H, F, K, V, M, A>
     where
         K: HashLike<L>,
         K: PartialEq<L>,
@@ -43,11 +46,11 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> MNode<H,
         }
     }
 
-    pub(crate) fn remove<L: Key>(&self, key: &L, flag: Option<Flag<H, F>>) -> RemoveResult<H, F, K, V, M> where K: PartialEq<L>, M: HasherBv<H, L> {
+    pub(crate) fn remove<L: Key>(&self, key: &L, flag: Option<Flag<H, F>>) -> RemoveResult<H, F, K, V, M, A> where K: PartialEq<L>, M: HasherBv<H, L> {
         match self {
             Self::C(cnode) => cnode.remove(key, flag),
-            Self::L(lnode) => lnode::remove(lnode, key),
-            Self::S(snode) => snode::remove(snode, key).into(),
+            Self::L(lnode) => lnode::remove(lnode, key, &flag.unwrap().hash_value()),
+            Self::S(snode) => snode::remove(snode, key, &flag.unwrap().hash_value()).into(),
         }
     }
     
@@ -59,10 +62,494 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> MNode<H,
         }
     }
 
-    pub(crate) async fn transform<ReduceT, ReduceOp, Op>(&self, reduce_op: ReduceOp, op: MapTransform<ReduceT, Op>, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, K, V, M, ReduceT>
+    pub(crate) fn fold<T, Op>(&self, init: T, op: &mut Op) -> T where Op: FnMut(T, &K, &V) -> T {
+        match self {
+            Self::C(cnode) => cnode.fold(init, op),
+            Self::L(lnode) => lnode.fold(init, op),
+            Self::S(snode) => snode.fold(init, op),
+        }
+    }
+
+    pub(crate) async fn partition<Pred>(&self, pred: &Pred, par_strat: ParallelismStrategy) -> (MNodeTransformResult<H, F, K, V, M, A, ()>, MNodeTransformResult<H, F, K, V, M, A, ()>)
+    where
+        Pred: Fn(&K, &V) -> bool + Send + Sync,
+    {
+        if let Self::C(cnode) = self {
+            return cnode::partition(cnode, pred, par_strat).await;
+        }
+
+        let mut entries = Vec::new();
+        self.entries_with_hashes(&mut entries);
+        let (kept, rejected): (Vec<_>, Vec<_>) = entries.into_iter().partition(|(key, value, _hash_value)| pred(key, value));
+        if rejected.is_empty() {
+            (MNodeTransformResult::Unchanged(()), MNodeTransformResult::Removed(()))
+        }
+        else if kept.is_empty() {
+            (MNodeTransformResult::Removed(()), MNodeTransformResult::Unchanged(()))
+        }
+        else {
+            (Self::partition_leaf(kept), Self::partition_leaf(rejected))
+        }
+    }
+
+    fn partition_leaf(entries: Vec<(&K, &V, H)>) -> MNodeTransformResult<H, F, K, V, M, A, ()> {
+        match Self::build_leaf(entries.into_iter().map(|(key, value, hash_value)| (hash_value, key.clone(), Some(value.clone()))).collect()) {
+            Some(Self::L(lnode)) => MNodeTransformResult::L(lnode, ()),
+            Some(Self::S(snode)) => MNodeTransformResult::S(snode, ()),
+            Some(Self::C(_)) | None => MNodeTransformResult::Removed(()),
+        }
+    }
+
+    pub(crate) async fn par_reduce<T, MapOp, ReduceOp>(&self, map_op: &MapOp, reduce_op: &ReduceOp, par_strat: ParallelismStrategy) -> Option<T>
+    where
+        MapOp: Fn(&K, &V) -> T + Send + Sync,
+        ReduceOp: Fn(T, T) -> T + Send + Sync,
+    {
+        match self {
+            Self::C(cnode) => cnode::par_reduce(cnode, map_op, reduce_op, par_strat).await,
+            Self::L(_) | Self::S(_) => self.fold(None, &mut |reduced, key, value| reduce_options(reduced, Some(map_op(key, value)), reduce_op)),
+        }
+    }
+
+    pub(crate) fn find_many<'a, L: Key>(&'a self, queries: &[(usize, H, &L)], depth: usize, found: &mut [Option<(&'a K, &'a V)>]) where K: PartialEq<L> {
+        if let Self::C(cnode) = self {
+            return cnode.find_many(queries, depth, found);
+        }
+
+        for (position, hash_value, key) in queries {
+            let result = match self {
+                Self::C(_) => FindResult::NotFound,
+                Self::L(lnode) => lnode.find(*key, hash_value),
+                Self::S(snode) => snode.find(*key, hash_value),
+            };
+            if let FindResult::Found(key, value) = result {
+                found[*position] = Some((key, value));
+            }
+        }
+    }
+
+    pub(crate) fn apply(this: Option<&Self>, entries: Vec<(H, K, Option<V>)>, depth: usize) -> Option<Self> {
+        let hash_value = match (this, entries.first()) {
+            (_, None) => return this.cloned(),
+            (Some(Self::C(_)), _) => None,
+            (Some(Self::L(lnode)), _) => Some(lnode.hash_value::<M>()),
+            (Some(Self::S(snode)), _) => Some(snode.hash_value::<M>()),
+            (None, Some((hash_value, _key, _value))) => Some(hash_value.clone()),
+        };
+        match hash_value {
+            Some(hash_value) if entries.iter().all(|(entry_hash_value, _key, _value)| *entry_hash_value == hash_value) => {
+                let mut existing = Vec::new();
+                if let Some(node) = this {
+                    node.entries_with_hashes(&mut existing);
+                }
+                let mut merged: Vec<(H, K, Option<V>)> = existing.into_iter().map(|(key, value, hash_value)| (hash_value, key.clone(), Some(value.clone()))).collect();
+                merged.extend(entries);
+                Self::build_leaf(merged)
+            },
+            _ => cnode::apply(this, entries, depth),
+        }
+    }
+
+    pub(crate) async fn transform_many<ReduceT, ReduceOp, Op>(nodes: &[&Self], reduce_op: &ReduceOp, op: &Op, depth: usize, par_strat: ParallelismStrategy) -> (Option<Self>, ReduceT)
+    where
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
+        Op: Fn(&[(&K, &V)]) -> MapTransformResult<V, ReduceT> + Send + Sync,
+    {
+        if let [node] = nodes {
+            return match node.transform(reduce_op.clone(), new_map_transform_generic(|key: &K, value: &V| op(&[(key, value)])), par_strat).await {
+                MNodeTransformResult::Unchanged(reduced) => (Some((*node).clone()), reduced),
+                MNodeTransformResult::C(cnode, reduced) => (Some(Self::C(cnode)), reduced),
+                MNodeTransformResult::L(lnode, reduced) => (Some(Self::L(lnode)), reduced),
+                MNodeTransformResult::S(snode, reduced) => (Some(Self::S(snode)), reduced),
+                MNodeTransformResult::Removed(reduced) => (None, reduced),
+            };
+        }
+        let hash_value = match nodes.first() {
+            Some(node) => node.leaf_hash_value(),
+            None => return (None, ReduceT::default()),
+        };
+        if hash_value.is_none() || nodes.iter().any(|node| node.leaf_hash_value() != hash_value) {
+            return cnode::transform_many(nodes, reduce_op, op, depth, par_strat).await;
+        }
+
+        let mut groups: Vec<Vec<(&K, &V)>> = Vec::new();
+        for node in nodes {
+            let mut entries = Vec::new();
+            node.entries_with_hashes(&mut entries);
+            for (key, value, _hash_value) in entries {
+                match groups.iter_mut().find(|group| *group[0].0 == *key) {
+                    Some(group) => group.push((key, value)),
+                    None => groups.push(vec!((key, value))),
+                }
+            }
+        }
+
+        let hash_value = hash_value.unwrap();
+        let mut reduced = ReduceT::default();
+        let mut entries = Vec::with_capacity(groups.len());
+        for group in groups {
+            let (key, value) = group[0];
+            match op(&group) {
+                MapTransformResult::Unchanged(r) => {
+                    entries.push((hash_value.clone(), key.clone(), Some(value.clone())));
+                    reduced = reduce_op.reduce(reduced, r);
+                },
+                MapTransformResult::Transformed(transformed, r) => {
+                    entries.push((hash_value.clone(), key.clone(), Some(transformed)));
+                    reduced = reduce_op.reduce(reduced, r);
+                },
+                MapTransformResult::Removed(r) => reduced = reduce_op.reduce(reduced, r),
+            }
+        }
+
+        (Self::build_leaf(entries), reduced)
+    }
+
+    pub(crate) async fn join<W: Value, B: Measure<K, W>, X: Value, C: Measure<K, X>, Op>(&self, right: &MNode<H, F, K, W, M, B>, op: &Op, keep: (bool, bool), depth: usize, par_strat: ParallelismStrategy) -> Option<MNode<H, F, K, X, M, C>>
     where
-        ReduceT: Clone + Default + Send + Sync,
-        ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+        Op: Fn(&K, Option<&V>, Option<&W>) -> X + Send + Sync,
+    {
+        match (self, right) {
+            (Self::C(_), _) | (_, MNode::C(_)) => cnode::join(self, right, op, keep, depth, par_strat).await,
+            _ => MNode::build(self.join_leaves(right, op, keep), depth),
+        }
+    }
+
+    fn join_leaves<W: Value, B: Measure<K, W>, X, Op>(&self, right: &MNode<H, F, K, W, M, B>, op: &Op, keep: (bool, bool)) -> Vec<(H, K, Option<X>)> where Op: Fn(&K, Option<&V>, Option<&W>) -> X {
+        let mut lentries = Vec::new();
+        self.entries_with_hashes(&mut lentries);
+        let mut rentries = Vec::new();
+        right.entries_with_hashes(&mut rentries);
+
+        let mut joined = Vec::new();
+        for (key, value, hash_value) in lentries {
+            match rentries.iter().position(|(rkey, _rvalue, rhash_value)| *rhash_value == hash_value && *rkey == key) {
+                Some(index) => {
+                    let (_rkey, rvalue, _rhash_value) = rentries.swap_remove(index);
+                    joined.push((hash_value, key.clone(), Some(op(key, Some(value), Some(rvalue)))));
+                },
+                None => if keep.0 {
+                    joined.push((hash_value, key.clone(), Some(op(key, Some(value), None))));
+                },
+            }
+        }
+        if keep.1 {
+            joined.extend(rentries.into_iter().map(|(key, value, hash_value)| (hash_value, key.clone(), Some(op(key, None, Some(value))))));
+        }
+        joined
+    }
+
+    #[must_use]
+    pub(crate) fn child_or_leaf(&self, index: usize, depth: usize) -> Option<&Self> {
+        let hash_value = match self {
+            Self::C(cnode) => return cnode.child(index),
+            Self::L(lnode) => lnode.hash_value::<M>(),
+            Self::S(snode) => snode.hash_value::<M>(),
+        };
+        match Flag::<H, F>::new_at_depth(hash_value, depth) {
+            Some(flag) if flag.index() == index => Some(self),
+            _ => None,
+        }
+    }
+
+    fn leaf_hash_value(&self) -> Option<H> {
+        match self {
+            Self::C(_) => None,
+            Self::L(lnode) => Some(lnode.hash_value::<M>()),
+            Self::S(snode) => Some(snode.hash_value::<M>()),
+        }
+    }
+
+    pub(crate) fn nth(&self, index: usize) -> FindResult<'_, K, V> {
+        match self {
+            Self::C(cnode) => cnode.nth(index),
+            Self::L(lnode) => lnode.nth(index),
+            Self::S(snode) => snode.nth(index),
+        }
+    }
+
+    pub(crate) fn entries_with_hash_prefix<'a>(&'a self, prefix: Option<Flag<H, F>>, bits: usize, entries: &mut Vec<(&'a K, &'a V)>) {
+        match self {
+            Self::C(cnode) => cnode.entries_with_hash_prefix(prefix, bits, entries),
+            Self::L(lnode) => if bits == 0 || prefix.unwrap().prefix_eq(&lnode.hash_value::<M>(), bits) {
+                lnode.entries(entries);
+            },
+            Self::S(snode) => if bits == 0 || prefix.unwrap().prefix_eq(&snode.hash_value::<M>(), bits) {
+                entries.push((snode.key(), snode.value()));
+            },
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn split_by_hash_prefix(&self, depth: usize, bits: usize) -> Vec<Self> {
+        if bits == 0 {
+            return vec!(self.clone());
+        }
+        let hash_value = match self {
+            Self::C(cnode) => return cnode.split_by_hash_prefix(depth, bits),
+            Self::L(lnode) => lnode.hash_value::<M>(),
+            Self::S(snode) => snode.hash_value::<M>(),
+        };
+        let index = Flag::<H, F>::new_at_depth(hash_value, depth).map_or(0, |flag| flag.prefix(bits));
+        let mut parts = Vec::with_capacity(1 << bits);
+        parts.resize_with(1 << bits, Self::default);
+        parts[index] = self.clone();
+        parts
+    }
+
+    #[must_use]
+    pub(crate) fn child(&self, index: usize) -> Option<&Self> {
+        match self {
+            Self::C(cnode) => cnode.child(index),
+            Self::L(_) | Self::S(_) => None,
+        }
+    }
+
+    pub(crate) fn map_entries<X: Value, B: Measure<K, X>, Op: Fn(&K, &V) -> X>(&self, op: &Op) -> MNode<H, F, K, X, M, B> {
+        match self {
+            Self::C(cnode) => MNode::C(cnode.map_entries(op)),
+            Self::L(lnode) => MNode::L(lnode.map_entries(op)),
+            Self::S(snode) => MNode::S(snode.map_entries(op)),
+        }
+    }
+
+    pub(crate) async fn restrict_keys<B: Measure<K, ()>>(&self, keys: &MNode<H, F, K, (), M, B>, keep: bool, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, K, V, M, A, ()> {
+        if let Self::C(cnode) = self {
+            return cnode::restrict_keys(cnode, keys, keep, depth, par_strat).await;
+        }
+
+        let mut entries = Vec::new();
+        self.entries_with_hashes(&mut entries);
+        let (kept, rejected): (Vec<_>, Vec<_>) = entries.into_iter().partition(|(key, _value, hash_value)| keys.contains_with_hash(*key, hash_value, depth) == keep);
+        if rejected.is_empty() {
+            MNodeTransformResult::Unchanged(())
+        }
+        else if kept.is_empty() {
+            MNodeTransformResult::Removed(())
+        }
+        else {
+            Self::partition_leaf(kept)
+        }
+    }
+
+    fn contains_with_hash(&self, key: &K, hash_value: &H, depth: usize) -> bool {
+        let result = match self {
+            Self::C(cnode) => cnode.find(key, Flag::new_at_depth(hash_value.clone(), depth)),
+            Self::L(lnode) => lnode.find(key, hash_value),
+            Self::S(snode) => snode.find(key, hash_value),
+        };
+        matches!(result, FindResult::Found(_key, _value))
+    }
+
+    pub(crate) fn entries_with_hashes<'a>(&'a self, entries: &mut Vec<(&'a K, &'a V, H)>) {
+        match self {
+            Self::C(cnode) => cnode.entries_with_hashes(entries),
+            Self::L(lnode) => {
+                let mut lentries = Vec::new();
+                lnode.entries(&mut lentries);
+                let hash_value = lnode.hash_value::<M>();
+                entries.extend(lentries.into_iter().map(|(key, value)| (key, value, hash_value.clone())));
+            },
+            Self::S(snode) => entries.push((snode.key(), snode.value(), snode.hash_value::<M>())),
+        }
+    }
+
+    pub(crate) fn diff<'a, Same: Fn(&Self, &Self) -> bool>(&'a self, right: &'a Self, same: &Same, diffs: &mut Vec<MapDiff<'a, K, V>>) where V: PartialEq {
+        if same(self, right) {
+            return;
+        }
+        if let (Self::C(this), Self::C(right)) = (self, right) {
+            return this.diff(right, same, diffs);
+        }
+
+        let mut lefts = Vec::new();
+        let mut rights = Vec::new();
+        self.entries_with_hashes(&mut lefts);
+        right.entries_with_hashes(&mut rights);
+        for (key, value, hash_value) in &lefts {
+            match rights.iter().find(|(k, _v, h)| h == hash_value && k == key) {
+                Some((_k, v, _h)) => if v != value {
+                    diffs.push(MapDiff::Changed(key, value, v));
+                },
+                None => diffs.push(MapDiff::Removed(key, value)),
+            }
+        }
+        for (key, value, hash_value) in &rights {
+            if !lefts.iter().any(|(k, _v, h)| h == hash_value && k == key) {
+                diffs.push(MapDiff::Added(key, value));
+            }
+        }
+    }
+
+    pub(crate) fn build(entries: Vec<(H, K, Option<V>)>, depth: usize) -> Option<Self> {
+        if depth != 0 && entries.iter().all(|(hash_value, _key, _value)| *hash_value == entries[0].0) {
+            return Self::build_leaf(entries);
+        }
+
+        let mut groups = Vec::with_capacity(F::max_ones());
+        groups.resize_with(F::max_ones(), Vec::new);
+        for entry in entries {
+            groups[Flag::<H, F>::new_at_depth(entry.0.clone(), depth).unwrap().index()].push(entry);
+        }
+
+        let mut size = 0;
+        let mut bits_t = F::default();
+        let mut values_t = Vec::default();
+        for (index, group) in groups.into_iter().enumerate() {
+            if let Some(node) = Self::build(group, depth + 1) {
+                size += node.size();
+                bits_t = bits_t.bit_insert(<F>::nth_bit(index).unwrap()).unwrap();
+                values_t.push(node);
+            }
+        }
+
+        if depth != 0 && values_t.len() <= 1 && !matches!(values_t.first(), Some(Self::C(_))) {
+            return values_t.pop();
+        }
+        Some(Self::C(CNode::new(new_bit_indexed_array(bits_t, BitIndexedArrayVec::new(&values_t), size).unwrap())))
+    }
+
+    fn build_leaf(entries: Vec<(H, K, Option<V>)>) -> Option<Self> {
+        let hash_value = entries.first()?.0.clone();
+        // The keys share a full hash value, so a second, independent hash brings equal keys together, each followed by its later operations.
+        let mut entries: Vec<(u64, usize, K, Option<V>)> = entries.into_iter().enumerate().map(|(position, (_hash_value, key, value))| {
+            let mut hasher = fnv::FnvHasher::with_key(REHASH_KEY);
+            key.hash(&mut hasher);
+            (hasher.finish(), position, key, value)
+        }).collect();
+        entries.sort_unstable_by_key(|(rehash, position, _key, _value)| (*rehash, *position));
+        let mut survivors: Vec<(K, V)> = Vec::new();
+        let mut run: Vec<K> = Vec::new();
+        let mut run_rehash = None;
+        for (rehash, _position, key, value) in entries.into_iter().rev() {
+            if run_rehash != Some(rehash) {
+                run_rehash = Some(rehash);
+                run.clear();
+            }
+            if run.contains(&key) {
+                continue;
+            }
+            if let Some(value) = value {
+                survivors.push((key.clone(), value));
+            }
+            run.push(key);
+        }
+        let (key, value) = survivors.pop()?;
+        let next = survivors.into_iter().fold(LNodeNext::S(SNode::new(key, value, LeafHash::new(hash_value))), |next, (key, value)| LNodeNext::L(LNode::new(key, value, next)));
+        Some(next.into())
+    }
+
+    pub(crate) fn measure_memory(&self, seen: &mut BTreeSet<usize>, usage: &mut MemoryUsage) {
+        match self {
+            Self::C(cnode) => cnode.measure_memory(seen, usage),
+            Self::L(lnode) => lnode::measure_memory(lnode, seen, usage),
+            Self::S(snode) => snode::measure_memory(snode, seen, usage),
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn merge3<Conflict: Fn(&K, Option<&V>, Option<&V>, Option<&V>) -> Resolution<V>>(&self, left: &Self, right: &Self, depth: usize, conflict: &Conflict) -> Self where V: PartialEq {
+        if left == self || left == right {
+            return right.clone();
+        }
+        if right == self {
+            return left.clone();
+        }
+        let nodes = [self, left, right];
+        let hash_values: Vec<H> = nodes.iter().filter_map(|node| node.leaf_hash_value()).collect();
+        if nodes.iter().any(|node| node.size() != 0 && matches!(node, Self::C(_))) || hash_values.iter().any(|hash_value| *hash_value != hash_values[0]) {
+            return cnode::merge3(self, left, right, depth, conflict).unwrap_or_default();
+        }
+
+        let mut bases = Vec::new();
+        let mut lefts = Vec::new();
+        let mut rights = Vec::new();
+        self.entries_with_hashes(&mut bases);
+        left.entries_with_hashes(&mut lefts);
+        right.entries_with_hashes(&mut rights);
+
+        let unseen_rights = rights.iter().filter(|(key, _value, hash_value)| Self::find_entry(&lefts, key, hash_value).is_none());
+        let unseen_bases = bases.iter().filter(|(key, _value, hash_value)| Self::find_entry(&lefts, key, hash_value).is_none() && Self::find_entry(&rights, key, hash_value).is_none());
+        let mut entries = Vec::new();
+        for (key, _value, hash_value) in lefts.iter().chain(unseen_rights).chain(unseen_bases) {
+            let (b, l, r) = (Self::find_entry(&bases, key, hash_value), Self::find_entry(&lefts, key, hash_value), Self::find_entry(&rights, key, hash_value));
+            let merged = if l == r || r == b {
+                l.cloned()
+            }
+            else if l == b {
+                r.cloned()
+            }
+            else {
+                match conflict(key, b, l, r) {
+                    Resolution::Left => l.cloned(),
+                    Resolution::Right => r.cloned(),
+                    Resolution::Value(value) => Some(value),
+                    Resolution::Removed => None,
+                }
+            };
+            if let Some(value) = merged {
+                entries.push((hash_value.clone(), (*key).clone(), Some(value)));
+            }
+        }
+        Self::build(entries, depth).unwrap_or_default()
+    }
+
+    fn find_entry<'a>(entries: &[(&'a K, &'a V, H)], key: &K, hash_value: &H) -> Option<&'a V> {
+        entries.iter().find(|(k, _v, h)| h == hash_value && *k == key).map(|(_k, v, _h)| *v)
+    }
+
+    #[must_use]
+    pub(crate) fn split_root(&self) -> Vec<Self> {
+        match self {
+            Self::C(cnode) => cnode.split_root().into_iter().map(Self::C).collect(),
+            Self::L(_) | Self::S(_) => vec!(self.clone()),
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn concat_disjoint(&self, right: &Self, depth: usize) -> Self {
+        if right.size() == 0 {
+            return self.clone();
+        }
+        if self.size() == 0 {
+            return right.clone();
+        }
+        match (self, right) {
+            (Self::C(this), Self::C(right)) => Self::C(this.concat_disjoint(right, depth)),
+            (_, Self::C(_)) => right.insert_leaf_entries(self, depth),
+            (_, _) => self.insert_leaf_entries(right, depth),
+        }
+    }
+
+    fn insert_leaf_entries(&self, leaf: &Self, depth: usize) -> Self {
+        let hash_value = match leaf {
+            Self::C(_) => panic!(),
+            Self::L(lnode) => lnode.hash_value::<M>(),
+            Self::S(snode) => snode.hash_value::<M>(),
+        };
+        let mut entries = Vec::new();
+        leaf.entries_with_hash_prefix(None, 0, &mut entries);
+
+        let mut node = self.clone();
+        for (key, value) in entries {
+            let inserted = match node.insert(key.clone(), value.clone(), Flag::new_at_depth(hash_value.clone(), depth), false) {
+                InsertResult::Found(_key, _value) => None,
+                InsertResult::InsertedC(cnode, _key, _value, _prev) => Some(Self::C(cnode)),
+                InsertResult::InsertedL(lnode, _key, _value, _prev) => Some(Self::L(lnode)),
+                InsertResult::InsertedS(snode, _key, _value, _prev) => Some(Self::S(snode)),
+            };
+            if let Some(inserted) = inserted {
+                node = inserted;
+            }
+        }
+        node
+    }
+
+    pub(crate) async fn transform<ReduceT, ReduceOp, Op>(&self, reduce_op: ReduceOp, op: MapTransform<ReduceT, Op>, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, K, V, M, A, ReduceT>
+    where
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
         Op: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
         <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
     {
@@ -73,10 +560,40 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> MNode<H,
         }
     }
 
-    pub(crate) unsafe fn transmute<S: Key, X: Value, ReduceT, ReduceOp, Op>(&self, reduce_op: ReduceOp, op: MapTransmute<ReduceT, Op>) -> MNodeTransmuteResult<H, F, S, X, M, ReduceT>
+    pub(crate) async fn transform_async<ReduceT, ReduceOp, Op, Fut>(&self, reduce_op: ReduceOp, op: Op, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, K, V, M, A, ReduceT>
+    where
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
+        Op: Fn(&K, &V) -> Fut + Clone + Send + Sync,
+        Fut: Future<Output = MapTransformResult<V, ReduceT>>,
+        <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
+    {
+        match self {
+            Self::C(cnode) => cnode::transform_async(cnode, reduce_op, op, par_strat).await,
+            Self::L(lnode) => lnode::transform_async(lnode, reduce_op, op).await.into(),
+            Self::S(snode) => snode::transform_async(snode, op).await.into(),
+        }
+    }
+
+    pub(crate) async fn try_transform<ReduceT, ReduceOp, Op, E>(&self, reduce_op: ReduceOp, op: Op, par_strat: ParallelismStrategy) -> Result<MNodeTransformResult<H, F, K, V, M, A, ReduceT>, E>
     where
-        ReduceT: Clone + Default + Send + Sync,
-        ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
+        Op: Fn(&K, &V) -> Result<MapTransformResult<V, ReduceT>, E> + Clone + Send + Sync,
+        <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
+    {
+        Ok(match self {
+            Self::C(cnode) => cnode::try_transform(cnode, reduce_op, op, par_strat).await?,
+            Self::L(lnode) => lnode::try_transform(lnode, reduce_op, op)?.into(),
+            Self::S(snode) => snode::try_transform(snode, op)?.into(),
+        })
+    }
+
+    pub(crate) unsafe fn transmute<S: Key, X: Value, ReduceT, ReduceOp, Op>(&self, reduce_op: ReduceOp, op: MapTransmute<ReduceT, Op>) -> MNodeTransmuteResult<H, F, S, X, M, A, ReduceT>
+    where
+        A: Measure<S, X>,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
         Op: Fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT> + Clone,
         K: HashLike<S>,
         K: PartialEq<S>,
@@ -90,10 +607,10 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> MNode<H,
         }
     }
 
-    pub(crate) async fn transform_with_transformed<ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(&self, right: &Self, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeJointTransformResult<H, F, K, V, M, ReduceT>
+    pub(crate) async fn transform_with_transformed<ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(&self, right: &Self, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeJointTransformResult<H, F, K, V, M, A, ReduceT>
     where
-        ReduceT: Clone + Default + Send + Sync,
-        ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
         BothOp: Fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT> + Clone + Send + Sync,
         LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
         RightOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
@@ -106,10 +623,119 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> MNode<H,
         }
     }
 
-    pub(crate) async unsafe fn transform_with_transmuted<L: Key, W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(&self, right: &MNode<H, F, L, W, M>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, K, V, M, ReduceT>
+    pub(crate) async fn try_transform_with_transformed<ReduceT, ReduceOp, BothOp, LeftOp, RightOp, E>(&self, right: &Self, ops: JointOps<'_, ReduceOp, BothOp, LeftOp, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> Result<MNodeJointTransformResult<H, F, K, V, M, A, ReduceT>, E>
     where
-        ReduceT: Clone + Default + Send + Sync,
-        ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
+        BothOp: Fn(&K, &V, &K, &V) -> Result<MapJointTransformResult<V, ReduceT>, E> + Clone + Send + Sync,
+        LeftOp: Fn(&K, &V) -> Result<MapTransformResult<V, ReduceT>, E> + Clone + Send + Sync,
+        RightOp: Fn(&K, &V) -> Result<MapTransformResult<V, ReduceT>, E> + Clone + Send + Sync,
+        <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
+    {
+        if self.leaf_hash_value().is_none() || self.leaf_hash_value() != right.leaf_hash_value() {
+            return cnode::try_transform_with_transformed(self, right, ops, depth, par_strat).await;
+        }
+
+        let mut lefts = Vec::new();
+        let mut rights = Vec::new();
+        self.entries_with_hashes(&mut lefts);
+        right.entries_with_hashes(&mut rights);
+
+        let mut entries = Vec::with_capacity(lefts.len() + rights.len());
+        let mut reduced = ReduceT::default();
+        let mut unchangedl = true;
+        let mut unchangedr = true;
+        for (key, value, hash_value) in &lefts {
+            let (value, r) = match rights.iter().find(|(k, _v, h)| h == hash_value && k == key) {
+                Some((rkey, rvalue, _h)) => match (ops.both_op)(key, value, rkey, rvalue)? {
+                    MapJointTransformResult::UnchangedLR(r) => (Some((*value).clone()), r),
+                    MapJointTransformResult::UnchangedL(r) => {
+                        unchangedr = false;
+                        (Some((*value).clone()), r)
+                    },
+                    MapJointTransformResult::UnchangedR(r) => {
+                        unchangedl = false;
+                        (Some((*rvalue).clone()), r)
+                    },
+                    MapJointTransformResult::Transformed(value, r) => {
+                        unchangedl = false;
+                        unchangedr = false;
+                        (Some(value), r)
+                    },
+                    MapJointTransformResult::Removed(r) => {
+                        unchangedl = false;
+                        unchangedr = false;
+                        (None, r)
+                    },
+                },
+                None => match (ops.left_op)(key, value)? {
+                    MapTransformResult::Unchanged(r) => {
+                        unchangedr = false;
+                        (Some((*value).clone()), r)
+                    },
+                    MapTransformResult::Transformed(value, r) => {
+                        unchangedl = false;
+                        unchangedr = false;
+                        (Some(value), r)
+                    },
+                    MapTransformResult::Removed(r) => {
+                        unchangedl = false;
+                        (None, r)
+                    },
+                },
+            };
+            reduced = ops.reduce_op.reduce(reduced, r);
+            entries.push((hash_value.clone(), (*key).clone(), value));
+        }
+        for (key, value, hash_value) in &rights {
+            if lefts.iter().any(|(k, _v, h)| h == hash_value && k == key) {
+                continue;
+            }
+            let (value, r) = match (ops.right_op)(key, value)? {
+                MapTransformResult::Unchanged(r) => {
+                    unchangedl = false;
+                    (Some((*value).clone()), r)
+                },
+                MapTransformResult::Transformed(value, r) => {
+                    unchangedl = false;
+                    unchangedr = false;
+                    (Some(value), r)
+                },
+                MapTransformResult::Removed(r) => {
+                    unchangedr = false;
+                    (None, r)
+                },
+            };
+            reduced = ops.reduce_op.reduce(reduced, r);
+            entries.push((hash_value.clone(), (*key).clone(), value));
+        }
+
+        Ok(if unchangedl {
+            if unchangedr {
+                MNodeJointTransformResult::UnchangedLR(reduced)
+            }
+            else {
+                MNodeJointTransformResult::UnchangedL(reduced)
+            }
+        }
+        else if unchangedr {
+            MNodeJointTransformResult::UnchangedR(reduced)
+        }
+        else {
+            match Self::build(entries, depth) {
+                Some(Self::C(cnode)) => MNodeJointTransformResult::C(cnode, reduced),
+                Some(Self::L(lnode)) => MNodeJointTransformResult::L(lnode, reduced),
+                Some(Self::S(snode)) => MNodeJointTransformResult::S(snode, reduced),
+                None => MNodeJointTransformResult::Removed(reduced),
+            }
+        })
+    }
+
+    pub(crate) async unsafe fn transform_with_transmuted<L: Key, W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(&self, right: &MNode<H, F, L, W, M, A>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, K, V, M, A, ReduceT>
+    where
+        A: Measure<L, W>,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
         BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<V, ReduceT> + Clone,
         LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
         RightOp: Fn(&L, &W) -> MapTransmuteResult<K, V, ReduceT> + Clone + Send + Sync,
@@ -127,10 +753,11 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> MNode<H,
         }
     }
 
-    pub(crate) async unsafe fn transmute_with_transformed<L: Key, W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(&self, right: &MNode<H, F, L, W, M>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, L, W, M, ReduceT>
+    pub(crate) async unsafe fn transmute_with_transformed<L: Key, W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(&self, right: &MNode<H, F, L, W, M, A>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, L, W, M, A, ReduceT>
     where
-        ReduceT: Clone + Default + Send + Sync,
-        ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+        A: Measure<L, W>,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
         BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<W, ReduceT> + Clone,
         LeftOp: Fn(&K, &V) -> MapTransmuteResult<L, W, ReduceT> + Clone + Send + Sync,
         RightOp: Fn(&L, &W) -> MapTransformResult<W, ReduceT> + Clone + Send + Sync,
@@ -148,10 +775,12 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> MNode<H,
         }
     }
 
-    pub(crate) unsafe fn transmute_with_transmuted<L: Key, W: Value, S: Key, X: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(&self, right: &MNode<H, F, L, W, M>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransmuteResult<H, F, S, X, M, ReduceT>
+    pub(crate) unsafe fn transmute_with_transmuted<L: Key, W: Value, S: Key, X: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(&self, right: &MNode<H, F, L, W, M, A>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransmuteResult<H, F, S, X, M, A, ReduceT>
     where
-        ReduceT: Clone + Default + Send + Sync,
-        ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+        A: Measure<L, W>,
+        A: Measure<S, X>,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
         BothOp: Fn(&K, &V, &L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
         LeftOp: Fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT> + Clone,
         RightOp: Fn(&L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
@@ -174,10 +803,10 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> MNode<H,
         }
     }
 
-    pub(crate) async fn transform_with_transformed_lnode<ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(&self, right: &Arc<LNode<K, V>>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeJointTransformResult<H, F, K, V, M, ReduceT>
+    pub(crate) async fn transform_with_transformed_lnode<ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(&self, right: &Arc<LNode<H, K, V>>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeJointTransformResult<H, F, K, V, M, A, ReduceT>
     where
-        ReduceT: Clone + Default + Send + Sync,
-        ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
         BothOp: Fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT> + Clone + Send + Sync,
         LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
         RightOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
@@ -190,10 +819,11 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> MNode<H,
         }
     }
 
-    pub(crate) async unsafe fn transform_with_transmuted_lnode<L: Key, W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(&self, right: &Arc<LNode<L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, K, V, M, ReduceT>
+    pub(crate) async unsafe fn transform_with_transmuted_lnode<L: Key, W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(&self, right: &Arc<LNode<H, L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, K, V, M, A, ReduceT>
     where
-        ReduceT: Clone + Default + Send + Sync,
-        ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+        A: Measure<L, W>,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
         BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<V, ReduceT> + Clone,
         LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
         RightOp: Fn(&L, &W) -> MapTransmuteResult<K, V, ReduceT> + Clone + Send + Sync,
@@ -207,14 +837,15 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> MNode<H,
         match self {
             MNode::C(cnode) => cnode::transform_with_transmuted_lnode(cnode, right, reduce_op, both_op, left_op, right_op, depth, par_strat).await,
             MNode::L(lnode) => lnode::transform_with_transmuted_lnode(lnode, right, reduce_op, both_op, left_op, right_op, depth),
-            MNode::S(snode) => lnode::transmute_with_transformed_snode(right, snode, reduce_op, both_op.flip_transform_transmute(), right_op, left_op, depth),
+            MNode::S(snode) => lnode::transmute_with_transformed_snode(right, snode, reduce_op, both_op.flip_joint(), right_op, left_op, depth),
         }
     }
 
-    pub(crate) unsafe fn transmute_with_transformed_lnode<L: Key, W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(&self, right: &Arc<LNode<L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize) -> MNodeTransformResult<H, F, L, W, M, ReduceT>
+    pub(crate) unsafe fn transmute_with_transformed_lnode<L: Key, W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(&self, right: &Arc<LNode<H, L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize) -> MNodeTransformResult<H, F, L, W, M, A, ReduceT>
     where
-        ReduceT: Clone + Default + Send + Sync,
-        ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+        A: Measure<L, W>,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
         BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<W, ReduceT> + Clone,
         LeftOp: Fn(&K, &V) -> MapTransmuteResult<L, W, ReduceT> + Clone + Send + Sync,
         RightOp: Fn(&L, &W) -> MapTransformResult<W, ReduceT> + Clone + Send + Sync,
@@ -227,15 +858,17 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> MNode<H,
     {
         match self {
             MNode::C(cnode) => cnode::transmute_with_transformed_lnode(cnode, right, reduce_op, both_op, left_op, right_op, depth),
-            MNode::L(lnode) => lnode::transform_with_transmuted_lnode(right, lnode, reduce_op, both_op.flip_transmute_transform(), right_op, left_op, depth),
-            MNode::S(snode) => lnode::transform_with_transmuted_snode(right, snode, reduce_op, both_op.flip_transmute_transform(), right_op, left_op, depth),
+            MNode::L(lnode) => lnode::transform_with_transmuted_lnode(right, lnode, reduce_op, both_op.flip_joint(), right_op, left_op, depth),
+            MNode::S(snode) => lnode::transform_with_transmuted_snode(right, snode, reduce_op, both_op.flip_joint(), right_op, left_op, depth),
         }
     }
 
-    pub(crate) unsafe fn transmute_with_transmuted_lnode<L: Key, W: Value, S: Key, X: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(&self, right: &Arc<LNode<L, W>>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransmuteResult<H, F, S, X, M, ReduceT>
+    pub(crate) unsafe fn transmute_with_transmuted_lnode<L: Key, W: Value, S: Key, X: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(&self, right: &Arc<LNode<H, L, W>>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransmuteResult<H, F, S, X, M, A, ReduceT>
     where
-        ReduceT: Clone + Default + Send + Sync,
-        ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+        A: Measure<L, W>,
+        A: Measure<S, X>,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
         BothOp: Fn(&K, &V, &L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
         LeftOp: Fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT> + Clone,
         RightOp: Fn(&L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
@@ -258,10 +891,10 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> MNode<H,
         }
     }
 
-    pub(crate) async fn transform_with_transformed_snode<ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(&self, right: &Arc<SNode<K, V>>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeJointTransformResult<H, F, K, V, M, ReduceT>
+    pub(crate) async fn transform_with_transformed_snode<ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(&self, right: &Arc<SNode<H, K, V>>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeJointTransformResult<H, F, K, V, M, A, ReduceT>
     where
-        ReduceT: Clone + Default + Send + Sync,
-        ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
         BothOp: Fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT> + Clone + Send + Sync,
         LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
         RightOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
@@ -274,10 +907,11 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> MNode<H,
         }
     }
 
-    pub(crate) async unsafe fn transform_with_transmuted_snode<L: Key, W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(&self, right: &Arc<SNode<L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, K, V, M, ReduceT>
+    pub(crate) async unsafe fn transform_with_transmuted_snode<L: Key, W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(&self, right: &Arc<SNode<H, L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, K, V, M, A, ReduceT>
     where
-        ReduceT: Clone + Default + Send + Sync,
-        ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+        A: Measure<L, W>,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
         BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<V, ReduceT> + Clone,
         LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
         RightOp: Fn(&L, &W) -> MapTransmuteResult<K, V, ReduceT> + Clone + Send + Sync,
@@ -295,10 +929,11 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> MNode<H,
         }
     }
 
-    pub(crate) unsafe fn transmute_with_transformed_snode<L: Key, W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(&self, right: &Arc<SNode<L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, L, W, M, ReduceT>
+    pub(crate) unsafe fn transmute_with_transformed_snode<L: Key, W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(&self, right: &Arc<SNode<H, L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, L, W, M, A, ReduceT>
     where
-        ReduceT: Clone + Default + Send + Sync,
-        ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+        A: Measure<L, W>,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
         BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<W, ReduceT> + Clone,
         LeftOp: Fn(&K, &V) -> MapTransmuteResult<L, W, ReduceT> + Clone + Send + Sync,
         RightOp: Fn(&L, &W) -> MapTransformResult<W, ReduceT> + Clone + Send + Sync,
@@ -316,10 +951,12 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> MNode<H,
         }
     }
 
-    pub(crate) unsafe fn transmute_with_transmuted_snode<L: Key, W: Value, S: Key, X: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(&self, right: &Arc<SNode<L, W>>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransmuteResult<H, F, S, X, M, ReduceT>
+    pub(crate) unsafe fn transmute_with_transmuted_snode<L: Key, W: Value, S: Key, X: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(&self, right: &Arc<SNode<H, L, W>>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransmuteResult<H, F, S, X, M, A, ReduceT>
     where
-        ReduceT: Clone + Default + Send + Sync,
-        ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+        A: Measure<L, W>,
+        A: Measure<S, X>,
+        ReduceT: Default + Send + Sync,
+        ReduceOp: Reducer<ReduceT>,
         BothOp: Fn(&K, &V, &L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
         LeftOp: Fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT> + Clone,
         RightOp: Fn(&L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
@@ -344,7 +981,18 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> MNode<H,
 
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static> Clone for MNode<H, F, K, V, M> {
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> MNode<H, F, K, V, M, A> {
+    #[must_use]
+    pub(crate) fn summary(&self) -> A::Summary {
+        match self {
+            Self::C(cnode) => cnode.summary().clone(),
+            Self::L(lnode) => lnode.summary::<A>(),
+            Self::S(snode) => A::measure(snode.key(), snode.value()),
+        }
+    }
+}
+
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>> Clone for MNode<H, F, K, V, M, A> {
     fn clone(&self) -> Self {
         match self {
             Self::C(this) => Self::C((*this).clone()),
@@ -354,15 +1002,25 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static> Clone for MNode
     }
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> Default for MNode<H, F, K, V, M> {
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>> Debug for MNode<H, F, K, V, M, A> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::C(cnode) => f.debug_tuple("C").field(cnode).finish(),
+            Self::L(lnode) => f.debug_tuple("L").field(lnode).finish(),
+            Self::S(snode) => f.debug_tuple("S").field(snode).finish(),
+        }
+    }
+}
+
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> Default for MNode<H, F, K, V, M, A> {
     fn default() -> Self {
         Self::C(CNode::default())
     }
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> Eq for MNode<H, F, K, V, M> {}
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> Eq for MNode<H, F, K, V, M, A> {}
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> PartialEq for MNode<H, F, K, V, M> {
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> PartialEq for MNode<H, F, K, V, M, A> {
     fn eq(&self, other: &Self) -> bool {
         match self {
             MNode::C(cnode) => if let MNode::C(other) = other {
@@ -387,6 +1045,13 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> PartialE
     }
 }
 
-unsafe impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> Send for MNode<H, F, K, V, M> {}
+unsafe impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> Send for MNode<H, F, K, V, M, A> {}
 
-unsafe impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> Sync for MNode<H, F, K, V, M> {}
+unsafe impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> Sync for MNode<H, F, K, V, M, A> {}
+
+pub(super) fn reduce_options<T, ReduceOp: Fn(T, T) -> T>(left: Option<T>, right: Option<T>, reduce_op: &ReduceOp) -> Option<T> {
+    match (left, right) {
+        (Some(left), Some(right)) => Some(reduce_op(left, right)),
+        (left, right) => left.or(right),
+    }
+}
@@ -1,9 +1,11 @@
 mod cnode;
+mod leaf_hash;
 mod lnode;
 mod mnode;
 mod snode;
 
-pub(crate) use cnode::CNode;
+pub(crate) use cnode::{CNode, JointOps};
+pub(crate) use leaf_hash::LeafHash;
 pub(crate) use lnode::LNode;
 pub(crate) use mnode::MNode;
 pub(crate) use snode::SNode;
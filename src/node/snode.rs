@@ -1,14 +1,26 @@
 use crate::{flag::*, transformations::*, results::*, traits::*, ParallelismStrategy};
-use super::{cnode::{self}, lnode::{self, *}, mnode::*};
-use alloc::{fmt::Debug, sync::Arc};
+use super::{cnode::{self}, leaf_hash::LeafHash, lnode::{self, *}, mnode::*};
+use alloc::{collections::BTreeSet, fmt::Debug, sync::Arc};
+use core::future::Future;
 
-#[derive(Clone, Debug)]
-pub(crate) struct SNode<K: Key, V: Value> {
+#[derive(Clone)]
+pub(crate) struct SNode<H: Hashword, K: Key, V: Value> {
+    hash: LeafHash<H>,
     key: K,
     value: V,
 }
 
-impl <K: Key, V: Value> SNode<K, V> {
+impl <H: Hashword, K: Key, V: Value> SNode<H, K, V> {
+    #[must_use]
+    pub(super) fn leaf_hash(&self) -> LeafHash<H> {
+        self.hash.clone()
+    }
+
+    #[must_use]
+    pub(super) fn hash_value<M: HasherBv<H, K>>(&self) -> H {
+        self.hash.get::<K, M>(&self.key)
+    }
+
     #[must_use]
     pub(super) fn key(&self) -> &K {
         &self.key
@@ -20,16 +32,33 @@ impl <K: Key, V: Value> SNode<K, V> {
     }
 
     #[must_use]
-    pub(crate) fn new(key: K, value: V) -> Arc<Self> {
-        Arc::new(Self {key, value})
+    pub(crate) fn new(key: K, value: V, hash: LeafHash<H>) -> Arc<Self> {
+        Arc::new(Self {hash, key, value})
+    }
+
+    pub(super) fn map_entries<X: Value, Op: Fn(&K, &V) -> X>(&self, op: &Op) -> Arc<SNode<H, K, X>> {
+        SNode::new(self.key.clone(), op(&self.key, &self.value), self.leaf_hash())
     }
 
     pub(super) fn visit<Op>(&self, op: Op) where Op: Fn(&K, &V) {
         op(&self.key, &self.value);
     }
 
-    pub(super) fn find<'a, L: Key>(&'a self, key: &L) -> FindResult<'a, K, V> where K: PartialEq<L> {
-        if self.key == *key {
+    pub(super) fn fold<T, Op>(&self, init: T, op: &mut Op) -> T where Op: FnMut(T, &K, &V) -> T {
+        op(init, &self.key, &self.value)
+    }
+
+    pub(super) fn nth(&self, index: usize) -> FindResult<'_, K, V> {
+        if index == 0 {
+            FindResult::Found(&self.key, &self.value)
+        }
+        else {
+            FindResult::NotFound
+        }
+    }
+
+    pub(super) fn find<'a, L: Key>(&'a self, key: &L, hash_value: &H) -> FindResult<'a, K, V> where K: PartialEq<L> {
+        if self.hash.matches(hash_value) && self.key == *key {
             FindResult::Found(&self.key, &self.value)
         }
         else {
@@ -39,16 +68,16 @@ impl <K: Key, V: Value> SNode<K, V> {
     
 }
 
-pub(super) fn insert<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key + Into<K>, W: Into<V>, M: HasherBv<H, K>>(this: &Arc<SNode<K, V>>, key: L, value: W, key_flag: Option<Flag<H, F>>, replace: bool) -> InsertResult<H, F, K, V, M>
+pub(super) fn insert<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key + Into<K>, W: Into<V>, M: HasherBv<H, K>, A: Measure<K, V>>(this: &Arc<SNode<H, K, V>>, key: L, value: W, key_flag: Option<Flag<H, F>>, replace: bool) -> InsertResult<H, F, K, V, M, A>
 where
     K: HashLike<L>,
     K: PartialEq<L>,
     M: HasherBv<H, L>,
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
-    if this.key == key {
+    if this.hash.matches(&key_flag.as_ref().unwrap().hash_value()) && this.key == key {
         if replace {
-            let snode = SNode::new(key.into(), value.into());
+            let snode = SNode::new(key.into(), value.into(), this.leaf_hash());
             let key: *const K = snode.key();
             let value: *const V = snode.value();
             InsertResult::InsertedS(snode, key, value, Some((this.key(), this.value())))
@@ -62,8 +91,8 @@ where
     }
 }
 
-pub(super) fn remove<'a, K: Key, V: Value, L: Key>(this: &'a Arc<SNode<K, V>>, key: &L) -> SNodeRemoveResult<'a, K, V> where K: PartialEq<L> {
-    if this.key == *key {
+pub(super) fn remove<'a, H: Hashword, K: Key, V: Value, L: Key>(this: &'a Arc<SNode<H, K, V>>, key: &L, hash_value: &H) -> SNodeRemoveResult<'a, K, V> where K: PartialEq<L> {
+    if this.hash.matches(hash_value) && this.key == *key {
         SNodeRemoveResult::RemovedZ(this.key(), this.value())
     }
     else {
@@ -71,32 +100,64 @@ pub(super) fn remove<'a, K: Key, V: Value, L: Key>(this: &'a Arc<SNode<K, V>>, k
     }
 }
 
-pub(super) fn transform<K: Key, V: Value, ReduceT, Op>(this: &Arc<SNode<K, V>>, op: MapTransform<ReduceT, Op>) -> SNodeTransformResult<K, V, ReduceT>
+pub(super) fn measure_memory<H: Hashword, K: Key, V: Value>(this: &Arc<SNode<H, K, V>>, seen: &mut BTreeSet<usize>, usage: &mut MemoryUsage) {
+    if seen.insert(Arc::as_ptr(this) as usize) {
+        usage.nodes += 1;
+        usage.bytes += core::mem::size_of::<SNode<H, K, V>>();
+    }
+}
+
+pub(super) fn transform<H: Hashword, K: Key, V: Value, ReduceT, Op>(this: &Arc<SNode<H, K, V>>, op: MapTransform<ReduceT, Op>) -> SNodeTransformResult<H, K, V, ReduceT>
     where
-    ReduceT: Clone + Default + Send + Sync,
+    ReduceT: Default + Send + Sync,
     Op: Fn(&K, &V) -> MapTransformResult<V, ReduceT>,
 {
     match op.call(&this.key, &this.value) {
         MapTransformResult::Unchanged(reduced) => SNodeTransformResult::Unchanged(reduced),
-        MapTransformResult::Transformed(value, reduced) => SNodeTransformResult::S(SNode::new(this.key.clone(), value), reduced),
+        MapTransformResult::Transformed(value, reduced) => SNodeTransformResult::S(SNode::new(this.key.clone(), value, this.leaf_hash()), reduced),
+        MapTransformResult::Removed(reduced) => SNodeTransformResult::Removed(reduced),
+    }
+}
+
+pub(super) fn try_transform<H: Hashword, K: Key, V: Value, ReduceT, Op, E>(this: &Arc<SNode<H, K, V>>, op: Op) -> Result<SNodeTransformResult<H, K, V, ReduceT>, E>
+    where
+    ReduceT: Default + Send + Sync,
+    Op: Fn(&K, &V) -> Result<MapTransformResult<V, ReduceT>, E>,
+{
+    Ok(match op(&this.key, &this.value)? {
+        MapTransformResult::Unchanged(reduced) => SNodeTransformResult::Unchanged(reduced),
+        MapTransformResult::Transformed(value, reduced) => SNodeTransformResult::S(SNode::new(this.key.clone(), value, this.leaf_hash()), reduced),
+        MapTransformResult::Removed(reduced) => SNodeTransformResult::Removed(reduced),
+    })
+}
+
+pub(super) async fn transform_async<H: Hashword, K: Key, V: Value, ReduceT, Op, Fut>(this: &Arc<SNode<H, K, V>>, op: Op) -> SNodeTransformResult<H, K, V, ReduceT>
+    where
+    ReduceT: Default + Send + Sync,
+    Op: Fn(&K, &V) -> Fut,
+    Fut: Future<Output = MapTransformResult<V, ReduceT>>,
+{
+    match op(&this.key, &this.value).await {
+        MapTransformResult::Unchanged(reduced) => SNodeTransformResult::Unchanged(reduced),
+        MapTransformResult::Transformed(value, reduced) => SNodeTransformResult::S(SNode::new(this.key.clone(), value, this.leaf_hash()), reduced),
         MapTransformResult::Removed(reduced) => SNodeTransformResult::Removed(reduced),
     }
 }
 
-pub(super) unsafe fn transmute<K: Key, V: Value, S: Key, X: Value, ReduceT, Op>(this: &Arc<SNode<K, V>>, op: MapTransmute<ReduceT, Op>) -> SNodeTransmuteResult<S, X, ReduceT>
+pub(super) unsafe fn transmute<H: Hashword, K: Key, V: Value, S: Key, X: Value, ReduceT, Op>(this: &Arc<SNode<H, K, V>>, op: MapTransmute<ReduceT, Op>) -> SNodeTransmuteResult<H, S, X, ReduceT>
     where
-    ReduceT: Clone + Default + Send + Sync,
+    ReduceT: Default + Send + Sync,
     Op: Fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT>,
     K: HashLike<S>,
     K: PartialEq<S>,
 {
-    op.call(&this.key, &this.value).into()
+    (op.call(&this.key, &this.value), this.leaf_hash()).into()
 }
 
-pub(crate) async fn transform_with_transformed<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<SNode<K, V>>, right: &MNode<H, F, K, V, M>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeJointTransformResult<H, F, K, V, M, ReduceT>
+pub(crate) async fn transform_with_transformed<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<SNode<H, K, V>>, right: &MNode<H, F, K, V, M, A>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeJointTransformResult<H, F, K, V, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT> + Clone + Send + Sync,
     LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
@@ -109,10 +170,10 @@ where
     }
 }
 
-pub(crate) unsafe fn transform_with_transmuted<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<SNode<K, V>>, right: &MNode<H, F, L, W, M>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, K, V, M, ReduceT>
+pub(crate) unsafe fn transform_with_transmuted<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, A: Measure<K, V> + Measure<L, W>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<SNode<H, K, V>>, right: &MNode<H, F, L, W, M, A>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, K, V, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<V, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&L, &W) -> MapTransmuteResult<K, V, ReduceT> + Clone + Send + Sync,
@@ -124,16 +185,16 @@ where
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
     match right {
-        MNode::C(cnode) => cnode::transmute_with_transformed_snode(cnode, this, reduce_op, both_op.flip_transform_transmute(), right_op, left_op, depth, par_strat),
-        MNode::L(lnode) => lnode::transmute_with_transformed_snode(lnode, this, reduce_op, both_op.flip_transform_transmute(), right_op, left_op, depth),
+        MNode::C(cnode) => cnode::transmute_with_transformed_snode(cnode, this, reduce_op, both_op.flip_joint(), right_op, left_op, depth, par_strat),
+        MNode::L(lnode) => lnode::transmute_with_transformed_snode(lnode, this, reduce_op, both_op.flip_joint(), right_op, left_op, depth),
         MNode::S(snode) => transform_with_transmuted_snode(this, snode, reduce_op, both_op, left_op, right_op, depth),
     }
 }
 
-pub(crate) async unsafe fn transmute_with_transformed<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<SNode<K, V>>, right: &MNode<H, F, L, W, M>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, L, W, M, ReduceT>
+pub(crate) async unsafe fn transmute_with_transformed<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, A: Measure<K, V> + Measure<L, W>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<SNode<H, K, V>>, right: &MNode<H, F, L, W, M, A>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize, par_strat: ParallelismStrategy) -> MNodeTransformResult<H, F, L, W, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<W, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransmuteResult<L, W, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&L, &W) -> MapTransformResult<W, ReduceT> + Clone + Send + Sync,
@@ -145,23 +206,23 @@ where
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
     match right {
-        MNode::C(cnode) => cnode::transform_with_transmuted_snode(cnode, this, reduce_op, both_op.flip_transmute_transform(), right_op, left_op, depth, par_strat).await,
-        MNode::L(lnode) => lnode::transform_with_transmuted_snode(lnode, this, reduce_op, both_op.flip_transmute_transform(), right_op, left_op, depth),
+        MNode::C(cnode) => cnode::transform_with_transmuted_snode(cnode, this, reduce_op, both_op.flip_joint(), right_op, left_op, depth, par_strat).await,
+        MNode::L(lnode) => lnode::transform_with_transmuted_snode(lnode, this, reduce_op, both_op.flip_joint(), right_op, left_op, depth),
         MNode::S(snode) => transmute_with_transformed_snode(this, snode, reduce_op, both_op, left_op, right_op, depth),
     }
 }
 
-pub(crate) fn transform_with_transformed_snode<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<SNode<K, V>>, right: &Arc<SNode<K, V>>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize) -> MNodeJointTransformResult<H, F, K, V, M, ReduceT>
+pub(crate) fn transform_with_transformed_snode<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<SNode<H, K, V>>, right: &Arc<SNode<H, K, V>>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize) -> MNodeJointTransformResult<H, F, K, V, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT> + Clone + Send + Sync,
     LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
-    let this_hash = M::default().hash(this.key());
-    let right_hash = M::default().hash(right.key());
+    let this_hash = this.hash_value::<M>();
+    let right_hash = right.hash_value::<M>();
 
     if this_hash == right_hash {
         transform_with_transformed_snode_impl(this, right, reduce_op, both_op, left_op, right_op).into()
@@ -175,28 +236,28 @@ where
 
         match left {
             SNodeTransformResult::Unchanged(lr) => match r {
-                SNodeTransformResult::Unchanged(rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(MNode::S(this.clone()), left_flag, right.clone().into(), right_flag), reduce_op(&lr, &rr)),
-                SNodeTransformResult::S(rsnode, rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(MNode::S(this.clone()), left_flag, rsnode.into(), right_flag), reduce_op(&lr, &rr)),
-                SNodeTransformResult::Removed(rr) => MNodeJointTransformResult::UnchangedL(reduce_op(&lr, &rr)),
+                SNodeTransformResult::Unchanged(rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(MNode::S(this.clone()), left_flag, right.clone().into(), right_flag), reduce_op.reduce(lr, rr)),
+                SNodeTransformResult::S(rsnode, rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(MNode::S(this.clone()), left_flag, rsnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                SNodeTransformResult::Removed(rr) => MNodeJointTransformResult::UnchangedL(reduce_op.reduce(lr, rr)),
             },
             SNodeTransformResult::S(lsnode, lr) => match r {
-                SNodeTransformResult::Unchanged(rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), left_flag, right.clone().into(), right_flag), reduce_op(&lr, &rr)),
-                SNodeTransformResult::S(rsnode, rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), left_flag, rsnode.into(), right_flag), reduce_op(&lr, &rr)),
-                SNodeTransformResult::Removed(rr) => MNodeJointTransformResult::S(lsnode, reduce_op(&lr, &rr)),
+                SNodeTransformResult::Unchanged(rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), left_flag, right.clone().into(), right_flag), reduce_op.reduce(lr, rr)),
+                SNodeTransformResult::S(rsnode, rr) => MNodeJointTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), left_flag, rsnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                SNodeTransformResult::Removed(rr) => MNodeJointTransformResult::S(lsnode, reduce_op.reduce(lr, rr)),
             },
             SNodeTransformResult::Removed(lr) => match r {
-                SNodeTransformResult::Unchanged(rr) => MNodeJointTransformResult::UnchangedR(reduce_op(&lr, &rr)),
-                SNodeTransformResult::S(rsnode, rr) => MNodeJointTransformResult::S(rsnode, reduce_op(&lr, &rr)),
-                SNodeTransformResult::Removed(rr) => MNodeJointTransformResult::Removed(reduce_op(&lr, &rr)),
+                SNodeTransformResult::Unchanged(rr) => MNodeJointTransformResult::UnchangedR(reduce_op.reduce(lr, rr)),
+                SNodeTransformResult::S(rsnode, rr) => MNodeJointTransformResult::S(rsnode, reduce_op.reduce(lr, rr)),
+                SNodeTransformResult::Removed(rr) => MNodeJointTransformResult::Removed(reduce_op.reduce(lr, rr)),
             },
         }
     }
 }
 
-pub(crate) fn transform_with_transformed_snode_impl<K: Key, V: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<SNode<K, V>>, right: &Arc<SNode<K, V>>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>) -> LNodeJointTransformResult<K, V, ReduceT>
+pub(crate) fn transform_with_transformed_snode_impl<H: Hashword, K: Key, V: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<SNode<H, K, V>>, right: &Arc<SNode<H, K, V>>, reduce_op: ReduceOp, both_op: MapJointTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>) -> LNodeJointTransformResult<H, K, V, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT> + Clone + Send + Sync,
     LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
@@ -206,7 +267,7 @@ where
             MapJointTransformResult::UnchangedLR(reduced) => LNodeJointTransformResult::UnchangedLR(reduced),
             MapJointTransformResult::UnchangedL(reduced) => LNodeJointTransformResult::UnchangedL(reduced),
             MapJointTransformResult::UnchangedR(reduced) => LNodeJointTransformResult::UnchangedR(reduced),
-            MapJointTransformResult::Transformed(value, reduced) => LNodeJointTransformResult::S(SNode::new(this.key().clone(), value), reduced),
+            MapJointTransformResult::Transformed(value, reduced) => LNodeJointTransformResult::S(SNode::new(this.key().clone(), value, this.leaf_hash()), reduced),
             MapJointTransformResult::Removed(reduced) => LNodeJointTransformResult::Removed(reduced),
         }
     }
@@ -216,28 +277,28 @@ where
 
         match left {
             MapTransformResult::Unchanged(lr) => match r {
-                MapTransformResult::Unchanged(rr) => LNodeJointTransformResult::L(LNode::new(right.key.clone(), right.value.clone(), LNodeNext::S(this.clone())), reduce_op(&lr, &rr)),
-                MapTransformResult::Transformed(rv, rr) => LNodeJointTransformResult::L(LNode::new(right.key().clone(), rv, LNodeNext::S(this.clone())), reduce_op(&lr, &rr)),
-                MapTransformResult::Removed(rr) => LNodeJointTransformResult::UnchangedL(reduce_op(&lr, &rr)),
+                MapTransformResult::Unchanged(rr) => LNodeJointTransformResult::L(LNode::new(right.key.clone(), right.value.clone(), LNodeNext::S(this.clone())), reduce_op.reduce(lr, rr)),
+                MapTransformResult::Transformed(rv, rr) => LNodeJointTransformResult::L(LNode::new(right.key().clone(), rv, LNodeNext::S(this.clone())), reduce_op.reduce(lr, rr)),
+                MapTransformResult::Removed(rr) => LNodeJointTransformResult::UnchangedL(reduce_op.reduce(lr, rr)),
             },
             MapTransformResult::Transformed(lv, lr) => match r {
-                MapTransformResult::Unchanged(rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), lv, LNodeNext::S(right.clone())), reduce_op(&lr, &rr)),
-                MapTransformResult::Transformed(rv, rr) => LNodeJointTransformResult::L(LNode::new(right.key.clone(), rv, LNodeNext::S(SNode::new(this.key().clone(), lv))), reduce_op(&lr, &rr)),
-                MapTransformResult::Removed(rr) => LNodeJointTransformResult::S(SNode::new(this.key().clone(), lv), reduce_op(&lr, &rr)),
+                MapTransformResult::Unchanged(rr) => LNodeJointTransformResult::L(LNode::new(this.key.clone(), lv, LNodeNext::S(right.clone())), reduce_op.reduce(lr, rr)),
+                MapTransformResult::Transformed(rv, rr) => LNodeJointTransformResult::L(LNode::new(right.key.clone(), rv, LNodeNext::S(SNode::new(this.key().clone(), lv, this.leaf_hash()))), reduce_op.reduce(lr, rr)),
+                MapTransformResult::Removed(rr) => LNodeJointTransformResult::S(SNode::new(this.key().clone(), lv, this.leaf_hash()), reduce_op.reduce(lr, rr)),
             },
             MapTransformResult::Removed(lr) => match r {
-                MapTransformResult::Unchanged(rr) => LNodeJointTransformResult::UnchangedR(reduce_op(&lr, &rr)),
-                MapTransformResult::Transformed(rv, rr) => LNodeJointTransformResult::S(SNode::new(right.key.clone(), rv), reduce_op(&lr, &rr)),
-                MapTransformResult::Removed(rr) => LNodeJointTransformResult::Removed(reduce_op(&lr, &rr)),
+                MapTransformResult::Unchanged(rr) => LNodeJointTransformResult::UnchangedR(reduce_op.reduce(lr, rr)),
+                MapTransformResult::Transformed(rv, rr) => LNodeJointTransformResult::S(SNode::new(right.key.clone(), rv, this.leaf_hash()), reduce_op.reduce(lr, rr)),
+                MapTransformResult::Removed(rr) => LNodeJointTransformResult::Removed(reduce_op.reduce(lr, rr)),
             },
         }
     }
 }
 
-pub(crate) unsafe fn transform_with_transmuted_snode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<SNode<K, V>>, right: &Arc<SNode<L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransformResult<H, F, K, V, M, ReduceT>
+pub(crate) unsafe fn transform_with_transmuted_snode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, A: Measure<K, V> + Measure<L, W>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<SNode<H, K, V>>, right: &Arc<SNode<H, L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransformResult<H, F, K, V, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<V, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&L, &W) -> MapTransmuteResult<K, V, ReduceT> + Clone + Send + Sync,
@@ -248,8 +309,8 @@ where
     M: HasherBv<H, L>,
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
-    let this_hash = M::default().hash(this.key());
-    let right_hash = M::default().hash(right.key());
+    let this_hash = this.hash_value::<M>();
+    let right_hash = right.hash_value::<M>();
 
     if this_hash == right_hash {
         transform_with_transmuted_snode_impl(this, right.key(), right.value(), reduce_op, both_op, left_op, right_op).into()
@@ -263,25 +324,25 @@ where
 
         match left {
             SNodeTransformResult::Unchanged(lr) => match right {
-                SNodeTransmuteResult::S(rsnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(MNode::S(this.clone()), left_flag, rsnode.into(), right_flag), reduce_op(&lr, &rr)),
-                SNodeTransmuteResult::Removed(rr) => MNodeTransformResult::Unchanged(reduce_op(&lr, &rr)),
+                SNodeTransmuteResult::S(rsnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(MNode::S(this.clone()), left_flag, rsnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                SNodeTransmuteResult::Removed(rr) => MNodeTransformResult::Unchanged(reduce_op.reduce(lr, rr)),
             },
             SNodeTransformResult::S(lsnode, lr) => match right {
-                SNodeTransmuteResult::S(rsnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), left_flag, rsnode.into(), right_flag), reduce_op(&lr, &rr)),
-                SNodeTransmuteResult::Removed(rr) => MNodeTransformResult::S(lsnode, reduce_op(&lr, &rr)),
+                SNodeTransmuteResult::S(rsnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), left_flag, rsnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                SNodeTransmuteResult::Removed(rr) => MNodeTransformResult::S(lsnode, reduce_op.reduce(lr, rr)),
             },
             SNodeTransformResult::Removed(lr) => match right {
-                SNodeTransmuteResult::S(rsnode, rr) => MNodeTransformResult::S(rsnode, reduce_op(&lr, &rr)),
-                SNodeTransmuteResult::Removed(rr) => MNodeTransformResult::Removed(reduce_op(&lr, &rr)),
+                SNodeTransmuteResult::S(rsnode, rr) => MNodeTransformResult::S(rsnode, reduce_op.reduce(lr, rr)),
+                SNodeTransmuteResult::Removed(rr) => MNodeTransformResult::Removed(reduce_op.reduce(lr, rr)),
             },
         }
     }
 }
 
-pub(crate) unsafe fn transform_with_transmuted_snode_impl<K: Key, V: Value, L: Key, W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<SNode<K, V>>, right_key: &L, right_value: &W, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>) -> LNodeTransformResult<K, V, ReduceT>
+pub(crate) unsafe fn transform_with_transmuted_snode_impl<H: Hashword, K: Key, V: Value, L: Key, W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<SNode<H, K, V>>, right_key: &L, right_value: &W, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransform<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>) -> LNodeTransformResult<H, K, V, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<V, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&L, &W) -> MapTransmuteResult<K, V, ReduceT> + Clone + Send + Sync,
@@ -291,9 +352,9 @@ where
     L: PartialEq<K>,
 {
     if *this.key() == *right_key {
-        match both_op.call_transform_transmute(this.key(), this.value(), right_key, right_value) {
+        match both_op.call_joint(this.key(), this.value(), right_key, right_value) {
             MapTransformResult::Unchanged(reduced) => LNodeTransformResult::Unchanged(reduced),
-            MapTransformResult::Transformed(value, reduced) => LNodeTransformResult::S(SNode::new(this.key().clone(), value), reduced),
+            MapTransformResult::Transformed(value, reduced) => LNodeTransformResult::S(SNode::new(this.key().clone(), value, this.leaf_hash()), reduced),
             MapTransformResult::Removed(reduced) => LNodeTransformResult::Removed(reduced),
         }
     }
@@ -303,25 +364,25 @@ where
 
         match left {
             MapTransformResult::Unchanged(lr) => match right {
-                MapTransmuteResult::Transmuted(rk, rv, rr) => LNodeTransformResult::L(LNode::new(rk, rv, LNodeNext::S(this.clone())), reduce_op(&lr, &rr)),
-                MapTransmuteResult::Removed(rr) => LNodeTransformResult::Unchanged(reduce_op(&lr, &rr)),
+                MapTransmuteResult::Transmuted(rk, rv, rr) => LNodeTransformResult::L(LNode::new(rk, rv, LNodeNext::S(this.clone())), reduce_op.reduce(lr, rr)),
+                MapTransmuteResult::Removed(rr) => LNodeTransformResult::Unchanged(reduce_op.reduce(lr, rr)),
             },
             MapTransformResult::Transformed(lv, lr) => match right {
-                MapTransmuteResult::Transmuted(rk, rv, rr) => LNodeTransformResult::L(LNode::new(rk, rv, LNodeNext::S(SNode::new(this.key().clone(), lv))), reduce_op(&lr, &rr)),
-                MapTransmuteResult::Removed(rr) => LNodeTransformResult::S(SNode::new(this.key().clone(), lv), reduce_op(&lr, &rr)),
+                MapTransmuteResult::Transmuted(rk, rv, rr) => LNodeTransformResult::L(LNode::new(rk, rv, LNodeNext::S(SNode::new(this.key().clone(), lv, this.leaf_hash()))), reduce_op.reduce(lr, rr)),
+                MapTransmuteResult::Removed(rr) => LNodeTransformResult::S(SNode::new(this.key().clone(), lv, this.leaf_hash()), reduce_op.reduce(lr, rr)),
             },
             MapTransformResult::Removed(lr) => match right {
-                MapTransmuteResult::Transmuted(rk, rv, rr) => LNodeTransformResult::S(SNode::new(rk, rv), reduce_op(&lr, &rr)),
-                MapTransmuteResult::Removed(rr) => LNodeTransformResult::Removed(reduce_op(&lr, &rr)),
+                MapTransmuteResult::Transmuted(rk, rv, rr) => LNodeTransformResult::S(SNode::new(rk, rv, this.leaf_hash()), reduce_op.reduce(lr, rr)),
+                MapTransmuteResult::Removed(rr) => LNodeTransformResult::Removed(reduce_op.reduce(lr, rr)),
             },
         }
     }
 }
 
-pub(crate) unsafe fn transmute_with_transformed_snode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<SNode<K, V>>, right: &Arc<SNode<L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize) -> MNodeTransformResult<H, F, L, W, M, ReduceT>
+pub(crate) unsafe fn transmute_with_transformed_snode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, M: HasherBv<H, K>, A: Measure<K, V> + Measure<L, W>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<SNode<H, K, V>>, right: &Arc<SNode<H, L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>, depth: usize) -> MNodeTransformResult<H, F, L, W, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<W, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransmuteResult<L, W, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&L, &W) -> MapTransformResult<W, ReduceT> + Clone + Send + Sync,
@@ -332,8 +393,8 @@ where
     M: HasherBv<H, L>,
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
-    let this_hash = M::default().hash(this.key());
-    let right_hash = M::default().hash(right.key());
+    let this_hash = this.hash_value::<M>();
+    let right_hash = right.hash_value::<M>();
 
     if this_hash == right_hash {
         transmute_with_transformed_snode_impl(this, right, reduce_op, both_op, left_op, right_op).into()
@@ -347,23 +408,23 @@ where
 
         match left {
             SNodeTransmuteResult::S(lsnode, lr) => match r {
-                SNodeTransformResult::Unchanged(rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), left_flag, right.clone().into(), right_flag), reduce_op(&lr, &rr)),
-                SNodeTransformResult::S(rsnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), left_flag, rsnode.into(), right_flag), reduce_op(&lr, &rr)),
-                SNodeTransformResult::Removed(rr) => MNodeTransformResult::S(lsnode, reduce_op(&lr, &rr)),
+                SNodeTransformResult::Unchanged(rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), left_flag, right.clone().into(), right_flag), reduce_op.reduce(lr, rr)),
+                SNodeTransformResult::S(rsnode, rr) => MNodeTransformResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), left_flag, rsnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                SNodeTransformResult::Removed(rr) => MNodeTransformResult::S(lsnode, reduce_op.reduce(lr, rr)),
             },
             SNodeTransmuteResult::Removed(lr) => match r {
-                SNodeTransformResult::Unchanged(rr) => MNodeTransformResult::Unchanged(reduce_op(&lr, &rr)),
-                SNodeTransformResult::S(rsnode, rr) => MNodeTransformResult::S(rsnode, reduce_op(&lr, &rr)),
-                SNodeTransformResult::Removed(rr) => MNodeTransformResult::Removed(reduce_op(&lr, &rr)),
+                SNodeTransformResult::Unchanged(rr) => MNodeTransformResult::Unchanged(reduce_op.reduce(lr, rr)),
+                SNodeTransformResult::S(rsnode, rr) => MNodeTransformResult::S(rsnode, reduce_op.reduce(lr, rr)),
+                SNodeTransformResult::Removed(rr) => MNodeTransformResult::Removed(reduce_op.reduce(lr, rr)),
             },
         }
     }
 }
 
-pub(crate) unsafe fn transmute_with_transformed_snode_impl<K: Key, V: Value, L: Key, W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<SNode<K, V>>, right: &Arc<SNode<L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>) -> LNodeTransformResult<L, W, ReduceT>
+pub(crate) unsafe fn transmute_with_transformed_snode_impl<H: Hashword, K: Key, V: Value, L: Key, W: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<SNode<H, K, V>>, right: &Arc<SNode<H, L, W>>, reduce_op: ReduceOp, both_op: MapTransform<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransform<ReduceT, RightOp>) -> LNodeTransformResult<H, L, W, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransformResult<W, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransmuteResult<L, W, ReduceT> + Clone + Send + Sync,
     RightOp: Fn(&L, &W) -> MapTransformResult<W, ReduceT> + Clone + Send + Sync,
@@ -373,9 +434,9 @@ where
     L: PartialEq<K>,
 {
     if *this.key() == *right.key() {
-        match both_op.call_transmute_transform(this.key(), this.value(), right.key(), right.value()) {
+        match both_op.call_joint(this.key(), this.value(), right.key(), right.value()) {
             MapTransformResult::Unchanged(reduced) => LNodeTransformResult::Unchanged(reduced),
-            MapTransformResult::Transformed(value, reduced) => LNodeTransformResult::S(SNode::new(right.key().clone(), value), reduced),
+            MapTransformResult::Transformed(value, reduced) => LNodeTransformResult::S(SNode::new(right.key().clone(), value, this.leaf_hash()), reduced),
             MapTransformResult::Removed(reduced) => LNodeTransformResult::Removed(reduced),
         }
     }
@@ -385,23 +446,23 @@ where
 
         match left {
             MapTransmuteResult::Transmuted(lk, lv, lr) => match r {
-                MapTransformResult::Unchanged(rr) => LNodeTransformResult::L(LNode::new(lk, lv, LNodeNext::S(right.clone())), reduce_op(&lr, &rr)),
-                MapTransformResult::Transformed(rv, rr) => LNodeTransformResult::L(LNode::new(lk, lv, LNodeNext::S(SNode::new(right.key().clone(), rv))), reduce_op(&lr, &rr)),
-                MapTransformResult::Removed(rr) => LNodeTransformResult::S(SNode::new(lk, lv), reduce_op(&lr, &rr)),
+                MapTransformResult::Unchanged(rr) => LNodeTransformResult::L(LNode::new(lk, lv, LNodeNext::S(right.clone())), reduce_op.reduce(lr, rr)),
+                MapTransformResult::Transformed(rv, rr) => LNodeTransformResult::L(LNode::new(lk, lv, LNodeNext::S(SNode::new(right.key().clone(), rv, this.leaf_hash()))), reduce_op.reduce(lr, rr)),
+                MapTransformResult::Removed(rr) => LNodeTransformResult::S(SNode::new(lk, lv, this.leaf_hash()), reduce_op.reduce(lr, rr)),
             },
             MapTransmuteResult::Removed(lr) => match r {
-                MapTransformResult::Unchanged(rr) => LNodeTransformResult::Unchanged(reduce_op(&lr, &rr)),
-                MapTransformResult::Transformed(rv, rr) => LNodeTransformResult::S(SNode::new(right.key().clone(), rv), reduce_op(&lr, &rr)),
-                MapTransformResult::Removed(rr) => LNodeTransformResult::Removed(reduce_op(&lr, &rr)),
+                MapTransformResult::Unchanged(rr) => LNodeTransformResult::Unchanged(reduce_op.reduce(lr, rr)),
+                MapTransformResult::Transformed(rv, rr) => LNodeTransformResult::S(SNode::new(right.key().clone(), rv, this.leaf_hash()), reduce_op.reduce(lr, rr)),
+                MapTransformResult::Removed(rr) => LNodeTransformResult::Removed(reduce_op.reduce(lr, rr)),
             },
         }
     }
 }
 
-pub(crate) unsafe fn transmute_with_transmuted<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, S: Key, W: Value, X: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<SNode<K, V>>, right: &MNode<H, F, L, W, M>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransmuteResult<H, F, S, X, M, ReduceT>
+pub(crate) unsafe fn transmute_with_transmuted<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, S: Key, W: Value, X: Value, M: HasherBv<H, K>, A: Measure<K, V> + Measure<L, W> + Measure<S, X>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<SNode<H, K, V>>, right: &MNode<H, F, L, W, M, A>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransmuteResult<H, F, S, X, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT> + Clone,
     RightOp: Fn(&L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
@@ -424,10 +485,10 @@ where
     }
 }
 
-pub(crate) unsafe fn transmute_with_transmuted_snode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, S: Key, X: Value, M: HasherBv<H, K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<SNode<K, V>>, right: &Arc<SNode<L, W>>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransmuteResult<H, F, S, X, M, ReduceT>
+pub(crate) unsafe fn transmute_with_transmuted_snode<H: Hashword, F: Flagword<H>, K: Key, V: Value, L: Key, W: Value, S: Key, X: Value, M: HasherBv<H, K>, A: Measure<K, V> + Measure<L, W> + Measure<S, X>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this: &Arc<SNode<H, K, V>>, right: &Arc<SNode<H, L, W>>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>, depth: usize) -> MNodeTransmuteResult<H, F, S, X, M, A, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT> + Clone,
     RightOp: Fn(&L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
@@ -443,11 +504,11 @@ where
     M: HasherBv<H, S>,
     <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug
 {
-    let this_hash = M::default().hash(this.key());
-    let right_hash = M::default().hash(right.key());
+    let this_hash = this.hash_value::<M>();
+    let right_hash = right.hash_value::<M>();
 
     if this_hash == right_hash {
-        transmute_with_transmuted_values(&this.key, &this.value, &right.key, &right.value, reduce_op, both_op, left_op, right_op).into()
+        transmute_with_transmuted_values(&this.key, &this.value, &right.key, &right.value, this.leaf_hash(), reduce_op, both_op, left_op, right_op).into()
     }
     else {
         let this = transmute(this, left_op);
@@ -458,21 +519,21 @@ where
 
         match this {
             SNodeTransmuteResult::S(lsnode, lr) => match right {
-                SNodeTransmuteResult::S(rsnode, rr) => MNodeTransmuteResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, rsnode.into(), right_flag), reduce_op(&lr, &rr)),
-                SNodeTransmuteResult::Removed(rr) => MNodeTransmuteResult::S(lsnode, reduce_op(&lr, &rr)),
+                SNodeTransmuteResult::S(rsnode, rr) => MNodeTransmuteResult::C(cnode::lift_to_cnode_and_insert(lsnode.into(), this_flag, rsnode.into(), right_flag), reduce_op.reduce(lr, rr)),
+                SNodeTransmuteResult::Removed(rr) => MNodeTransmuteResult::S(lsnode, reduce_op.reduce(lr, rr)),
             },
             SNodeTransmuteResult::Removed(lr) => match right {
-                SNodeTransmuteResult::S(rsnode, rr) => MNodeTransmuteResult::S(rsnode, reduce_op(&lr, &rr)),
-                SNodeTransmuteResult::Removed(rr) => MNodeTransmuteResult::Removed(reduce_op(&lr, &rr)),
+                SNodeTransmuteResult::S(rsnode, rr) => MNodeTransmuteResult::S(rsnode, reduce_op.reduce(lr, rr)),
+                SNodeTransmuteResult::Removed(rr) => MNodeTransmuteResult::Removed(reduce_op.reduce(lr, rr)),
             },
         }
     }
 }
 
-pub(crate) unsafe fn transmute_with_transmuted_values<K: Key, V: Value, L: Key, W: Value, S: Key, X: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this_key: &K, this_value: &V, right_key: &L, right_value: &W, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>) -> LNodeTransmuteResult<S, X, ReduceT>
+pub(crate) unsafe fn transmute_with_transmuted_values<H: Hashword, K: Key, V: Value, L: Key, W: Value, S: Key, X: Value, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>(this_key: &K, this_value: &V, right_key: &L, right_value: &W, hash: LeafHash<H>, reduce_op: ReduceOp, both_op: MapTransmute<ReduceT, BothOp>, left_op: MapTransmute<ReduceT, LeftOp>, right_op: MapTransmute<ReduceT, RightOp>) -> LNodeTransmuteResult<H, S, X, ReduceT>
 where
-    ReduceT: Clone + Default + Send + Sync,
-    ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
+    ReduceT: Default + Send + Sync,
+    ReduceOp: Reducer<ReduceT>,
     BothOp: Fn(&K, &V, &L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
     LeftOp: Fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT> + Clone,
     RightOp: Fn(&L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone,
@@ -486,7 +547,7 @@ where
     L: PartialEq<S>,
 {
     if this_key == right_key {
-        both_op.call_transmute(this_key, this_value, right_key, right_value).into()
+        (both_op.call_transmute(this_key, this_value, right_key, right_value), hash).into()
     }
     else {
         let lr = left_op.call(this_key, this_value);
@@ -494,19 +555,25 @@ where
 
         match lr {
             MapTransmuteResult::Transmuted(lk, lv, lr) => match rr {
-                MapTransmuteResult::Transmuted(rk, rv, rr) => LNodeTransmuteResult::L(LNode::new(lk, lv, LNodeNext::S(SNode::new(rk, rv))), reduce_op(&lr, &rr)),
-                MapTransmuteResult::Removed(rr) => LNodeTransmuteResult::S(SNode::new(lk, lv), reduce_op(&lr, &rr)),
+                MapTransmuteResult::Transmuted(rk, rv, rr) => LNodeTransmuteResult::L(LNode::new(lk, lv, LNodeNext::S(SNode::new(rk, rv, hash.clone()))), reduce_op.reduce(lr, rr)),
+                MapTransmuteResult::Removed(rr) => LNodeTransmuteResult::S(SNode::new(lk, lv, hash.clone()), reduce_op.reduce(lr, rr)),
             },
             MapTransmuteResult::Removed(lr) => match rr {
-                MapTransmuteResult::Transmuted(rk, rv, rr) => LNodeTransmuteResult::S(SNode::new(rk, rv), reduce_op(&lr, &rr)),
-                MapTransmuteResult::Removed(rr) => LNodeTransmuteResult::Removed(reduce_op(&lr, &rr)),
+                MapTransmuteResult::Transmuted(rk, rv, rr) => LNodeTransmuteResult::S(SNode::new(rk, rv, hash.clone()), reduce_op.reduce(lr, rr)),
+                MapTransmuteResult::Removed(rr) => LNodeTransmuteResult::Removed(reduce_op.reduce(lr, rr)),
             },
         }
     }
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>> From<Arc<SNode<K, V>>> for MNode<H, F, K, V, M> {
-    fn from(other: Arc<SNode<K, V>>) -> Self {
+impl <H: Hashword, K: Key, V: Value> Debug for SNode<H, K, V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SNode").field("key", &self.key).field("value", &self.value).finish()
+    }
+}
+
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> From<Arc<SNode<H, K, V>>> for MNode<H, F, K, V, M, A> {
+    fn from(other: Arc<SNode<H, K, V>>) -> Self {
         MNode::S(other)
     }
 }
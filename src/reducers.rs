@@ -0,0 +1,137 @@
+use crate::traits::*;
+use alloc::{fmt::Debug, vec::Vec};
+use core::ops::Add;
+
+/// `Count` counts the transform operations that returned `Count(1)`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Count(pub usize);
+
+impl Monoid for Count {
+    fn empty() -> Self {
+        Count(0)
+    }
+
+    fn combine(self, other: Self) -> Self {
+        Count(self.0 + other.0)
+    }
+}
+
+/// `Sum` adds up the values returned by transform operations.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Sum<T>(pub T);
+
+impl <T: Add<Output = T> + Clone + Debug + Default + Send + Sync + 'static> Monoid for Sum<T> {
+    fn empty() -> Self {
+        Sum(T::default())
+    }
+
+    fn combine(self, other: Self) -> Self {
+        Sum(self.0 + other.0)
+    }
+}
+
+/// `Min` keeps the least value returned by transform operations, if any.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Min<T>(pub Option<T>);
+
+impl <T> Default for Min<T> {
+    fn default() -> Self {
+        Min(None)
+    }
+}
+
+impl <T: Ord + Clone + Debug + Send + Sync + 'static> Monoid for Min<T> {
+    fn empty() -> Self {
+        Min(None)
+    }
+
+    fn combine(self, other: Self) -> Self {
+        match (self.0, other.0) {
+            (Some(left), Some(right)) => Min(Some(left.min(right))),
+            (left, right) => Min(left.or(right)),
+        }
+    }
+}
+
+/// `Max` keeps the greatest value returned by transform operations, if any.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Max<T>(pub Option<T>);
+
+impl <T> Default for Max<T> {
+    fn default() -> Self {
+        Max(None)
+    }
+}
+
+impl <T: Ord + Clone + Debug + Send + Sync + 'static> Monoid for Max<T> {
+    fn empty() -> Self {
+        Max(None)
+    }
+
+    fn combine(self, other: Self) -> Self {
+        match (self.0, other.0) {
+            (Some(left), Some(right)) => Max(Some(left.max(right))),
+            (left, right) => Max(left.or(right)),
+        }
+    }
+}
+
+/// `Collect` gathers the values returned by transform operations into a `Vec`, in no particular order.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Collect<T>(pub Vec<T>);
+
+impl <T> Default for Collect<T> {
+    fn default() -> Self {
+        Collect(Vec::new())
+    }
+}
+
+impl <T: Clone + Debug + Send + Sync + 'static> Monoid for Collect<T> {
+    fn empty() -> Self {
+        Collect(Vec::new())
+    }
+
+    fn combine(mut self, mut other: Self) -> Self {
+        if self.0.len() < other.0.len() {
+            core::mem::swap(&mut self, &mut other);
+        }
+        self.0.append(&mut other.0);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{*, reducers::*, results::*, transformations::*};
+    use futures_executor::block_on;
+
+    #[test]
+    fn reducers_transform_monoid() {
+        let mut map = DefaultHashTrieMap::<i32, i32>::new();
+        for i in 1..101 {
+            map = map.insert(i, i, false).unwrap().0;
+        }
+
+        let (same, Count(count)) = block_on(map.transform_monoid(new_map_transform_generic(|_, _| MapTransformResult::Unchanged(Count(1))), ParallelismStrategy::default_par()));
+        assert_eq!(count, 100);
+        assert_eq!(same.size(), 100);
+
+        let (_, Sum(sum)) = block_on(map.transform_monoid(new_map_transform_generic(|_, v| MapTransformResult::Unchanged(Sum(*v))), ParallelismStrategy::default_par()));
+        assert_eq!(sum, 5050);
+
+        let (_, (Min(min), Max(max))) = block_on(map.transform_monoid(new_map_transform_generic(|_, v| MapTransformResult::Unchanged((Min(Some(*v)), Max(Some(*v))))), ParallelismStrategy::Sequential));
+        assert_eq!(min, Some(1));
+        assert_eq!(max, Some(100));
+
+        let (evens, Collect(mut removed)) = block_on(map.transform_monoid(new_map_transform_generic(|k, _| match k % 2 {
+            0 => MapTransformResult::Unchanged(Collect(vec!())),
+            _ => MapTransformResult::Removed(Collect(vec!(*k))),
+        }), ParallelismStrategy::default_par()));
+        removed.sort_unstable();
+        assert_eq!(evens.size(), 50);
+        assert_eq!(removed, (1..101).step_by(2).collect::<Vec<i32>>());
+
+        let (unchanged, ()) = block_on(map.transform_monoid(new_map_transform_unchanged(()), ParallelismStrategy::default_par()));
+        assert_eq!(unchanged.size(), 100);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{node::{CNode, LNode, SNode}, traits::*, *};
+use crate::{node::{CNode, LNode, LeafHash, SNode}, traits::*, *};
 use alloc::sync::Arc;
 
 /// `BitError` enumerates possible error conditions when bitops are used "incorrectly."
@@ -22,6 +22,60 @@ pub enum HashTrieError {
     NotFound,
 }
 
+/// `MapDiff` describes one difference found going from a first map to a second.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MapDiff<'a, K, V> {
+    /// The key is present only in the second map.
+    Added(&'a K, &'a V),
+    /// The key is present only in the first map.
+    Removed(&'a K, &'a V),
+    /// The key is present in both maps with different values, first then second.
+    Changed(&'a K, &'a V, &'a V),
+}
+
+/// `MapChange` is an owned `MapDiff`, describing one difference going from a first map to a second.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MapChange<K, V> {
+    /// The key is present only in the second map.
+    Added(K, V),
+    /// The key is present only in the first map.
+    Removed(K, V),
+    /// The key is present in both maps with different values, first then second.
+    Changed(K, V, V),
+}
+
+impl <'a, K: Clone, V: Clone> From<MapDiff<'a, K, V>> for MapChange<K, V> {
+    fn from(other: MapDiff<'a, K, V>) -> Self {
+        match other {
+            MapDiff::Added(key, value) => MapChange::Added(key.clone(), value.clone()),
+            MapDiff::Removed(key, value) => MapChange::Removed(key.clone(), value.clone()),
+            MapDiff::Changed(key, left, right) => MapChange::Changed(key.clone(), left.clone(), right.clone()),
+        }
+    }
+}
+
+/// `MemoryUsage` accounts for the distinct nodes reachable from one or more maps, counting shared nodes once.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct MemoryUsage {
+    /// The number of distinct node allocations.
+    pub nodes: usize,
+    /// The approximate number of bytes in those allocations, excluding any heap memory owned by keys and values.
+    pub bytes: usize,
+}
+
+/// `Resolution` settles a key changed differently in both sides of a three-way merge.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Resolution<V> {
+    /// Keep the left entry, or its absence.
+    Left,
+    /// Keep the right entry, or its absence.
+    Right,
+    /// Use the given value.
+    Value(V),
+    /// Remove the key.
+    Removed,
+}
+
 #[must_use]
 pub(crate) enum FindResult<'a, K: Key, V: Value> {
     NotFound,
@@ -29,21 +83,21 @@ pub(crate) enum FindResult<'a, K: Key, V: Value> {
 }
 
 #[must_use]
-pub(crate) enum InsertResult<'a, H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static> {
+pub(crate) enum InsertResult<'a, H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>> {
     Found(&'a K, &'a V),
-    InsertedC(CNode<H, F, K, V, M>, *const K, *const V, Option<(&'a K, &'a V)>),
-    InsertedL(Arc<LNode<K, V>>, *const K, *const V, Option<(&'a K, &'a V)>),
-    InsertedS(Arc<SNode<K, V>>, *const K, *const V, Option<(&'a K, &'a V)>),
+    InsertedC(CNode<H, F, K, V, M, A>, *const K, *const V, Option<(&'a K, &'a V)>),
+    InsertedL(Arc<LNode<H, K, V>>, *const K, *const V, Option<(&'a K, &'a V)>),
+    InsertedS(Arc<SNode<H, K, V>>, *const K, *const V, Option<(&'a K, &'a V)>),
 }
 
 #[must_use]
-pub(crate) enum CNodeInsertResult<'a, H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static> {
+pub(crate) enum CNodeInsertResult<'a, H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>> {
     Found(&'a K, &'a V),
-    InsertedC(CNode<H, F, K, V, M>, *const K, *const V, Option<(&'a K, &'a V)>),
+    InsertedC(CNode<H, F, K, V, M, A>, *const K, *const V, Option<(&'a K, &'a V)>),
 }
 
-impl <'a, H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static> From<CNodeInsertResult<'a, H, F, K, V, M>> for InsertResult<'a, H, F, K, V, M> {
-    fn from(other: CNodeInsertResult<'a, H, F, K, V, M>) -> Self {
+impl <'a, H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>> From<CNodeInsertResult<'a, H, F, K, V, M, A>> for InsertResult<'a, H, F, K, V, M, A> {
+    fn from(other: CNodeInsertResult<'a, H, F, K, V, M, A>) -> Self {
         match other {
             CNodeInsertResult::Found(key, value) => InsertResult::Found(key, value),
             CNodeInsertResult::InsertedC(lnode, key, value, prev) => InsertResult::InsertedC(lnode, key, value, prev),
@@ -52,14 +106,14 @@ impl <'a, H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static> From<CNodeI
 }
 
 #[must_use]
-pub(crate) enum LNodeInsertResult<'a, H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static> {
+pub(crate) enum LNodeInsertResult<'a, H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>> {
     Found(&'a K, &'a V),
-    InsertedC(CNode<H, F, K, V, M>, *const K, *const V, Option<(&'a K, &'a V)>),
-    InsertedL(Arc<LNode<K, V>>, *const K, *const V, Option<(&'a K, &'a V)>),
+    InsertedC(CNode<H, F, K, V, M, A>, *const K, *const V, Option<(&'a K, &'a V)>),
+    InsertedL(Arc<LNode<H, K, V>>, *const K, *const V, Option<(&'a K, &'a V)>),
 }
 
-impl <'a, H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static> From<LNodeInsertResult<'a, H, F, K, V, M>> for InsertResult<'a, H, F, K, V, M> {
-    fn from(other: LNodeInsertResult<'a, H, F, K, V, M>) -> Self {
+impl <'a, H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>> From<LNodeInsertResult<'a, H, F, K, V, M, A>> for InsertResult<'a, H, F, K, V, M, A> {
+    fn from(other: LNodeInsertResult<'a, H, F, K, V, M, A>) -> Self {
         match other {
             LNodeInsertResult::Found(key, value) => InsertResult::Found(key, value),
             LNodeInsertResult::InsertedC(lnode, key, value, prev) => InsertResult::InsertedC(lnode, key, value, prev),
@@ -69,23 +123,23 @@ impl <'a, H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static> From<LNodeI
 }
 
 #[must_use]
-pub(crate) enum RemoveResult<'a, H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static> {
+pub(crate) enum RemoveResult<'a, H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>> {
     NotFound,
-    RemovedC(CNode<H, F, K, V, M>, &'a K, &'a V),
-    RemovedL(Arc<LNode<K, V>>, &'a K, &'a V),
-    RemovedS(Arc<SNode<K, V>>, &'a K, &'a V),
+    RemovedC(CNode<H, F, K, V, M, A>, &'a K, &'a V),
+    RemovedL(Arc<LNode<H, K, V>>, &'a K, &'a V),
+    RemovedS(Arc<SNode<H, K, V>>, &'a K, &'a V),
     RemovedZ(&'a K, &'a V),
 }
 
 #[must_use]
-pub(crate) enum LNodeRemoveResult<'a, K: Key, V: Value> {
+pub(crate) enum LNodeRemoveResult<'a, H: Hashword, K: Key, V: Value> {
     NotFound,
-    RemovedL(Arc<LNode<K, V>>, &'a K, &'a V),
-    RemovedS(Arc<SNode<K, V>>, &'a K, &'a V),
+    RemovedL(Arc<LNode<H, K, V>>, &'a K, &'a V),
+    RemovedS(Arc<SNode<H, K, V>>, &'a K, &'a V),
 }
 
-impl <'a, H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static> From<LNodeRemoveResult<'a, K, V>> for RemoveResult<'a, H, F, K, V, M> {
-    fn from(other: LNodeRemoveResult<'a, K, V>) -> Self {
+impl <'a, H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>> From<LNodeRemoveResult<'a, H, K, V>> for RemoveResult<'a, H, F, K, V, M, A> {
+    fn from(other: LNodeRemoveResult<'a, H, K, V>) -> Self {
         match other {
             LNodeRemoveResult::NotFound => RemoveResult::NotFound,
             LNodeRemoveResult::RemovedL(lnode, key, value) => RemoveResult::RemovedL(lnode, key, value),
@@ -100,7 +154,7 @@ pub(crate) enum SNodeRemoveResult<'a, K: Key, V: Value> {
     RemovedZ(&'a K, &'a V),
 }
 
-impl <'a, H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static> From<SNodeRemoveResult<'a, K, V>> for RemoveResult<'a, H, F, K, V, M> {
+impl <'a, H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>> From<SNodeRemoveResult<'a, K, V>> for RemoveResult<'a, H, F, K, V, M, A> {
     fn from(other: SNodeRemoveResult<'a, K, V>) -> Self {
         match other {
             SNodeRemoveResult::NotFound => RemoveResult::NotFound,
@@ -219,26 +273,26 @@ pub enum MapTransmuteResult<K, V, ReduceT> {
 }
 
 #[must_use]
-pub(crate) enum MNodeTransformResult<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, ReduceT> {
+pub(crate) enum MNodeTransformResult<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>, ReduceT> {
     Unchanged(ReduceT),
-    C(CNode<H, F, K, V, M>, ReduceT),
-    L(Arc<LNode<K, V>>, ReduceT),
-    S(Arc<SNode<K, V>>, ReduceT),
+    C(CNode<H, F, K, V, M, A>, ReduceT),
+    L(Arc<LNode<H, K, V>>, ReduceT),
+    S(Arc<SNode<H, K, V>>, ReduceT),
     Removed(ReduceT),
 }
 
 #[must_use]
-pub(crate) enum MNodeJointTransformResult<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, ReduceT> {
+pub(crate) enum MNodeJointTransformResult<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>, ReduceT> {
     UnchangedLR(ReduceT),
     UnchangedL(ReduceT),
     UnchangedR(ReduceT),
-    C(CNode<H, F, K, V, M>, ReduceT),
-    L(Arc<LNode<K, V>>, ReduceT),
-    S(Arc<SNode<K, V>>, ReduceT),
+    C(CNode<H, F, K, V, M, A>, ReduceT),
+    L(Arc<LNode<H, K, V>>, ReduceT),
+    S(Arc<SNode<H, K, V>>, ReduceT),
     Removed(ReduceT),
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, ReduceT> MNodeJointTransformResult<H, F, K, V, M, ReduceT> {
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>, ReduceT> MNodeJointTransformResult<H, F, K, V, M, A, ReduceT> {
     pub(crate) fn flip(self) -> Self {
         match self {
             Self::UnchangedLR(reduced) => Self::UnchangedLR(reduced),
@@ -253,15 +307,15 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, ReduceT> MNodeJ
 }
 
 #[must_use]
-pub(crate) enum MNodeTransmuteResult<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, ReduceT> {
-    C(CNode<H, F, K, V, M>, ReduceT),
-    L(Arc<LNode<K, V>>, ReduceT),
-    S(Arc<SNode<K, V>>, ReduceT),
+pub(crate) enum MNodeTransmuteResult<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>, ReduceT> {
+    C(CNode<H, F, K, V, M, A>, ReduceT),
+    L(Arc<LNode<H, K, V>>, ReduceT),
+    S(Arc<SNode<H, K, V>>, ReduceT),
     Removed(ReduceT),
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, ReduceT> From<MNodeTransmuteResult<H, F, K, V, M, ReduceT>> for MNodeTransformResult<H, F, K, V, M, ReduceT> {
-    fn from(other: MNodeTransmuteResult<H, F, K, V, M, ReduceT>) -> Self {
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>, ReduceT> From<MNodeTransmuteResult<H, F, K, V, M, A, ReduceT>> for MNodeTransformResult<H, F, K, V, M, A, ReduceT> {
+    fn from(other: MNodeTransmuteResult<H, F, K, V, M, A, ReduceT>) -> Self {
         match other {
             MNodeTransmuteResult::C(cnode, reduced) => MNodeTransformResult::C(cnode, reduced),
             MNodeTransmuteResult::L(lnode, reduced) => MNodeTransformResult::L(lnode, reduced),
@@ -272,15 +326,15 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, ReduceT> From<M
 }
 
 #[must_use]
-pub(crate) enum LNodeTransformResult<K: Key, V: Value, ReduceT> {
+pub(crate) enum LNodeTransformResult<H: Hashword, K: Key, V: Value, ReduceT> {
     Unchanged(ReduceT),
-    L(Arc<LNode<K, V>>, ReduceT),
-    S(Arc<SNode<K, V>>, ReduceT),
+    L(Arc<LNode<H, K, V>>, ReduceT),
+    S(Arc<SNode<H, K, V>>, ReduceT),
     Removed(ReduceT),
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, ReduceT> From<LNodeTransformResult<K, V, ReduceT>> for MNodeTransformResult<H, F, K, V, M, ReduceT> {
-    fn from(other: LNodeTransformResult<K, V, ReduceT>) -> Self {
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>, ReduceT> From<LNodeTransformResult<H, K, V, ReduceT>> for MNodeTransformResult<H, F, K, V, M, A, ReduceT> {
+    fn from(other: LNodeTransformResult<H, K, V, ReduceT>) -> Self {
         match other {
             LNodeTransformResult::Unchanged(reduced) => MNodeTransformResult::Unchanged(reduced),
             LNodeTransformResult::L(lnode, reduced) => MNodeTransformResult::L(lnode, reduced),
@@ -291,17 +345,17 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, ReduceT> From<L
 }
 
 #[must_use]
-pub(crate) enum LNodeJointTransformResult<K: Key, V: Value, ReduceT> {
+pub(crate) enum LNodeJointTransformResult<H: Hashword, K: Key, V: Value, ReduceT> {
     UnchangedLR(ReduceT),
     UnchangedL(ReduceT),
     UnchangedR(ReduceT),
-    L(Arc<LNode<K, V>>, ReduceT),
-    S(Arc<SNode<K, V>>, ReduceT),
+    L(Arc<LNode<H, K, V>>, ReduceT),
+    S(Arc<SNode<H, K, V>>, ReduceT),
     Removed(ReduceT),
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, ReduceT> From<LNodeJointTransformResult<K, V, ReduceT>> for MNodeJointTransformResult<H, F, K, V, M, ReduceT> {
-    fn from(other: LNodeJointTransformResult<K, V, ReduceT>) -> Self {
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>, ReduceT> From<LNodeJointTransformResult<H, K, V, ReduceT>> for MNodeJointTransformResult<H, F, K, V, M, A, ReduceT> {
+    fn from(other: LNodeJointTransformResult<H, K, V, ReduceT>) -> Self {
         match other {
             LNodeJointTransformResult::UnchangedLR(reduced) => MNodeJointTransformResult::UnchangedLR(reduced),
             LNodeJointTransformResult::UnchangedL(reduced) => MNodeJointTransformResult::UnchangedL(reduced),
@@ -314,14 +368,14 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, ReduceT> From<L
 }
 
 #[must_use]
-pub(crate) enum LNodeTransmuteResult<K: Key, V: Value, ReduceT> {
-    L(Arc<LNode<K, V>>, ReduceT),
-    S(Arc<SNode<K, V>>, ReduceT),
+pub(crate) enum LNodeTransmuteResult<H: Hashword, K: Key, V: Value, ReduceT> {
+    L(Arc<LNode<H, K, V>>, ReduceT),
+    S(Arc<SNode<H, K, V>>, ReduceT),
     Removed(ReduceT),
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, ReduceT> From<LNodeTransmuteResult<K, V, ReduceT>> for MNodeTransformResult<H, F, K, V, M, ReduceT> {
-    fn from(other: LNodeTransmuteResult<K, V, ReduceT>) -> Self {
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>, ReduceT> From<LNodeTransmuteResult<H, K, V, ReduceT>> for MNodeTransformResult<H, F, K, V, M, A, ReduceT> {
+    fn from(other: LNodeTransmuteResult<H, K, V, ReduceT>) -> Self {
         match other {
             LNodeTransmuteResult::L(lnode, reduced) => MNodeTransformResult::L(lnode, reduced),
             LNodeTransmuteResult::S(snode, reduced) => MNodeTransformResult::S(snode, reduced),
@@ -330,8 +384,8 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, ReduceT> From<L
     }
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, ReduceT> From<LNodeTransmuteResult<K, V, ReduceT>> for MNodeTransmuteResult<H, F, K, V, M, ReduceT> {
-    fn from(other: LNodeTransmuteResult<K, V, ReduceT>) -> Self {
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>, ReduceT> From<LNodeTransmuteResult<H, K, V, ReduceT>> for MNodeTransmuteResult<H, F, K, V, M, A, ReduceT> {
+    fn from(other: LNodeTransmuteResult<H, K, V, ReduceT>) -> Self {
         match other {
             LNodeTransmuteResult::L(lnode, reduced) => MNodeTransmuteResult::L(lnode, reduced),
             LNodeTransmuteResult::S(snode, reduced) => MNodeTransmuteResult::S(snode, reduced),
@@ -340,33 +394,33 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, ReduceT> From<L
     }
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, ReduceT> From<MapTransmuteResult<K, V, ReduceT>> for MNodeTransmuteResult<H, F, K, V, M, ReduceT> {
-    fn from(other: MapTransmuteResult<K, V, ReduceT>) -> Self {
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>, ReduceT> From<(MapTransmuteResult<K, V, ReduceT>, LeafHash<H>)> for MNodeTransmuteResult<H, F, K, V, M, A, ReduceT> {
+    fn from((other, hash): (MapTransmuteResult<K, V, ReduceT>, LeafHash<H>)) -> Self {
         match other {
-            MapTransmuteResult::Transmuted(key, value, reduced) => MNodeTransmuteResult::S(SNode::new(key, value), reduced),
+            MapTransmuteResult::Transmuted(key, value, reduced) => MNodeTransmuteResult::S(SNode::new(key, value, hash), reduced),
             MapTransmuteResult::Removed(reduced) => MNodeTransmuteResult::Removed(reduced),
         }
     }
 }
 
-impl <K: Key, V: Value, ReduceT> From<MapTransmuteResult<K, V, ReduceT>> for LNodeTransmuteResult<K, V, ReduceT> {
-    fn from(other: MapTransmuteResult<K, V, ReduceT>) -> Self {
+impl <H: Hashword, K: Key, V: Value, ReduceT> From<(MapTransmuteResult<K, V, ReduceT>, LeafHash<H>)> for LNodeTransmuteResult<H, K, V, ReduceT> {
+    fn from((other, hash): (MapTransmuteResult<K, V, ReduceT>, LeafHash<H>)) -> Self {
         match other {
-            MapTransmuteResult::Transmuted(key, value, reduced) => LNodeTransmuteResult::S(SNode::new(key, value), reduced),
+            MapTransmuteResult::Transmuted(key, value, reduced) => LNodeTransmuteResult::S(SNode::new(key, value, hash), reduced),
             MapTransmuteResult::Removed(reduced) => LNodeTransmuteResult::Removed(reduced),
         }
     }
 }
 
 #[must_use]
-pub(crate) enum SNodeTransformResult<K: Key, V: Value, ReduceT> {
+pub(crate) enum SNodeTransformResult<H: Hashword, K: Key, V: Value, ReduceT> {
     Unchanged(ReduceT),
-    S(Arc<SNode<K, V>>, ReduceT),
+    S(Arc<SNode<H, K, V>>, ReduceT),
     Removed(ReduceT),
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, ReduceT> From<SNodeTransformResult<K, V, ReduceT>> for MNodeTransformResult<H, F, K, V, M, ReduceT> {
-    fn from(other: SNodeTransformResult<K, V, ReduceT>) -> Self {
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>, ReduceT> From<SNodeTransformResult<H, K, V, ReduceT>> for MNodeTransformResult<H, F, K, V, M, A, ReduceT> {
+    fn from(other: SNodeTransformResult<H, K, V, ReduceT>) -> Self {
         match other {
             SNodeTransformResult::Unchanged(reduced) => MNodeTransformResult::Unchanged(reduced),
             SNodeTransformResult::S(snode, reduced) => MNodeTransformResult::S(snode, reduced),
@@ -375,8 +429,8 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, ReduceT> From<S
     }
 }
 
-impl <K: Key, V: Value, ReduceT> From<SNodeTransformResult<K, V, ReduceT>> for LNodeTransformResult<K, V, ReduceT> {
-    fn from(other: SNodeTransformResult<K, V, ReduceT>) -> Self {
+impl <H: Hashword, K: Key, V: Value, ReduceT> From<SNodeTransformResult<H, K, V, ReduceT>> for LNodeTransformResult<H, K, V, ReduceT> {
+    fn from(other: SNodeTransformResult<H, K, V, ReduceT>) -> Self {
         match other {
             SNodeTransformResult::Unchanged(reduced) => LNodeTransformResult::Unchanged(reduced),
             SNodeTransformResult::S(snode, reduced) => LNodeTransformResult::S(snode, reduced),
@@ -386,13 +440,13 @@ impl <K: Key, V: Value, ReduceT> From<SNodeTransformResult<K, V, ReduceT>> for L
 }
 
 #[must_use]
-pub(crate) enum SNodeTransmuteResult<K: Key, V: Value, ReduceT> {
-    S(Arc<SNode<K, V>>, ReduceT),
+pub(crate) enum SNodeTransmuteResult<H: Hashword, K: Key, V: Value, ReduceT> {
+    S(Arc<SNode<H, K, V>>, ReduceT),
     Removed(ReduceT),
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, ReduceT> From<SNodeTransmuteResult<K, V, ReduceT>> for MNodeTransformResult<H, F, K, V, M, ReduceT> {
-    fn from(other: SNodeTransmuteResult<K, V, ReduceT>) -> Self {
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>, ReduceT> From<SNodeTransmuteResult<H, K, V, ReduceT>> for MNodeTransformResult<H, F, K, V, M, A, ReduceT> {
+    fn from(other: SNodeTransmuteResult<H, K, V, ReduceT>) -> Self {
         match other {
             SNodeTransmuteResult::S(snode, reduced) => MNodeTransformResult::S(snode, reduced),
             SNodeTransmuteResult::Removed(reduced) => MNodeTransformResult::Removed(reduced),
@@ -400,8 +454,8 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, ReduceT> From<S
     }
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, ReduceT> From<SNodeTransmuteResult<K, V, ReduceT>> for MNodeTransmuteResult<H, F, K, V, M, ReduceT> {
-    fn from(other: SNodeTransmuteResult<K, V, ReduceT>) -> Self {
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, A: Measure<K, V>, ReduceT> From<SNodeTransmuteResult<H, K, V, ReduceT>> for MNodeTransmuteResult<H, F, K, V, M, A, ReduceT> {
+    fn from(other: SNodeTransmuteResult<H, K, V, ReduceT>) -> Self {
         match other {
             SNodeTransmuteResult::S(snode, reduced) => MNodeTransmuteResult::S(snode, reduced),
             SNodeTransmuteResult::Removed(reduced) => MNodeTransmuteResult::Removed(reduced),
@@ -409,8 +463,8 @@ impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: 'static, ReduceT> From<S
     }
 }
 
-impl <K: Key, V: Value, ReduceT> From<SNodeTransmuteResult<K, V, ReduceT>> for LNodeTransmuteResult<K, V, ReduceT> {
-    fn from(other: SNodeTransmuteResult<K, V, ReduceT>) -> Self {
+impl <H: Hashword, K: Key, V: Value, ReduceT> From<SNodeTransmuteResult<H, K, V, ReduceT>> for LNodeTransmuteResult<H, K, V, ReduceT> {
+    fn from(other: SNodeTransmuteResult<H, K, V, ReduceT>) -> Self {
         match other {
             SNodeTransmuteResult::S(snode, reduced) => LNodeTransmuteResult::S(snode, reduced),
             SNodeTransmuteResult::Removed(reduced) => LNodeTransmuteResult::Removed(reduced),
@@ -418,10 +472,10 @@ impl <K: Key, V: Value, ReduceT> From<SNodeTransmuteResult<K, V, ReduceT>> for L
     }
 }
 
-impl <K: Key, V: Value, ReduceT> From<MapTransmuteResult<K, V, ReduceT>> for SNodeTransmuteResult<K, V, ReduceT> {
-    fn from(other: MapTransmuteResult<K, V, ReduceT>) -> Self {
+impl <H: Hashword, K: Key, V: Value, ReduceT> From<(MapTransmuteResult<K, V, ReduceT>, LeafHash<H>)> for SNodeTransmuteResult<H, K, V, ReduceT> {
+    fn from((other, hash): (MapTransmuteResult<K, V, ReduceT>, LeafHash<H>)) -> Self {
         match other {
-            MapTransmuteResult::Transmuted(key, value, reduced) => SNodeTransmuteResult::S(SNode::new(key, value), reduced),
+            MapTransmuteResult::Transmuted(key, value, reduced) => SNodeTransmuteResult::S(SNode::new(key, value, hash), reduced),
             MapTransmuteResult::Removed(reduced) => SNodeTransmuteResult::Removed(reduced),
         }
     }
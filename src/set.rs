@@ -1,8 +1,10 @@
-use crate::{results::*, transformations::*, traits::*, hash_trie::HashTrie, *};
-use alloc::fmt::Debug;
+use crate::{digest::Digest, results::*, transformations::*, traits::*, hash_trie::HashTrie, *};
+use alloc::{fmt::Debug, vec::Vec};
 
 /// `HashTrieSet` implements a hash set using a hash array mapped trie (HAMT).
 /// 
+/// With the `cache_hash` feature, each entry also stores the full hash of its key, `size_of::<H>()` bytes, which is reused rather than recomputed whenever leaves are split, merged or compared.
+/// 
 /// # Example Usage
 /// 
 /// ```
@@ -63,13 +65,12 @@ use alloc::fmt::Debug;
 ///     Err(_) => panic!(),
 /// }
 /// ```
-#[derive(Clone, Debug)]
 #[must_use]
-pub struct HashTrieSet <H: Hashword, F: Flagword<H>, K: Key, M: HasherBv<H, K>> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
-    set: HashTrie<H, F, K, (), M>,
+pub struct HashTrieSet <H: Hashword, F: Flagword<H>, K: Key, M: HasherBv<H, K>, A: Measure<K, ()> = ()> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    pub(crate) set: HashTrie<H, F, K, (), M, A>,
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, M: HasherBv<H, K>> HashTrieSet<H, F, K, M> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+impl <H: Hashword, F: Flagword<H>, K: Key, M: HasherBv<H, K>, A: Measure<K, ()>> HashTrieSet<H, F, K, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
     /// Get a new, empty HashTrieSet.
     pub fn new() -> Self {
         Self {
@@ -82,6 +83,16 @@ impl <H: Hashword, F: Flagword<H>, K: Key, M: HasherBv<H, K>> HashTrieSet<H, F,
         self.set.size()
     }
 
+    /// Get the summary of all entries in the set under the measure `A`, cached incrementally by each node.
+    pub fn summary(&self) -> <A as Measure<K, ()>>::Summary {
+        self.set.summary()
+    }
+
+    /// Get the content digest of the whole set in O(1). Sets hold equal keys exactly when their digests are equal, barring a SHA-256 collision.
+    pub fn root_digest(&self) -> Digest where A: Measure<K, (), Summary = Digest> {
+        self.set.summary()
+    }
+
     /// Search the HashTrieSet for the given key and return a reference if found, or `HashTrieError::NotFound` if not found.
     pub fn find<'a, L: Key + HashLike<K>>(&'a self, key: &L) -> Result<&'a K, HashTrieError> where K: PartialEq<L>, M: HasherBv<H, L> {
         self.set.find(key).map(|(key, _value)| key)
@@ -89,7 +100,7 @@ impl <H: Hashword, F: Flagword<H>, K: Key, M: HasherBv<H, K>> HashTrieSet<H, F,
 
     /// Search the HashTrieSet for the spot to insert the key and return both a mutated set and, if applicable, a reference to the replaced key.
     /// If found and replacement is disabled, a reference to the existing key is returned.
-    pub fn insert<'a, L: Key + HashLike<K> + Into<K>>(&'a self, key: L, replace: bool) -> Result<(Self, *const K, Option<&'a K>), &'a K>
+    pub fn insert<L: Key + HashLike<K> + Into<K>>(&self, key: L, replace: bool) -> Result<(Self, *const K, Option<&K>), &K>
     where
         K: HashLike<L>,
         K: PartialEq<L>,
@@ -103,17 +114,108 @@ impl <H: Hashword, F: Flagword<H>, K: Key, M: HasherBv<H, K>> HashTrieSet<H, F,
         self.set.remove(key).map(|(set, key, _value)| (Self {set}, key))
     }
 
+    /// Search the HashTrieSet for the given key using a precomputed hash and return a reference if found, or `HashTrieError::NotFound` if not found.
+    /// The hash must be the one `M` would compute for the key.
+    pub fn find_with_hash<'a, L: Key + HashLike<K>>(&'a self, hash: H, key: &L) -> Result<&'a K, HashTrieError> where K: PartialEq<L>, M: HasherBv<H, L> {
+        self.set.find_with_hash(hash, key).map(|(key, _value)| key)
+    }
+
+    /// Search the HashTrieSet for the spot to insert the key using a precomputed hash and return both a mutated set and, if applicable, a reference to the replaced key.
+    /// The hash must be the one `M` would compute for the key.
+    pub fn insert_with_hash<L: Key + HashLike<K> + Into<K>>(&self, hash: H, key: L, replace: bool) -> Result<(Self, *const K, Option<&K>), &K>
+    where
+        K: HashLike<L>,
+        K: PartialEq<L>,
+        M: HasherBv<H, L>
+    {
+        self.set.insert_with_hash(hash, key, (), replace).map(|(set, key, _value, prev)| (Self {set}, key, prev.map(|(k, _v)| k))).map_err(|(key, _value)| key)
+    }
+
+    /// Search the HashTrieSet for the given key to remove using a precomputed hash and return a mutated set, or `HashTrieError::NotFound` if not found.
+    /// The hash must be the one `M` would compute for the key.
+    pub fn remove_with_hash<'a, L: Key + HashLike<K>>(&'a self, hash: H, key: &L) -> Result<(Self, &'a K), HashTrieError> where K: PartialEq<L>, M: HasherBv<H, L> {
+        self.set.remove_with_hash(hash, key).map(|(set, key, _value)| (Self {set}, key))
+    }
+
+    /// Search the HashTrieSet for each of the given keys in a single traversal, returning results in the order of `keys`.
+    pub fn get_many<'a, L: Key + HashLike<K>>(&'a self, keys: &[L]) -> Vec<Result<&'a K, HashTrieError>> where K: PartialEq<L>, M: HasherBv<H, L> {
+        self.set.find_many(keys).into_iter().map(|result| result.map(|(key, _value)| key)).collect()
+    }
+
+    /// Insert the keys in a single traversal, rebuilding each touched node once.
+    pub fn insert_many<I: IntoIterator<Item = K>>(&self, keys: I) -> Self {
+        Self {set: self.set.apply(keys.into_iter().map(|key| (M::default().hash(&key), key, Some(()))).collect())}
+    }
+
+    /// Remove the keys, if present, in a single traversal, rebuilding each touched node once.
+    pub fn remove_many<I: IntoIterator<Item = K>>(&self, keys: I) -> Self {
+        Self {set: self.set.apply(keys.into_iter().map(|key| (M::default().hash(&key), key, None)).collect())}
+    }
+
     /// Run an operation on each entry in the set.
     pub fn visit<Op: Clone>(&self, op: Op) where Op: Fn(&K) {
         self.set.visit(|key, _value| op(key));
     }
 
+    /// Get the key at the given position in the set's visiting order, or `HashTrieError::NotFound` if out of range. Descends using cached subtree sizes.
+    pub fn nth(&self, index: usize) -> Result<&K, HashTrieError> {
+        self.set.nth(index).map(|(key, _value)| key)
+    }
+
+    /// Pick a key uniformly at random, or `HashTrieError::NotFound` if the set is empty.
+    #[cfg(feature = "rand")]
+    pub fn get_random<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Result<&K, HashTrieError> {
+        self.set.get_random(rng).map(|(key, _value)| key)
+    }
+
+    /// Collect references to every key whose hash matches `prefix` in its `bits` least significant bits, the bits the trie indexes first.
+    #[must_use]
+    pub fn entries_with_hash_prefix(&self, prefix: H, bits: usize) -> Vec<&K> {
+        self.set.entries_with_hash_prefix(prefix, bits).into_iter().map(|(key, _value)| key).collect()
+    }
+
+    /// Split the set into one set per populated child of the root. Subtrees are shared with this set rather than copied.
+    #[must_use]
+    pub fn split_root(&self) -> Vec<Self> {
+        self.set.split_root().into_iter().map(|set| Self {set}).collect()
+    }
+
+    /// Combine sets known to hold disjoint keys, such as those produced by `split_root`, sharing their subtrees.
+    pub fn concat_disjoint(sets: &[Self]) -> Self {
+        Self {set: HashTrie::concat_disjoint(sets.iter().map(|set| &set.set))}
+    }
+
+    /// Merge `left` and `right`, both derived from `base`, keeping each side's insertions and removals. Subtrees either side left untouched are shared rather than walked.
+    pub fn merge3(base: &Self, left: &Self, right: &Self) -> Self {
+        Self {set: HashTrie::merge3(&base.set, &left.set, &right.set, |_key, _base, _left, _right| unreachable!())}
+    }
+
+    /// Split the set into `2^bits` sets, indexed by the `bits` least significant bits of each key's hash. Subtrees are shared with this set rather than copied.
+    #[must_use]
+    pub fn split_by_hash_prefix(&self, bits: usize) -> Vec<Self> {
+        self.set.split_by_hash_prefix(bits).into_iter().map(|set| Self {set}).collect()
+    }
+
+    /// Group the keys by the key `op` returns for each, bulk building one set per group. Key hashes are reused rather than recomputed.
+    pub fn group_by<G: Key, Op: Fn(&K) -> G>(&self, op: Op) -> HashTrieMap<H, F, G, Self, M> where M: HasherBv<H, G> {
+        HashTrieMap::build(self.set.group_by(|key, _value| op(key)).into_iter().map(|(hash_value, group, entries)| (hash_value, group, Some(Self::build(entries)))).collect())
+    }
+
+    /// Build a map holding the value `op` returns for each key, mapping over the trie so its shape and key hashes are reused rather than rebuilt.
+    pub fn to_map_with<V: Value, Op: Fn(&K) -> V>(&self, op: Op) -> HashTrieMap<H, F, K, V, M> {
+        HashTrieMap {set: self.set.map_entries(|key, _value| op(key))}
+    }
+
+    pub(crate) fn build(entries: Vec<(H, K, Option<()>)>) -> Self {
+        Self {set: HashTrie::build(entries)}
+    }
+
     /// Run a transform operation on each entry in the set. Returns the transformed set and a reduction of the secondary returns of the transform operations.
     pub async fn transform<ReduceT, ReduceOp, Op>
         (&self, reduce_op: ReduceOp, op: SetTransform<ReduceT, Op>, par_strat: ParallelismStrategy) -> (Self, ReduceT)
         where
         Self: Sized,
-        ReduceT: Clone + Default + Send + Sync,
+        ReduceT: Default + Send + Sync,
         ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
         Op: Fn(&K) -> SetTransformResult<ReduceT> + Clone + Send + Sync,
     {
@@ -128,12 +230,32 @@ impl <H: Hashword, F: Flagword<H>, K: Key, M: HasherBv<H, K>> HashTrieSet<H, F,
         (Self{set}, reduced)
     }
 
+    /// Run a transform operation on each entry in the set, combining the secondary returns with `Monoid::combine`. Returns the transformed set and the combined reduction.
+    pub async fn transform_monoid<ReduceT, Op>
+        (&self, op: SetTransform<ReduceT, Op>, par_strat: ParallelismStrategy) -> (Self, ReduceT)
+        where
+        Self: Sized,
+        ReduceT: Monoid + Default,
+        Op: Fn(&K) -> SetTransformResult<ReduceT> + Clone + Send + Sync,
+    {
+        let (set, reduced) = self.set.transform(MonoidReducer, match op {
+            SetTransform::Generic(f) => new_map_transform_generic(move |key, _value| match f(key) {
+                SetTransformResult::Unchanged(reduced) => MapTransformResult::Unchanged(reduced),
+                SetTransformResult::Removed(reduced) => MapTransformResult::Removed(reduced),
+            }),
+            SetTransform::Unchanged(r) => MapTransform::Unchanged(r),
+            SetTransform::Removed(r) => MapTransform::Removed(r),
+        }, par_strat).await;
+        (Self{set}, reduced)
+    }
+
     /// Run a transmute operation on each entry in the set. Returns the transmuted set and a reduction of the secondary returns of the transmute operations.
     pub unsafe fn transmute<S: Key + HashLike<S>, ReduceT, ReduceOp, Op>
-        (&self, reduce_op: ReduceOp, op: SetTransmute<ReduceT, Op>) -> (HashTrieSet<H, F, S, M>, ReduceT)
+        (&self, reduce_op: ReduceOp, op: SetTransmute<ReduceT, Op>) -> (HashTrieSet<H, F, S, M, A>, ReduceT)
         where
+        A: Measure<S, ()>,
         Self: Sized,
-        ReduceT: Clone + Default + Send + Sync,
+        ReduceT: Default + Send + Sync,
         ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
         Op: Fn(&K) -> SetTransmuteResult<S, ReduceT> + Clone + Send + Sync,
         K: HashLike<S>,
@@ -155,7 +277,7 @@ impl <H: Hashword, F: Flagword<H>, K: Key, M: HasherBv<H, K>> HashTrieSet<H, F,
         (&self, right: &Self, reduce_op: ReduceOp, both_op: SetJointTransform<ReduceT, BothOp>, left_op: SetTransform<ReduceT, LeftOp>, right_op: SetTransform<ReduceT, RightOp>, par_strat: ParallelismStrategy) -> (Self, ReduceT)
         where
         Self: Sized,
-        ReduceT: Clone + Default + Send + Sync,
+        ReduceT: Default + Send + Sync,
         ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
         BothOp: Fn(&K, &K) -> SetJointTransformResult<ReduceT> + Clone + Send + Sync,
         LeftOp: Fn(&K) -> SetTransformResult<ReduceT> + Clone + Send + Sync,
@@ -164,10 +286,10 @@ impl <H: Hashword, F: Flagword<H>, K: Key, M: HasherBv<H, K>> HashTrieSet<H, F,
         let (set, reduced) = self.set.transform_with_transformed(&right.set, reduce_op,
             match both_op {
                 SetJointTransform::Generic(g, flipped) => MapJointTransform::Generic(move |k:&_, _: &_, l:&_, _: &_| g(k, l).into(), flipped),
-                SetJointTransform::UnchangedLR(r) => MapJointTransform::UnchangedLR(r.clone()),
-                SetJointTransform::UnchangedL(r) => MapJointTransform::UnchangedL(r.clone()),
-                SetJointTransform::UnchangedR(r) => MapJointTransform::UnchangedR(r.clone()),
-                SetJointTransform::Removed(r) => MapJointTransform::Removed(r.clone()),
+                SetJointTransform::UnchangedLR(r) => MapJointTransform::UnchangedLR(r),
+                SetJointTransform::UnchangedL(r) => MapJointTransform::UnchangedL(r),
+                SetJointTransform::UnchangedR(r) => MapJointTransform::UnchangedR(r),
+                SetJointTransform::Removed(r) => MapJointTransform::Removed(r),
             },
             match left_op {
                 SetTransform::Generic(f) => MapTransform::Generic(move |l: &_, _: &_| f(l).into()),
@@ -184,10 +306,11 @@ impl <H: Hashword, F: Flagword<H>, K: Key, M: HasherBv<H, K>> HashTrieSet<H, F,
 
     /// Run a transform/transmute operation on each entry or pair of entries in the sets. Returns the transmuted set and a reduction of the secondary returns of the transmute operations. Can reuse nodes from the transformed set.
     pub async unsafe fn transform_with_transmuted<L: Key + HashLike<K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>
-        (&self, right: &HashTrieSet<H, F, L, M>, reduce_op: ReduceOp, both_op: SetTransform<ReduceT, BothOp>, left_op: SetTransform<ReduceT, LeftOp>, right_op: SetTransmute<ReduceT, RightOp>, par_strat: ParallelismStrategy) -> (Self, ReduceT)
+        (&self, right: &HashTrieSet<H, F, L, M, A>, reduce_op: ReduceOp, both_op: SetTransform<ReduceT, BothOp>, left_op: SetTransform<ReduceT, LeftOp>, right_op: SetTransmute<ReduceT, RightOp>, par_strat: ParallelismStrategy) -> (Self, ReduceT)
         where
+        A: Measure<L, ()>,
         Self: Sized,
-        ReduceT: Clone + Default + Send + Sync,
+        ReduceT: Default + Send + Sync,
         ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
         BothOp: Fn(&K, &L) -> SetTransformResult<ReduceT> + Clone,
         LeftOp: Fn(&K) -> SetTransformResult<ReduceT> + Clone + Send + Sync,
@@ -199,7 +322,11 @@ impl <H: Hashword, F: Flagword<H>, K: Key, M: HasherBv<H, K>> HashTrieSet<H, F,
         M: HasherBv<H, L>,
     {
         let (set, reduced) = self.set.transform_with_transmuted(&right.set, reduce_op,
-            new_map_transform_transmute_generic(|l: &_, _: &_, r: &_, _: &_| both_op.call_transmute(l, r).into()),
+            match both_op {
+                SetTransform::Generic(f) => MapTransform::Generic(move |l: &_, _: &_, r: &_, _: &_| f(l, r).into()),
+                SetTransform::Unchanged(r) => MapTransform::Unchanged(r),
+                SetTransform::Removed(r) => MapTransform::Removed(r),
+            },
             match left_op {
                 SetTransform::Generic(f) => MapTransform::Generic(move |l: &_, _: &_| f(l).into()),
                 SetTransform::Unchanged(r) => MapTransform::Unchanged(r),
@@ -217,10 +344,11 @@ impl <H: Hashword, F: Flagword<H>, K: Key, M: HasherBv<H, K>> HashTrieSet<H, F,
 
     /// Run a transmute/transform operation on each entry or pair of entries in the sets. Returns the transmuted set and a reduction of the secondary returns of the transmute operations. Can reuse nodes from the transformed set.
     pub async unsafe fn transmute_with_transformed<L: Key + HashLike<K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>
-        (&self, right: &HashTrieSet<H, F, L, M>, reduce_op: ReduceOp, both_op: SetTransform<ReduceT, BothOp>, left_op: SetTransmute<ReduceT, LeftOp>, right_op: SetTransform<ReduceT, RightOp>, par_strat: ParallelismStrategy) -> (HashTrieSet<H, F, L, M>, ReduceT)
+        (&self, right: &HashTrieSet<H, F, L, M, A>, reduce_op: ReduceOp, both_op: SetTransform<ReduceT, BothOp>, left_op: SetTransmute<ReduceT, LeftOp>, right_op: SetTransform<ReduceT, RightOp>, par_strat: ParallelismStrategy) -> (HashTrieSet<H, F, L, M, A>, ReduceT)
         where
+        A: Measure<L, ()>,
         Self: Sized,
-        ReduceT: Clone + Default + Send + Sync,
+        ReduceT: Default + Send + Sync,
         ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
         BothOp: Fn(&K, &L) -> SetTransformResult<ReduceT> + Clone,
         LeftOp: Fn(&K) -> SetTransmuteResult<L, ReduceT> + Clone + Send + Sync,
@@ -232,7 +360,11 @@ impl <H: Hashword, F: Flagword<H>, K: Key, M: HasherBv<H, K>> HashTrieSet<H, F,
         M: HasherBv<H, L>,
     {
         let (set, reduced) = self.set.transmute_with_transformed(&right.set, reduce_op,
-            new_map_transmute_transform_generic(|l: &_, _: &_, r: &_, _: &_| both_op.call_transmute(l, r).into()),
+            match both_op {
+                SetTransform::Generic(f) => MapTransform::Generic(move |l: &_, _: &_, r: &_, _: &_| f(l, r).into()),
+                SetTransform::Unchanged(r) => MapTransform::Unchanged(r),
+                SetTransform::Removed(r) => MapTransform::Removed(r),
+            },
             match left_op {
                 SetTransmute::Generic(f) => MapTransmute::Generic(move |l: &_, _: &_| match f(l) {
                     SetTransmuteResult::Transmuted(k, r) => MapTransmuteResult::Transmuted(k, (), r),
@@ -250,10 +382,12 @@ impl <H: Hashword, F: Flagword<H>, K: Key, M: HasherBv<H, K>> HashTrieSet<H, F,
 
     /// Run a transmute operation on each entry or pair of entries in the sets. Returns the transmuted set and a reduction of the secondary returns of the transmute operations.
     pub unsafe fn transmute_with_transmuted<L: Key + HashLike<K>, S: Key + HashLike<K>, ReduceT, ReduceOp, BothOp, LeftOp, RightOp>
-        (&self, right: &HashTrieSet<H, F, L, M>, reduce_op: ReduceOp, both_op: SetTransmute<ReduceT, BothOp>, left_op: SetTransmute<ReduceT, LeftOp>, right_op: SetTransmute<ReduceT, RightOp>) -> (HashTrieSet<H, F, S, M>, ReduceT)
+        (&self, right: &HashTrieSet<H, F, L, M, A>, reduce_op: ReduceOp, both_op: SetTransmute<ReduceT, BothOp>, left_op: SetTransmute<ReduceT, LeftOp>, right_op: SetTransmute<ReduceT, RightOp>) -> (HashTrieSet<H, F, S, M, A>, ReduceT)
         where
+        A: Measure<L, ()>,
+        A: Measure<S, ()>,
         Self: Sized,
-        ReduceT: Clone + Default + Send + Sync,
+        ReduceT: Default + Send + Sync,
         ReduceOp: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync,
         BothOp: Fn(&K, &L) -> SetTransmuteResult<S, ReduceT> + Clone,
         LeftOp: Fn(&K) -> SetTransmuteResult<S, ReduceT> + Clone,
@@ -270,10 +404,13 @@ impl <H: Hashword, F: Flagword<H>, K: Key, M: HasherBv<H, K>> HashTrieSet<H, F,
         M: HasherBv<H, S>,
     {
         let (set, reduced) = self.set.transmute_with_transmuted(&right.set, reduce_op, 
-            new_map_transmute_transmute_generic(|l: &_, _: &_, r: &_, _: &_| match both_op.call_transmute(l, r) {
-                SetTransmuteResult::Transmuted(key, reduced) => MapTransmuteResult::Transmuted(key, (), reduced),
-                SetTransmuteResult::Removed(reduced) => MapTransmuteResult::Removed(reduced),
-            }),
+            match both_op {
+                SetTransmute::Generic(f) => MapTransmute::Generic(move |l: &_, _: &_, r: &_, _: &_| match f(l, r) {
+                    SetTransmuteResult::Transmuted(key, reduced) => MapTransmuteResult::Transmuted(key, (), reduced),
+                    SetTransmuteResult::Removed(reduced) => MapTransmuteResult::Removed(reduced),
+                }),
+                SetTransmute::Removed(r) => MapTransmute::Removed(r),
+            },
             match left_op {
                 SetTransmute::Generic(f) => MapTransmute::Generic(move |l: &_, _: &_| match f(l) {
                     SetTransmuteResult::Transmuted(k, r) => MapTransmuteResult::Transmuted(k, (), r),
@@ -293,15 +430,38 @@ impl <H: Hashword, F: Flagword<H>, K: Key, M: HasherBv<H, K>> HashTrieSet<H, F,
 
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, M: HasherBv<H, K>> Default for HashTrieSet<H, F, K, M> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+impl <H: Hashword, F: Flagword<H>, G: Key, K: Key, M: HasherBv<H, G> + HasherBv<H, K>, A: Measure<K, ()>, B: Measure<G, HashTrieSet<H, F, K, M, A>>> HashTrieMap<H, F, G, HashTrieSet<H, F, K, M, A>, M, B> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    /// Merge the sets held as values into a single set, undoing `group_by`.
+    pub fn flatten(&self) -> HashTrieSet<H, F, K, M, A> {
+        let mut entries = Vec::new();
+        for (_group, set) in self.entries() {
+            entries.extend(set.set.entries_with_hashes().into_iter().map(|(key, _value, hash_value)| (hash_value, key.clone(), Some(()))));
+        }
+        HashTrieSet::build(entries)
+    }
+}
+
+impl <H: Hashword, F: Flagword<H>, K: Key, M: HasherBv<H, K>, A: Measure<K, ()>> Clone for HashTrieSet<H, F, K, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    fn clone(&self) -> Self {
+        Self {set: self.set.clone()}
+    }
+}
+
+impl <H: Hashword, F: Flagword<H>, K: Key, M: HasherBv<H, K>, A: Measure<K, ()>> Debug for HashTrieSet<H, F, K, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("HashTrieSet").field("set", &self.set).finish()
+    }
+}
+
+impl <H: Hashword, F: Flagword<H>, K: Key, M: HasherBv<H, K>, A: Measure<K, ()>> Default for HashTrieSet<H, F, K, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl <H: Hashword, F: Flagword<H>, K: Key, M: HasherBv<H, K>> Eq for HashTrieSet<H, F, K, M> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {}
+impl <H: Hashword, F: Flagword<H>, K: Key, M: HasherBv<H, K>, A: Measure<K, ()>> Eq for HashTrieSet<H, F, K, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {}
 
-impl <H: Hashword, F: Flagword<H>, K: Key, M: HasherBv<H, K>> PartialEq for HashTrieSet<H, F, K, M> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+impl <H: Hashword, F: Flagword<H>, K: Key, M: HasherBv<H, K>, A: Measure<K, ()>> PartialEq for HashTrieSet<H, F, K, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
     fn eq(&self, other: &Self) -> bool {
         self.set == other.set
     }
@@ -328,6 +488,37 @@ mod tests {
         assert_eq!(summed.1, 5050);
     }
 
+    #[test]
+    fn set_batch() {
+        let set = DefaultHashTrieSet::<i32>::new().insert_many(0..1000);
+        assert_eq!(set.size(), 1000);
+
+        let set = set.remove_many((0..1000).filter(|i| i % 4 != 0)).insert_many([1, 1, 2000]);
+        assert_eq!(set.size(), 252);
+        assert_eq!(set.get_many(&[0, 1, 2, 2000]).iter().map(|result| result.is_ok()).collect::<Vec<_>>(), vec!(true, true, false, true));
+    }
+
+    #[test]
+    fn set_group_by_flatten() {
+        let mut set = DefaultHashTrieSet::<i32>::new();
+
+        for i in 0..1000 {
+            set = set.insert(i, false).unwrap().0;
+        }
+
+        let groups = set.group_by(|k| k % 7);
+        assert_eq!(groups.size(), 7);
+        assert_eq!(groups.find(&3).unwrap().1.size(), 143);
+        assert!(groups.find(&3).unwrap().1.find(&94).is_ok());
+        assert!(groups.find(&3).unwrap().1.find(&95).is_err());
+
+        let flattened = groups.flatten();
+        assert_eq!(flattened.size(), 1000);
+        for i in 0..1000 {
+            assert!(flattened.find(&i).is_ok());
+        }
+    }
+
     #[test]
     fn set_transmute() {
         let mut set = DefaultHashTrieSet::<i32>::new();
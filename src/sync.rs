@@ -0,0 +1,124 @@
+use crate::digest::Digest;
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+/// `SyncPath` addresses a subtree by the child index taken at each level from the root, and lists the digests of its children.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SyncPath {
+    /// The child index taken at each level, starting from the root.
+    pub path: Vec<u8>,
+    /// The digest of each populated child of the addressed subtree, by child index.
+    pub digests: Vec<(u8, Digest)>,
+}
+
+/// `SyncRequest` describes subtrees of one replica so that a peer can answer with what differs.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SyncRequest {
+    /// The subtrees being compared.
+    pub paths: Vec<SyncPath>,
+}
+
+/// `SyncResponse` answers a `SyncRequest` with entries from differing subtrees and the subtrees worth comparing more finely.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SyncResponse<K, V> {
+    /// Entries of the responder in subtrees whose digests differ.
+    pub entries: Vec<(K, V)>,
+    /// Subtrees that differ and are large enough to compare another level down.
+    pub descend: Vec<Vec<u8>>,
+}
+
+impl SyncRequest {
+    /// Encode the request for transport.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        put_u32(&mut bytes, self.paths.len());
+        for path in &self.paths {
+            put_path(&mut bytes, &path.path);
+            put_u32(&mut bytes, path.digests.len());
+            for (index, digest) in &path.digests {
+                bytes.push(*index);
+                bytes.extend_from_slice(&digest.0);
+            }
+        }
+        bytes
+    }
+
+    /// Decode a request encoded by `to_bytes`, or `None` if the bytes are malformed.
+    #[must_use]
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut bytes = bytes;
+        let mut paths = Vec::new();
+        for _ in 0..get_u32(&mut bytes)? {
+            let path = get_path(&mut bytes)?;
+            let mut digests = Vec::new();
+            for _ in 0..get_u32(&mut bytes)? {
+                let index = *bytes.first()?;
+                bytes = &bytes[1..];
+                digests.push((index, Digest(get_digest(&mut bytes)?)));
+            }
+            paths.push(SyncPath {path, digests});
+        }
+        if bytes.is_empty() {Some(Self {paths})} else {None}
+    }
+}
+
+impl <K, V> SyncResponse<K, V> {
+    /// Encode the response for transport, using `encode` to append each entry.
+    #[must_use]
+    pub fn to_bytes<Encode: Fn(&K, &V, &mut Vec<u8>)>(&self, encode: Encode) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        put_u32(&mut bytes, self.entries.len());
+        for (key, value) in &self.entries {
+            encode(key, value, &mut bytes);
+        }
+        put_u32(&mut bytes, self.descend.len());
+        for path in &self.descend {
+            put_path(&mut bytes, path);
+        }
+        bytes
+    }
+
+    /// Decode a response encoded by `to_bytes`, using `decode` to consume each entry, or `None` if the bytes are malformed.
+    #[must_use]
+    pub fn from_bytes<Decode: Fn(&mut &[u8]) -> Option<(K, V)>>(bytes: &[u8], decode: Decode) -> Option<Self> {
+        let mut bytes = bytes;
+        let mut entries = Vec::new();
+        for _ in 0..get_u32(&mut bytes)? {
+            entries.push(decode(&mut bytes)?);
+        }
+        let mut descend = Vec::new();
+        for _ in 0..get_u32(&mut bytes)? {
+            descend.push(get_path(&mut bytes)?);
+        }
+        if bytes.is_empty() {Some(Self {entries, descend})} else {None}
+    }
+}
+
+fn put_u32(bytes: &mut Vec<u8>, value: usize) {
+    bytes.extend_from_slice(&(value as u32).to_le_bytes());
+}
+
+fn put_path(bytes: &mut Vec<u8>, path: &[u8]) {
+    bytes.push(path.len() as u8);
+    bytes.extend_from_slice(path);
+}
+
+fn get_u32(bytes: &mut &[u8]) -> Option<u32> {
+    let value = u32::from_le_bytes(bytes.get(..4)?.try_into().ok()?);
+    *bytes = &bytes[4..];
+    Some(value)
+}
+
+fn get_digest(bytes: &mut &[u8]) -> Option<[u8; 32]> {
+    let value = bytes.get(..32)?.try_into().ok()?;
+    *bytes = &bytes[32..];
+    Some(value)
+}
+
+fn get_path(bytes: &mut &[u8]) -> Option<Vec<u8>> {
+    let len = *bytes.first()? as usize;
+    let path = bytes.get(1..1 + len)?.to_vec();
+    *bytes = &bytes[1 + len..];
+    Some(path)
+}
@@ -265,3 +265,73 @@ hasher_bv_impl!(u8);
 hasher_bv_impl!(u16);
 hasher_bv_impl!(u32);
 hasher_bv_impl!(u64);
+
+/// `Monoid` provides an associative combining operation with an identity element.
+pub trait Monoid: Clone + Debug + Send + Sync + 'static {
+    /// Get the identity element.
+    #[must_use]
+    fn empty() -> Self;
+    /// Combine two elements. Must be associative, with `empty()` as the identity.
+    #[must_use]
+    fn combine(self, other: Self) -> Self;
+}
+impl Monoid for () {
+    fn empty() -> Self {}
+    fn combine(self, _other: Self) -> Self {}
+}
+impl <A: Monoid, B: Monoid> Monoid for (A, B) {
+    fn empty() -> Self {
+        (A::empty(), B::empty())
+    }
+    fn combine(self, other: Self) -> Self {
+        (self.0.combine(other.0), self.1.combine(other.1))
+    }
+}
+
+/// `Reducer` combines the secondary returns of transform operations, taking ownership of both sides.
+/// 
+/// Any `Fn(&ReduceT, &ReduceT) -> ReduceT` is a `Reducer`, as is `MonoidReducer` for any `Monoid`. Reductions are seeded with `ReduceT::default()`. The reduction carried by a constant transform variant, such as `MapTransform::Removed(r)`, is contributed once per transform.
+pub trait Reducer<ReduceT>: Clone + Send + Sync {
+    /// Combine two reductions.
+    #[must_use]
+    fn reduce(&self, left: ReduceT, right: ReduceT) -> ReduceT;
+}
+impl <ReduceT, F: Fn(&ReduceT, &ReduceT) -> ReduceT + Clone + Send + Sync> Reducer<ReduceT> for F {
+    fn reduce(&self, left: ReduceT, right: ReduceT) -> ReduceT {
+        self(&left, &right)
+    }
+}
+
+/// `MonoidReducer` reduces with `Monoid::combine`, moving accumulators rather than cloning them.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MonoidReducer;
+impl <ReduceT: Monoid> Reducer<ReduceT> for MonoidReducer {
+    fn reduce(&self, left: ReduceT, right: ReduceT) -> ReduceT {
+        left.combine(right)
+    }
+}
+
+/// `Measure` summarizes entries as a `Monoid` cached by each node for its entire subtree.
+/// 
+/// The unit measure, `()`, caches nothing.
+pub trait Measure<K, V>: 'static {
+    /// The cached summary type.
+    type Summary: Monoid;
+    /// Summarize a single entry.
+    #[must_use]
+    fn measure(key: &K, value: &V) -> Self::Summary;
+    /// Summarize entries sharing a full hash value, given in no particular order. Defaults to combining them.
+    #[must_use]
+    fn measure_collisions<I: Iterator<Item = Self::Summary>>(summaries: I) -> Self::Summary {
+        summaries.fold(Self::Summary::empty(), Self::Summary::combine)
+    }
+    /// Summarize a branch from the summaries of its populated children, given in bit-index order. Defaults to combining them.
+    #[must_use]
+    fn measure_branch<I: Iterator<Item = (usize, Self::Summary)>>(children: I) -> Self::Summary {
+        children.fold(Self::Summary::empty(), |summary, (_index, child)| summary.combine(child))
+    }
+}
+impl <K, V> Measure<K, V> for () {
+    type Summary = ();
+    fn measure(_key: &K, _value: &V) -> Self::Summary {}
+}
@@ -15,8 +15,8 @@ pub enum MapJointTransform<ReduceT, F> {
     Removed(ReduceT),
 }
 
-impl <ReduceT: Clone, F: Clone> MapJointTransform<ReduceT, F> {
-    /// Call the joint transformation and return a result.
+impl <ReduceT: Default, F> MapJointTransform<ReduceT, F> {
+    /// Call the joint transformation and return a result. Constant variants return `ReduceT::default()`.
     pub fn call<K, V>(&self, k: &K, v: &V, l: &K, w: &V) -> MapJointTransformResult<V, ReduceT> where F: Fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT>
     {
         match self {
@@ -28,48 +28,67 @@ impl <ReduceT: Clone, F: Clone> MapJointTransform<ReduceT, F> {
                     f(k, v, l, w)
                 }
             },
-            Self::UnchangedLR(r) => MapJointTransformResult::UnchangedLR(r.clone()),
-            Self::UnchangedL(r) => MapJointTransformResult::UnchangedL(r.clone()),
-            Self::UnchangedR(r) => MapJointTransformResult::UnchangedR(r.clone()),
-            Self::Removed(r) => MapJointTransformResult::Removed(r.clone()),
+            Self::UnchangedLR(_) => MapJointTransformResult::UnchangedLR(ReduceT::default()),
+            Self::UnchangedL(_) => MapJointTransformResult::UnchangedL(ReduceT::default()),
+            Self::UnchangedR(_) => MapJointTransformResult::UnchangedR(ReduceT::default()),
+            Self::Removed(_) => MapJointTransformResult::Removed(ReduceT::default()),
         }
     }
 
+    pub(crate) fn fork(&self) -> Self where F: Clone {
+        match self {
+            Self::Generic(f, flipped) => Self::Generic(f.clone(), *flipped),
+            Self::UnchangedLR(_) => Self::UnchangedLR(ReduceT::default()),
+            Self::UnchangedL(_) => Self::UnchangedL(ReduceT::default()),
+            Self::UnchangedR(_) => Self::UnchangedR(ReduceT::default()),
+            Self::Removed(_) => Self::Removed(ReduceT::default()),
+        }
+    }
+
+    pub(crate) fn split_reduction(self) -> (Self, ReduceT) {
+        match self {
+            Self::Generic(f, flipped) => (Self::Generic(f, flipped), ReduceT::default()),
+            Self::UnchangedLR(r) => (Self::UnchangedLR(ReduceT::default()), r),
+            Self::UnchangedL(r) => (Self::UnchangedL(ReduceT::default()), r),
+            Self::UnchangedR(r) => (Self::UnchangedR(ReduceT::default()), r),
+            Self::Removed(r) => (Self::Removed(ReduceT::default()), r),
+        }
+    }
+}
+
+impl <ReduceT, F> MapJointTransform<ReduceT, F> {
     /// Flip the joint transformation so it can respond correctly to arguments in the reverse order.
-    pub fn flip(&self) -> Self
+    pub fn flip(self) -> Self
     {
         match self {
-            Self::Generic(f, flipped) => Self::Generic(f.clone(), !flipped),
-            Self::UnchangedLR(r) => Self::UnchangedLR(r.clone()),
-            Self::UnchangedL(r) => Self::UnchangedL(r.clone()),
-            Self::UnchangedR(r) => Self::UnchangedR(r.clone()),
-            Self::Removed(r) => Self::Removed(r.clone()),
+            Self::Generic(f, flipped) => Self::Generic(f, !flipped),
+            other => other,
         }
     }
 }
 
 /// Construct a generic joint transformation.
-pub fn new_map_joint_transform_generic<K, V, ReduceT: Clone, F: Fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT> + Clone>(f: F) -> MapJointTransform<ReduceT, F> {
+pub fn new_map_joint_transform_generic<K, V, ReduceT, F: Fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT> + Clone>(f: F) -> MapJointTransform<ReduceT, F> {
     MapJointTransform::Generic(f, false)
 }
 
 /// Construct a left/right indifferent identity joint transformation.
-pub fn new_map_joint_transform_unchangedlr<K, V, ReduceT: Clone>(r: ReduceT) -> MapJointTransform<ReduceT, fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT>> {
+pub fn new_map_joint_transform_unchangedlr<K, V, ReduceT>(r: ReduceT) -> MapJointTransform<ReduceT, fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT>> {
     MapJointTransform::UnchangedLR(r)
 }
 
 /// Construct a left-identity joint transformation.
-pub fn new_map_joint_transform_unchangedl<K, V, ReduceT: Clone>(r: ReduceT) -> MapJointTransform<ReduceT, fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT>> {
+pub fn new_map_joint_transform_unchangedl<K, V, ReduceT>(r: ReduceT) -> MapJointTransform<ReduceT, fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT>> {
     MapJointTransform::UnchangedL(r)
 }
 
 /// Construct a right-identity joint transformation.
-pub fn new_map_joint_transform_unchangedr<K, V, ReduceT: Clone>(r: ReduceT) -> MapJointTransform<ReduceT, fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT>> {
+pub fn new_map_joint_transform_unchangedr<K, V, ReduceT>(r: ReduceT) -> MapJointTransform<ReduceT, fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT>> {
     MapJointTransform::UnchangedR(r)
 }
 
 /// Construct a null joint transformation.
-pub fn new_map_joint_transform_removed<K, V, ReduceT: Clone>(r: ReduceT) -> MapJointTransform<ReduceT, fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT>> {
+pub fn new_map_joint_transform_removed<K, V, ReduceT>(r: ReduceT) -> MapJointTransform<ReduceT, fn(&K, &V, &K, &V) -> MapJointTransformResult<V, ReduceT>> {
     MapJointTransform::Removed(r)
 }
 
@@ -88,8 +107,8 @@ pub enum SetJointTransform<ReduceT, F> {
     Removed(ReduceT),
 }
 
-impl <ReduceT: Clone, F: Clone> SetJointTransform<ReduceT, F> {
-    /// Call the joint transformation and return a result.
+impl <ReduceT: Default, F> SetJointTransform<ReduceT, F> {
+    /// Call the joint transformation and return a result. Constant variants return `ReduceT::default()`.
     pub fn call<K>(&self, k: &K, l: &K) -> SetJointTransformResult<ReduceT> where F: Fn(&K, &K) -> SetJointTransformResult<ReduceT>
     {
         match self {
@@ -101,48 +120,47 @@ impl <ReduceT: Clone, F: Clone> SetJointTransform<ReduceT, F> {
                     f(k, l)
                 }
             },
-            Self::UnchangedLR(r) => SetJointTransformResult::UnchangedLR(r.clone()),
-            Self::UnchangedL(r) => SetJointTransformResult::UnchangedL(r.clone()),
-            Self::UnchangedR(r) => SetJointTransformResult::UnchangedR(r.clone()),
-            Self::Removed(r) => SetJointTransformResult::Removed(r.clone()),
+            Self::UnchangedLR(_) => SetJointTransformResult::UnchangedLR(ReduceT::default()),
+            Self::UnchangedL(_) => SetJointTransformResult::UnchangedL(ReduceT::default()),
+            Self::UnchangedR(_) => SetJointTransformResult::UnchangedR(ReduceT::default()),
+            Self::Removed(_) => SetJointTransformResult::Removed(ReduceT::default()),
         }
     }
+}
 
+impl <ReduceT, F> SetJointTransform<ReduceT, F> {
     /// Flip the joint transformation so it can respond correctly to arguments in the reverse order.
-    pub fn flip(&self) -> Self
+    pub fn flip(self) -> Self
     {
         match self {
-            Self::Generic(f, flipped) => Self::Generic(f.clone(), !flipped),
-            Self::UnchangedLR(r) => Self::UnchangedLR(r.clone()),
-            Self::UnchangedL(r) => Self::UnchangedL(r.clone()),
-            Self::UnchangedR(r) => Self::UnchangedR(r.clone()),
-            Self::Removed(r) => Self::Removed(r.clone()),
+            Self::Generic(f, flipped) => Self::Generic(f, !flipped),
+            other => other,
         }
     }
 }
 
 /// Construct a generic joint transformation.
-pub fn new_set_joint_transform_generic<K, ReduceT: Clone, F: Fn(&K, &K) -> SetJointTransformResult<ReduceT> + Clone>(f: F) -> SetJointTransform<ReduceT, F> {
+pub fn new_set_joint_transform_generic<K, ReduceT, F: Fn(&K, &K) -> SetJointTransformResult<ReduceT> + Clone>(f: F) -> SetJointTransform<ReduceT, F> {
     SetJointTransform::Generic(f, false)
 }
 
 /// Construct a left/right indifferent identity joint transformation.
-pub fn new_set_joint_transform_unchangedlr<K, ReduceT: Clone>(r: ReduceT) -> SetJointTransform<ReduceT, fn(&K, &K) -> SetJointTransformResult<ReduceT>> {
+pub fn new_set_joint_transform_unchangedlr<K, ReduceT>(r: ReduceT) -> SetJointTransform<ReduceT, fn(&K, &K) -> SetJointTransformResult<ReduceT>> {
     SetJointTransform::UnchangedLR(r)
 }
 
 /// Construct a left-identity joint transformation.
-pub fn new_set_joint_transform_unchangedl<K, ReduceT: Clone>(r: ReduceT) -> SetJointTransform<ReduceT, fn(&K, &K) -> SetJointTransformResult<ReduceT>> {
+pub fn new_set_joint_transform_unchangedl<K, ReduceT>(r: ReduceT) -> SetJointTransform<ReduceT, fn(&K, &K) -> SetJointTransformResult<ReduceT>> {
     SetJointTransform::UnchangedL(r)
 }
 
 /// Construct a right-identity joint transformation.
-pub fn new_set_joint_transform_unchangedr<K, ReduceT: Clone>(r: ReduceT) -> SetJointTransform<ReduceT, fn(&K, &K) -> SetJointTransformResult<ReduceT>> {
+pub fn new_set_joint_transform_unchangedr<K, ReduceT>(r: ReduceT) -> SetJointTransform<ReduceT, fn(&K, &K) -> SetJointTransformResult<ReduceT>> {
     SetJointTransform::UnchangedR(r)
 }
 
 /// Construct a null joint transformation.
-pub fn new_set_joint_transform_removed<K, ReduceT: Clone>(r: ReduceT) -> SetJointTransform<ReduceT, fn(&K, &K) -> SetJointTransformResult<ReduceT>> {
+pub fn new_set_joint_transform_removed<K, ReduceT>(r: ReduceT) -> SetJointTransform<ReduceT, fn(&K, &K) -> SetJointTransformResult<ReduceT>> {
     SetJointTransform::Removed(r)
 }
 
@@ -157,110 +175,90 @@ pub enum MapTransform<ReduceT, F> {
     Removed(ReduceT),
 }
 
-impl <ReduceT: Clone, F> MapTransform<ReduceT, F> {
-    /// Call the transformation and return a result.
+impl <ReduceT: Default, F> MapTransform<ReduceT, F> {
+    /// Call the transformation and return a result. Constant variants return `ReduceT::default()`.
     pub fn call<K, V>(&self, k: &K, v: &V) -> MapTransformResult<V, ReduceT> where F: Fn(&K, &V) -> MapTransformResult<V, ReduceT>
     {
         match self {
             Self::Generic(f) => f(k, v),
-            Self::Unchanged(r) => MapTransformResult::Unchanged(r.clone()),
-            Self::Removed(r) => MapTransformResult::Removed(r.clone()),
+            Self::Unchanged(_) => MapTransformResult::Unchanged(ReduceT::default()),
+            Self::Removed(_) => MapTransformResult::Removed(ReduceT::default()),
         }
     }
 
-    /// Call the joint transformation/transmutation and return a result.
-    pub fn call_transform_transmute<K, V, L, W>(&self, k: &K, v: &V, l: &L, w: &W) -> MapTransformResult<V, ReduceT> where F: Fn(&K, &V, &L, &W) -> MapTransformResult<V, ReduceT>
+    /// Call the joint transformation/transmutation or transmutation/transformation and return a result. Constant variants return `ReduceT::default()`.
+    pub fn call_joint<K, V, L, W, X>(&self, k: &K, v: &V, l: &L, w: &W) -> MapTransformResult<X, ReduceT> where F: Fn(&K, &V, &L, &W) -> MapTransformResult<X, ReduceT>
     {
         match self {
             Self::Generic(f) => f(k, v, l, w),
-            Self::Unchanged(r) => MapTransformResult::Unchanged(r.clone()),
-            Self::Removed(r) => MapTransformResult::Removed(r.clone()),
+            Self::Unchanged(_) => MapTransformResult::Unchanged(ReduceT::default()),
+            Self::Removed(_) => MapTransformResult::Removed(ReduceT::default()),
         }
     }
 
-    /// Call the joint transmutation/transformation and return a result.
-    pub fn call_transmute_transform<K, V, L, W>(&self, k: &K, v: &V, l: &L, w: &W) -> MapTransformResult<W, ReduceT> where F: Fn(&K, &V, &L, &W) -> MapTransformResult<W, ReduceT>
-    {
+    pub(crate) fn fork(&self) -> Self where F: Clone {
         match self {
-            Self::Generic(f) => f(k, v, l, w),
-            Self::Unchanged(r) => MapTransformResult::Unchanged(r.clone()),
-            Self::Removed(r) => MapTransformResult::Removed(r.clone()),
+            Self::Generic(f) => Self::Generic(f.clone()),
+            Self::Unchanged(_) => Self::Unchanged(ReduceT::default()),
+            Self::Removed(_) => Self::Removed(ReduceT::default()),
         }
     }
 
-    /// Flip the joint transformation/transmutation so it can respond correctly to arguments in the reverse order.
-    pub fn flip_transform_transmute<K, V, L, W>(&self) -> MapTransform<ReduceT, impl Fn(&L, &W, &K, &V) -> MapTransformResult<V, ReduceT> + Clone>
-    where F: Fn(&K, &V, &L, &W) -> MapTransformResult<V, ReduceT> + Clone
-    {
+    pub(crate) fn split_reduction(self) -> (Self, ReduceT) {
         match self {
-            Self::Generic(f) => {
-                let g = f.clone();
-                MapTransform::Generic(move |l: &_, w: &_, k: &_, v: &_| g(k, v, l, w))
-            },
-            Self::Unchanged(r) => MapTransform::Unchanged(r.clone()),
-            Self::Removed(r) => MapTransform::Removed(r.clone()),
+            Self::Generic(f) => (Self::Generic(f), ReduceT::default()),
+            Self::Unchanged(r) => (Self::Unchanged(ReduceT::default()), r),
+            Self::Removed(r) => (Self::Removed(ReduceT::default()), r),
         }
     }
+}
 
-    /// Flip the joint transmutation/transformation so it can respond correctly to arguments in the reverse order.
-    pub fn flip_transmute_transform<K, V, L, W>(&self) -> MapTransform<ReduceT, impl Fn(&L, &W, &K, &V) -> MapTransformResult<W, ReduceT> + Clone>
-    where F: Fn(&K, &V, &L, &W) -> MapTransformResult<W, ReduceT> + Clone
+impl <ReduceT, F> MapTransform<ReduceT, F> {
+    /// Flip the joint transformation/transmutation or transmutation/transformation so it can respond correctly to arguments in the reverse order.
+    pub fn flip_joint<K, V, L, W, X>(self) -> MapTransform<ReduceT, impl Fn(&L, &W, &K, &V) -> MapTransformResult<X, ReduceT> + Clone>
+    where F: Fn(&K, &V, &L, &W) -> MapTransformResult<X, ReduceT> + Clone
     {
         match self {
-            Self::Generic(f) => {
-                let g = f.clone();
-                MapTransform::Generic(move |l: &_, w: &_, k: &_, v: &_| g(k, v, l, w))
-            },
-            Self::Unchanged(r) => MapTransform::Unchanged(r.clone()),
-            Self::Removed(r) => MapTransform::Removed(r.clone()),
+            Self::Generic(f) => MapTransform::Generic(move |l: &_, w: &_, k: &_, v: &_| f(k, v, l, w)),
+            Self::Unchanged(r) => MapTransform::Unchanged(r),
+            Self::Removed(r) => MapTransform::Removed(r),
         }
     }
 }
 
 /// Construct a generic transformation.
-pub fn new_map_transform_generic<K, V, ReduceT: Clone, F: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone>(f: F) -> MapTransform<ReduceT, F> {
+pub fn new_map_transform_generic<K, V, ReduceT, F: Fn(&K, &V) -> MapTransformResult<V, ReduceT> + Clone>(f: F) -> MapTransform<ReduceT, F> {
     MapTransform::Generic(f)
 }
 
-/// Construct an identity joint transformation.
-pub fn new_map_transform_unchanged<K, V, ReduceT: Clone>(r: ReduceT) -> MapTransform<ReduceT, fn(&K, &V) -> MapTransformResult<V, ReduceT>> {
+/// Construct an identity transformation.
+pub fn new_map_transform_unchanged<K, V, ReduceT>(r: ReduceT) -> MapTransform<ReduceT, fn(&K, &V) -> MapTransformResult<V, ReduceT>> {
     MapTransform::Unchanged(r)
 }
 
 /// Construct a null transformation.
-pub fn new_map_transform_removed<K, V, ReduceT: Clone>(r: ReduceT) -> MapTransform<ReduceT, fn(&K, &V) -> MapTransformResult<V, ReduceT>> {
+pub fn new_map_transform_removed<K, V, ReduceT>(r: ReduceT) -> MapTransform<ReduceT, fn(&K, &V) -> MapTransformResult<V, ReduceT>> {
     MapTransform::Removed(r)
 }
 
-/// Construct a generic joint transformation/transmutation.
-pub fn new_map_transform_transmute_generic<K, V, L, W, ReduceT: Clone, F: Fn(&K, &V, &L, &W) -> MapTransformResult<V, ReduceT> + Clone>(f: F) -> MapTransform<ReduceT, F> {
+/// Construct a generic joint transformation/transmutation, producing values of the left map, or transmutation/transformation, producing values of the right map.
+pub fn new_map_transform_transmute_generic<K, V, L, W, X, ReduceT, F: Fn(&K, &V, &L, &W) -> MapTransformResult<X, ReduceT> + Clone>(f: F) -> MapTransform<ReduceT, F> {
     MapTransform::Generic(f)
 }
 
-/// Construct a left-identity joint transformation/transmutation.
-pub fn new_map_transform_transmute_unchanged<K, V, L, W, ReduceT: Clone>(r: ReduceT) -> MapTransform<ReduceT, fn(&K, &V, &L, &W) -> MapTransformResult<V, ReduceT>> {
+/// Construct a joint transformation/transmutation or transmutation/transformation reusing the value of the transformed side.
+pub fn new_map_transform_transmute_unchanged<K, V, L, W, X, ReduceT>(r: ReduceT) -> MapTransform<ReduceT, fn(&K, &V, &L, &W) -> MapTransformResult<X, ReduceT>> {
     MapTransform::Unchanged(r)
 }
 
-/// Construct a null joint transformation/transmutation.
-pub fn new_map_transform_transmute_removed<K, V, L, W, ReduceT: Clone>(r: ReduceT) -> MapTransform<ReduceT, fn(&K, &V, &L, &W) -> MapTransformResult<V, ReduceT>> {
+/// Construct a null joint transformation/transmutation or transmutation/transformation.
+pub fn new_map_transform_transmute_removed<K, V, L, W, X, ReduceT>(r: ReduceT) -> MapTransform<ReduceT, fn(&K, &V, &L, &W) -> MapTransformResult<X, ReduceT>> {
     MapTransform::Removed(r)
 }
 
-/// Construct a generic joint transmutation/transformation.
-pub fn new_map_transmute_transform_generic<K, V, L, W, ReduceT: Clone, F: Fn(&K, &V, &L, &W) -> MapTransformResult<W, ReduceT> + Clone>(f: F) -> MapTransform<ReduceT, F> {
-    MapTransform::Generic(f)
-}
-
-/// Construct a right-identity joint transmutation/transformation.
-pub fn new_map_transmute_transform_unchanged<K, V, L, W, ReduceT: Clone>(r: ReduceT) -> MapTransform<ReduceT, fn(&K, &V, &L, &W) -> MapTransformResult<W, ReduceT>> {
-    MapTransform::Unchanged(r)
-}
-
-/// Construct a null joint transmutation/transformation.
-pub fn new_map_transmute_transform_removed<K, V, L, W, ReduceT: Clone>(r: ReduceT) -> MapTransform<ReduceT, fn(&K, &V, &L, &W) -> MapTransformResult<W, ReduceT>> {
-    MapTransform::Removed(r)
-}
+pub use self::new_map_transform_transmute_generic as new_map_transmute_transform_generic;
+pub use self::new_map_transform_transmute_unchanged as new_map_transmute_transform_unchanged;
+pub use self::new_map_transform_transmute_removed as new_map_transmute_transform_removed;
 
 /// `SetTransform` represents a transformation that can be run on a set.
 #[derive(Clone)]
@@ -273,69 +271,68 @@ pub enum SetTransform<ReduceT, F> {
     Removed(ReduceT),
 }
 
-impl <ReduceT: Clone, F> SetTransform<ReduceT, F> {
-    /// Call the transformation and return a result.
+impl <ReduceT: Default, F> SetTransform<ReduceT, F> {
+    /// Call the transformation and return a result. Constant variants return `ReduceT::default()`.
     pub fn call<K>(&self, k: &K) -> SetTransformResult<ReduceT> where F: Fn(&K) -> SetTransformResult<ReduceT>
     {
         match self {
             Self::Generic(f) => f(k),
-            Self::Unchanged(r) => SetTransformResult::Unchanged(r.clone()),
-            Self::Removed(r) => SetTransformResult::Removed(r.clone()),
+            Self::Unchanged(_) => SetTransformResult::Unchanged(ReduceT::default()),
+            Self::Removed(_) => SetTransformResult::Removed(ReduceT::default()),
         }
     }
 
-    /// Call the transmutation and return a result.
+    /// Call the transmutation and return a result. Constant variants return `ReduceT::default()`.
     pub fn call_transmute<K, L>(&self, k: &K, l: &L) -> SetTransformResult<ReduceT> where F: Fn(&K, &L) -> SetTransformResult<ReduceT>
     {
         match self {
             Self::Generic(f) => f(k, l),
-            Self::Unchanged(r) => SetTransformResult::Unchanged(r.clone()),
-            Self::Removed(r) => SetTransformResult::Removed(r.clone()),
+            Self::Unchanged(_) => SetTransformResult::Unchanged(ReduceT::default()),
+            Self::Removed(_) => SetTransformResult::Removed(ReduceT::default()),
         }
     }
+}
 
+impl <ReduceT, F> SetTransform<ReduceT, F> {
     /// Flip the joint transformation/transmutation so it can respond correctly to arguments in the reverse order.
-    pub fn flip_transmute<K, L>(&self) -> SetTransform<ReduceT, impl Fn(&L, &K) -> SetTransformResult<ReduceT> + Clone>
+    pub fn flip_transmute<K, L>(self) -> SetTransform<ReduceT, impl Fn(&L, &K) -> SetTransformResult<ReduceT> + Clone>
     where F: Fn(&K, &L) -> SetTransformResult<ReduceT> + Clone
     {
         match self {
-            Self::Generic(f) => {
-                let g = f.clone();
-                SetTransform::Generic(move |l: &_, k: &_| g(k, l))
-            },
-            Self::Unchanged(r) => SetTransform::Unchanged(r.clone()),
-            Self::Removed(r) => SetTransform::Removed(r.clone()),
+            Self::Generic(f) => SetTransform::Generic(move |l: &_, k: &_| f(k, l)),
+            Self::Unchanged(r) => SetTransform::Unchanged(r),
+            Self::Removed(r) => SetTransform::Removed(r),
         }
     }
 }
 
 /// Construct a generic transformation.
-pub fn new_set_transform_generic<K, ReduceT: Clone, F: Fn(&K) -> SetTransformResult<ReduceT> + Clone>(f: F) -> SetTransform<ReduceT, F> {
+pub fn new_set_transform_generic<K, ReduceT, F: Fn(&K) -> SetTransformResult<ReduceT> + Clone>(f: F) -> SetTransform<ReduceT, F> {
     SetTransform::Generic(f)
 }
 
 /// Construct an identity transformation.
-pub fn new_set_transform_unchanged<K, ReduceT: Clone>(r: ReduceT) -> SetTransform<ReduceT, fn(&K) -> SetTransformResult<ReduceT>> {
+pub fn new_set_transform_unchanged<K, ReduceT>(r: ReduceT) -> SetTransform<ReduceT, fn(&K) -> SetTransformResult<ReduceT>> {
     SetTransform::Unchanged(r)
 }
 
 /// Construct a null transformation.
-pub fn new_set_transform_removed<K, ReduceT: Clone>(r: ReduceT) -> SetTransform<ReduceT, fn(&K) -> SetTransformResult<ReduceT>> {
+pub fn new_set_transform_removed<K, ReduceT>(r: ReduceT) -> SetTransform<ReduceT, fn(&K) -> SetTransformResult<ReduceT>> {
     SetTransform::Removed(r)
 }
 
 /// Construct a generic joint transformation/transmutation.
-pub fn new_set_transform_transmute_generic<K, L, ReduceT: Clone, F: Fn(&K, &L) -> SetTransformResult<ReduceT> + Clone>(f: F) -> SetTransform<ReduceT, F> {
+pub fn new_set_transform_transmute_generic<K, L, ReduceT, F: Fn(&K, &L) -> SetTransformResult<ReduceT> + Clone>(f: F) -> SetTransform<ReduceT, F> {
     SetTransform::Generic(f)
 }
 
 /// Construct a left/right identity joint transformation/transmutation.
-pub fn new_set_transform_transmute_unchanged<K, L, ReduceT: Clone>(r: ReduceT) -> SetTransform<ReduceT, fn(&K, &L) -> SetTransformResult<ReduceT>> {
+pub fn new_set_transform_transmute_unchanged<K, L, ReduceT>(r: ReduceT) -> SetTransform<ReduceT, fn(&K, &L) -> SetTransformResult<ReduceT>> {
     SetTransform::Unchanged(r)
 }
 
-/// Construct a generic joint transformation/transmutation.
-pub fn new_set_transform_transmute_removed<K, L, ReduceT: Clone>(r: ReduceT) -> SetTransform<ReduceT, fn(&K, &L) -> SetTransformResult<ReduceT>> {
+/// Construct a null joint transformation/transmutation.
+pub fn new_set_transform_transmute_removed<K, L, ReduceT>(r: ReduceT) -> SetTransform<ReduceT, fn(&K, &L) -> SetTransformResult<ReduceT>> {
     SetTransform::Removed(r)
 }
 
@@ -348,56 +345,69 @@ pub enum MapTransmute<ReduceT, F> {
     Removed(ReduceT),
 }
 
-impl <ReduceT: Clone, F> MapTransmute<ReduceT, F> {
-    /// Call the transmutation and return a result.
+impl <ReduceT: Default, F> MapTransmute<ReduceT, F> {
+    /// Call the transmutation and return a result. `MapTransmute::Removed` returns `ReduceT::default()`.
     pub fn call<K, V, S, X>(&self, k: &K, v: &V) -> MapTransmuteResult<S, X, ReduceT> where F: Fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT>
     {
         match self {
             Self::Generic(f) => f(k, v),
-            Self::Removed(r) => MapTransmuteResult::Removed(r.clone()),
+            Self::Removed(_) => MapTransmuteResult::Removed(ReduceT::default()),
         }
     }
 
-    /// Call the joint transmutation and return a result.
+    /// Call the joint transmutation and return a result. `MapTransmute::Removed` returns `ReduceT::default()`.
     pub fn call_transmute<K, V, L, W, S, X>(&self, k: &K, v: &V, l: &L, w: &W) -> MapTransmuteResult<S, X, ReduceT> where F: Fn(&K, &V, &L, &W) -> MapTransmuteResult<S, X, ReduceT>
     {
         match self {
             Self::Generic(f) => f(k, v, l, w),
-            Self::Removed(r) => MapTransmuteResult::Removed(r.clone()),
+            Self::Removed(_) => MapTransmuteResult::Removed(ReduceT::default()),
+        }
+    }
+
+    pub(crate) fn fork(&self) -> Self where F: Clone {
+        match self {
+            Self::Generic(f) => Self::Generic(f.clone()),
+            Self::Removed(_) => Self::Removed(ReduceT::default()),
         }
     }
 
+    pub(crate) fn split_reduction(self) -> (Self, ReduceT) {
+        match self {
+            Self::Generic(f) => (Self::Generic(f), ReduceT::default()),
+            Self::Removed(r) => (Self::Removed(ReduceT::default()), r),
+        }
+    }
+}
+
+impl <ReduceT, F> MapTransmute<ReduceT, F> {
     /// Flip the joint transmutation so it can respond correctly to arguments in the reverse order.
-    pub fn flip<K, V, L, W, S, X>(&self) -> MapTransmute<ReduceT, impl Fn(&L, &W, &K, &V) -> MapTransmuteResult<S, X, ReduceT> + Clone>
+    pub fn flip<K, V, L, W, S, X>(self) -> MapTransmute<ReduceT, impl Fn(&L, &W, &K, &V) -> MapTransmuteResult<S, X, ReduceT> + Clone>
     where F: Fn(&K, &V, &L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone
     {
         match self {
-            Self::Generic(f) => {
-                let g = f.clone();
-                MapTransmute::Generic(move |l: &_, w: &_, k: &_, v: &_| g(k, v, l, w))
-            },
-            Self::Removed(r) => MapTransmute::Removed(r.clone()),
+            Self::Generic(f) => MapTransmute::Generic(move |l: &_, w: &_, k: &_, v: &_| f(k, v, l, w)),
+            Self::Removed(r) => MapTransmute::Removed(r),
         }
     }
 }
 
 /// Construct a generic transmutation.
-pub fn new_map_transmute_generic<K, V, S, X, ReduceT: Clone, F: Fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT> + Clone>(f: F) -> MapTransmute<ReduceT, F> {
+pub fn new_map_transmute_generic<K, V, S, X, ReduceT, F: Fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT> + Clone>(f: F) -> MapTransmute<ReduceT, F> {
     MapTransmute::Generic(f)
 }
 
 /// Construct a null transmutation.
-pub fn new_map_transmute_removed<K, V, S, X, ReduceT: Clone>(r: ReduceT) -> MapTransmute<ReduceT, fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT>> {
+pub fn new_map_transmute_removed<K, V, S, X, ReduceT>(r: ReduceT) -> MapTransmute<ReduceT, fn(&K, &V) -> MapTransmuteResult<S, X, ReduceT>> {
     MapTransmute::Removed(r)
 }
 
 /// Construct a generic joint transmutation.
-pub fn new_map_transmute_transmute_generic<K, V, L, W, S, X, ReduceT: Clone, F: Fn(&K, &V, &L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone>(f: F) -> MapTransmute<ReduceT, F> {
+pub fn new_map_transmute_transmute_generic<K, V, L, W, S, X, ReduceT, F: Fn(&K, &V, &L, &W) -> MapTransmuteResult<S, X, ReduceT> + Clone>(f: F) -> MapTransmute<ReduceT, F> {
     MapTransmute::Generic(f)
 }
 
 /// Construct a null joint transmutation.
-pub fn new_map_transmute_transmute_removed<K, V, L, W, S, X, ReduceT: Clone>(r: ReduceT) -> MapTransmute<ReduceT, fn(&K, &V, &L, &W) -> MapTransmuteResult<S, X, ReduceT>> {
+pub fn new_map_transmute_transmute_removed<K, V, L, W, S, X, ReduceT>(r: ReduceT) -> MapTransmute<ReduceT, fn(&K, &V, &L, &W) -> MapTransmuteResult<S, X, ReduceT>> {
     MapTransmute::Removed(r)
 }
 
@@ -410,55 +420,54 @@ pub enum SetTransmute<ReduceT, F> {
     Removed(ReduceT),
 }
 
-impl <ReduceT: Clone, F> SetTransmute<ReduceT, F> {
-    /// Call the transmutation and return a result.
+impl <ReduceT: Default, F> SetTransmute<ReduceT, F> {
+    /// Call the transmutation and return a result. `SetTransmute::Removed` returns `ReduceT::default()`.
     pub fn call<K, S>(&self, k: &K) -> SetTransmuteResult<S, ReduceT> where F: Fn(&K) -> SetTransmuteResult<S, ReduceT>
     {
         match self {
             Self::Generic(f) => f(k),
-            Self::Removed(r) => SetTransmuteResult::Removed(r.clone()),
+            Self::Removed(_) => SetTransmuteResult::Removed(ReduceT::default()),
         }
     }
 
-    /// Call the joint transmutation and return a result.
+    /// Call the joint transmutation and return a result. `SetTransmute::Removed` returns `ReduceT::default()`.
     pub fn call_transmute<K, L, S>(&self, k: &K, l: &L) -> SetTransmuteResult<S, ReduceT> where F: Fn(&K, &L) -> SetTransmuteResult<S, ReduceT>
     {
         match self {
             Self::Generic(f) => f(k, l),
-            Self::Removed(r) => SetTransmuteResult::Removed(r.clone()),
+            Self::Removed(_) => SetTransmuteResult::Removed(ReduceT::default()),
         }
     }
+}
 
+impl <ReduceT, F> SetTransmute<ReduceT, F> {
     /// Flip the joint transmutation so it can respond correctly to arguments in the reverse order.
-    pub fn flip<K, L, S>(&self) -> SetTransmute<ReduceT, impl Fn(&L, &K) -> SetTransmuteResult<S, ReduceT> + Clone>
+    pub fn flip<K, L, S>(self) -> SetTransmute<ReduceT, impl Fn(&L, &K) -> SetTransmuteResult<S, ReduceT> + Clone>
     where F: Fn(&K, &L) -> SetTransmuteResult<S, ReduceT> + Clone
     {
         match self {
-            Self::Generic(f) => {
-                let g = f.clone();
-                SetTransmute::Generic(move |l: &_, k: &_| g(k, l))
-            },
-            Self::Removed(r) => SetTransmute::Removed(r.clone()),
+            Self::Generic(f) => SetTransmute::Generic(move |l: &_, k: &_| f(k, l)),
+            Self::Removed(r) => SetTransmute::Removed(r),
         }
     }
 }
 
 /// Construct a generic transmutation.
-pub fn new_set_transmute_generic<K, S, ReduceT: Clone, F: Fn(&K) -> SetTransmuteResult<S, ReduceT> + Clone>(f: F) -> SetTransmute<ReduceT, F> {
+pub fn new_set_transmute_generic<K, S, ReduceT, F: Fn(&K) -> SetTransmuteResult<S, ReduceT> + Clone>(f: F) -> SetTransmute<ReduceT, F> {
     SetTransmute::Generic(f)
 }
 
 /// Construct a null transmutation.
-pub fn new_set_transmute_removed<K, S, ReduceT: Clone>(r: ReduceT) -> SetTransmute<ReduceT, fn(&K) -> SetTransmuteResult<S, ReduceT>> {
+pub fn new_set_transmute_removed<K, S, ReduceT>(r: ReduceT) -> SetTransmute<ReduceT, fn(&K) -> SetTransmuteResult<S, ReduceT>> {
     SetTransmute::Removed(r)
 }
 
 /// Construct a generic joint transmutation.
-pub fn new_set_transmute_transmute_generic<K, L, S, ReduceT: Clone, F: Fn(&K, &L) -> SetTransmuteResult<S, ReduceT> + Clone>(f: F) -> SetTransmute<ReduceT, F> {
+pub fn new_set_transmute_transmute_generic<K, L, S, ReduceT, F: Fn(&K, &L) -> SetTransmuteResult<S, ReduceT> + Clone>(f: F) -> SetTransmute<ReduceT, F> {
     SetTransmute::Generic(f)
 }
 
 /// Construct a null joint transmutation.
-pub fn new_set_transmute_transmute_removed<K, L, S, ReduceT: Clone>(r: ReduceT) -> SetTransmute<ReduceT, fn(&K, &L) -> SetTransmuteResult<S, ReduceT>> {
+pub fn new_set_transmute_transmute_removed<K, L, S, ReduceT>(r: ReduceT) -> SetTransmute<ReduceT, fn(&K, &L) -> SetTransmuteResult<S, ReduceT>> {
     SetTransmute::Removed(r)
 }
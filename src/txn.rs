@@ -0,0 +1,192 @@
+use crate::{atomic::{AtomicArc, Status, Watchers}, traits::*, AtomicHashTrieMap, HashTrieMap};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{any::Any, marker::PhantomData};
+
+/// `Txn` reads and writes several `AtomicHashTrieMap` cells, possibly of different types, and publishes all of its writes together.
+///
+/// Each cell is read once, so the transaction works against snapshots. `commit` succeeds only if every cell read is still the very map that was read, compared by identity.
+/// `commit` claims every cell accessed, in address order, checking each read as it goes, and then takes effect in all of them at once by deciding a status shared by the claims.
+/// Nothing waits for a claim: readers see the map it replaces until the commit is decided, and writers, including other commits, abort an undecided commit they run into, so `commit` then fails and `run` tries again.
+#[must_use]
+pub struct Txn<'a> {
+    accesses: Vec<Box<dyn Access>>,
+    cells: PhantomData<&'a ()>,
+}
+
+impl <'a> Txn<'a> {
+    /// Get a new, empty transaction.
+    pub fn new() -> Self {
+        Self {
+            accesses: Vec::new(),
+            cells: PhantomData,
+        }
+    }
+
+    /// Run `op` in a new transaction and commit it, retrying from scratch until the commit succeeds. Returns the result of the successful run.
+    pub fn run<R, Op: FnMut(&mut Txn<'a>) -> R>(mut op: Op) -> R {
+        loop {
+            let mut txn = Self::new();
+            let result = op(&mut txn);
+            if txn.commit() {
+                return result;
+            }
+        }
+    }
+
+    /// Get the map in `cell` as seen by this transaction: its pending write if any, or else the snapshot taken the first time it was read.
+    pub fn read<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>>(&mut self, cell: &'a AtomicHashTrieMap<H, F, K, V, M, A>) -> Arc<HashTrieMap<H, F, K, V, M, A>> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+        let access = self.access(&cell.map, &cell.watchers);
+        if let Some(write) = &access.write {
+            return write.clone();
+        }
+        access.read.get_or_insert_with(|| cell.load()).clone()
+    }
+
+    /// Stage `map` to replace the map in `cell` when the transaction commits.
+    pub fn write<H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>>(&mut self, cell: &'a AtomicHashTrieMap<H, F, K, V, M, A>, map: HashTrieMap<H, F, K, V, M, A>) where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+        self.access(&cell.map, &cell.watchers).write = Some(Arc::new(map));
+    }
+
+    /// Publish every staged write if no cell read has changed since. Returns whether the transaction committed.
+    #[must_use]
+    pub fn commit(mut self) -> bool {
+        self.accesses.sort_by_key(|access| access.cell() as usize);
+        let status = Arc::new(Status::new());
+        let claimed = self.accesses.iter().take_while(|access| access.claim(&status)).count();
+        let committed = claimed == self.accesses.len() && status.commit();
+        for access in &self.accesses[..claimed] {
+            access.unclaim(&status, committed);
+        }
+        committed
+    }
+
+    fn access<T: 'static>(&mut self, cell: &'a AtomicArc<T>, watchers: &'a Watchers) -> &mut CellAccess<T> {
+        let cell = cell as *const AtomicArc<T>;
+        let position = self.accesses.iter().position(|access| access.cell() == cell as *const ());
+        let position = position.unwrap_or_else(|| {
+            self.accesses.push(Box::new(CellAccess {cell, watchers, read: None, write: None}));
+            self.accesses.len() - 1
+        });
+        self.accesses[position].as_any_mut().downcast_mut().unwrap()
+    }
+}
+
+impl <'a> Default for Txn<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+trait Access {
+    fn cell(&self) -> *const ();
+    fn claim(&self, status: &Arc<Status>) -> bool;
+    fn unclaim(&self, status: &Arc<Status>, committed: bool);
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+struct CellAccess<T> {
+    cell: *const AtomicArc<T>,
+    watchers: *const Watchers,
+    read: Option<Arc<T>>,
+    write: Option<Arc<T>>,
+}
+
+impl <T: 'static> Access for CellAccess<T> {
+    fn cell(&self) -> *const () {
+        self.cell as *const ()
+    }
+
+    fn claim(&self, status: &Arc<Status>) -> bool {
+        // SAFETY: the transaction borrows each cell it accesses for its whole lifetime.
+        unsafe {&*self.cell}.claim(status, self.read.as_ref(), self.write.as_ref())
+    }
+
+    fn unclaim(&self, status: &Arc<Status>, committed: bool) {
+        // SAFETY: the transaction borrows each cell it accesses for its whole lifetime.
+        unsafe {&*self.cell}.unclaim(status);
+        if committed && self.write.is_some() {
+            // SAFETY: the watchers belong to the same cell.
+            unsafe {&*self.watchers}.notify();
+        }
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use alloc::sync::Arc;
+
+    #[test]
+    fn txn_transfers() {
+        let balances = Arc::new(AtomicHashTrieMap::<u64, u32, i32, i64, fnv::FnvHasher>::default());
+        let ledger = Arc::new(AtomicHashTrieMap::<u64, u32, usize, (i32, i32), fnv::FnvHasher>::default());
+        balances.store((0..4).fold(HashTrieMap::new(), |map, account| map.insert(account, 100, false).unwrap().0));
+
+        let threads: Vec<_> = (0..4).map(|t| {
+            let balances = balances.clone();
+            let ledger = ledger.clone();
+            std::thread::spawn(move || {
+                for i in 0..100 {
+                    Txn::run(|txn| {
+                        let map = txn.read(&balances);
+                        let (from, to) = (t, (t + 1) % 4);
+                        let debited = map.insert(from, map.find(&from).unwrap().1 - 1, true).unwrap().0;
+                        let credited = debited.insert(to, debited.find(&to).unwrap().1 + 1, true).unwrap().0;
+                        txn.write(&balances, credited);
+                        let entries = txn.read(&ledger);
+                        txn.write(&ledger, entries.insert(t as usize * 100 + i, (from, to), false).unwrap().0);
+                    });
+                }
+            })
+        }).collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        let map = balances.load();
+        for account in 0..4 {
+            assert_eq!(*map.find(&account).unwrap().1, 100);
+        }
+        assert_eq!(ledger.load().size(), 400);
+
+        let mut txn = Txn::new();
+        let snapshot = txn.read(&balances);
+        txn.write(&ledger, HashTrieMap::new());
+        balances.store(snapshot.remove(&0).unwrap().0);
+        assert!(!txn.commit());
+        assert_eq!(ledger.load().size(), 400);
+    }
+
+    #[test]
+    fn txn_with_direct_writes() {
+        let counters = Arc::new(AtomicHashTrieMap::<u64, u32, i32, i64, fnv::FnvHasher>::default());
+        counters.store(HashTrieMap::new().insert(0, 0, false).unwrap().0);
+        let increment = |map: &HashTrieMap<u64, u32, i32, i64, fnv::FnvHasher>| map.insert(0, map.find(&0).unwrap().1 + 1, true).unwrap().0;
+
+        let threads: Vec<_> = (0..4).map(|t| {
+            let counters = counters.clone();
+            std::thread::spawn(move || {
+                for _ in 0..250 {
+                    if t % 2 == 0 {
+                        Txn::run(|txn| {
+                            let map = txn.read(&counters);
+                            txn.write(&counters, increment(&map));
+                        });
+                    }
+                    else {
+                        counters.rcu(increment);
+                    }
+                }
+            })
+        }).collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        assert_eq!(*counters.load().find(&0).unwrap().1, 1000);
+    }
+}
@@ -0,0 +1,140 @@
+use crate::{results::*, traits::*, HashTrieMap};
+use alloc::collections::{BTreeSet, VecDeque};
+use core::ops::Range;
+
+/// `VersionedHashTrieMap` records a history of versions of a `HashTrieMap` for undo and redo.
+///
+/// Versions share every subtree they have in common, so retaining a version costs only the nodes that differ from its neighbours. Versions are numbered in the order they were committed, and the oldest are dropped once the history exceeds its limit, if any.
+#[must_use]
+pub struct VersionedHashTrieMap <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V> = ()> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    versions: VecDeque<HashTrieMap<H, F, K, V, M, A>>,
+    first: usize,
+    current: usize,
+    limit: Option<usize>,
+}
+
+impl <H: Hashword, F: Flagword<H>, K: Key, V: Value, M: HasherBv<H, K>, A: Measure<K, V>> VersionedHashTrieMap<H, F, K, V, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    /// Get a new VersionedHashTrieMap with `map` as version 0 and an unbounded history.
+    pub fn new(map: HashTrieMap<H, F, K, V, M, A>) -> Self {
+        Self {
+            versions: vec!(map).into(),
+            first: 0,
+            current: 0,
+            limit: None,
+        }
+    }
+
+    /// Get a new VersionedHashTrieMap with `map` as version 0, retaining at most `limit` versions.
+    pub fn with_limit(map: HashTrieMap<H, F, K, V, M, A>, limit: usize) -> Self {
+        assert!(limit > 0);
+        Self {
+            limit: Some(limit),
+            ..Self::new(map)
+        }
+    }
+
+    /// Get the current version of the map.
+    pub fn current(&self) -> &HashTrieMap<H, F, K, V, M, A> {
+        &self.versions[self.current]
+    }
+
+    /// Get the number of the current version.
+    #[must_use]
+    pub fn version(&self) -> usize {
+        self.first + self.current
+    }
+
+    /// Get the numbers of all retained versions.
+    #[must_use]
+    pub fn versions(&self) -> Range<usize> {
+        self.first..self.first + self.versions.len()
+    }
+
+    /// Make `map` the current version, discarding any versions that could have been redone. Returns the number of the new version.
+    pub fn commit(&mut self, map: HashTrieMap<H, F, K, V, M, A>) -> usize {
+        self.versions.truncate(self.current + 1);
+        self.versions.push_back(map);
+        if matches!(self.limit, Some(limit) if self.versions.len() > limit) {
+            self.versions.pop_front();
+            self.first += 1;
+        }
+        self.current = self.versions.len() - 1;
+        self.version()
+    }
+
+    /// Step back to the previous version, if retained, and return it.
+    pub fn undo(&mut self) -> Option<&HashTrieMap<H, F, K, V, M, A>> {
+        self.current = self.current.checked_sub(1)?;
+        Some(self.current())
+    }
+
+    /// Step forward to the next version, if any, and return it.
+    pub fn redo(&mut self) -> Option<&HashTrieMap<H, F, K, V, M, A>> {
+        if self.current + 1 == self.versions.len() {
+            return None;
+        }
+        self.current += 1;
+        Some(self.current())
+    }
+
+    /// Make the given retained version current without discarding any others, and return it.
+    pub fn checkout(&mut self, version: usize) -> Result<&HashTrieMap<H, F, K, V, M, A>, HashTrieError> {
+        if !self.versions().contains(&version) {
+            return Err(HashTrieError::NotFound);
+        }
+        self.current = version - self.first;
+        Ok(self.current())
+    }
+
+    /// Account for the nodes of all retained versions, counting each node shared between versions once.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let mut seen = BTreeSet::new();
+        let mut usage = MemoryUsage::default();
+        for map in &self.versions {
+            map.measure_memory(&mut seen, &mut usage);
+        }
+        usage
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{results::*, *};
+
+    #[test]
+    fn versioned_map_undo_redo() {
+        let mut versioned = VersionedHashTrieMap::new(DefaultHashTrieMap::<i32, i32>::new());
+        for i in 0..100 {
+            let map = versioned.current().insert(i, i, false).unwrap().0;
+            assert_eq!(versioned.commit(map), i as usize + 1);
+        }
+        let single = versioned.memory_usage();
+        let map = versioned.current().insert(100, 100, false).unwrap().0;
+        versioned.commit(map);
+        let both = versioned.memory_usage();
+        assert!(both.nodes > single.nodes && both.nodes < 2 * single.nodes);
+
+        assert_eq!(versioned.undo().unwrap().size(), 100);
+        assert_eq!(versioned.undo().unwrap().size(), 99);
+        assert_eq!(versioned.redo().unwrap().size(), 100);
+        assert_eq!(versioned.checkout(10).unwrap().size(), 10);
+        assert_eq!(versioned.checkout(102).err(), Some(HashTrieError::NotFound));
+        assert_eq!(versioned.versions(), 0..102);
+
+        let map = versioned.current().remove(&0).unwrap().0;
+        assert_eq!(versioned.commit(map), 11);
+        assert_eq!(versioned.versions(), 0..12);
+        assert!(versioned.redo().is_none());
+
+        let mut bounded = VersionedHashTrieMap::with_limit(DefaultHashTrieMap::<i32, i32>::new(), 3);
+        for i in 0..10 {
+            let map = bounded.current().insert(i, i, false).unwrap().0;
+            bounded.commit(map);
+        }
+        assert_eq!(bounded.versions(), 8..11);
+        assert_eq!(bounded.undo().unwrap().size(), 9);
+        assert_eq!(bounded.undo().unwrap().size(), 8);
+        assert!(bounded.undo().is_none());
+        assert!(bounded.checkout(6).is_err());
+    }
+}
@@ -0,0 +1,387 @@
+use crate::{builder::HashTrieMapBuilder, traits::*, HashTrieMap};
+use core::convert::{TryFrom, TryInto};
+use std::{fs::{self, File, OpenOptions}, io::{self, Read, Write}, path::{Path, PathBuf}, string::String, vec::Vec};
+
+const INSERT: u8 = 0;
+const REMOVE: u8 = 1;
+const CLEAR: u8 = 2;
+
+/// `LogCodec` encodes keys and values for a `WriteAheadLog`.
+pub trait LogCodec: Sized {
+    /// Append the encoding of `self` to `bytes`.
+    fn encode(&self, bytes: &mut Vec<u8>);
+    /// Consume an encoding from the front of `bytes`, or return `None` if it is malformed.
+    fn decode(bytes: &mut &[u8]) -> Option<Self>;
+}
+
+macro_rules! log_codec_impl {
+    ( $type:ty ) => {
+        impl LogCodec for $type {
+            fn encode(&self, bytes: &mut Vec<u8>) {
+                bytes.extend_from_slice(&self.to_le_bytes());
+            }
+
+            fn decode(bytes: &mut &[u8]) -> Option<Self> {
+                let size = core::mem::size_of::<$type>();
+                let word = bytes.get(..size)?.try_into().unwrap();
+                *bytes = &bytes[size..];
+                Some(<$type>::from_le_bytes(word))
+            }
+        }
+    };
+}
+log_codec_impl!(u8);
+log_codec_impl!(u16);
+log_codec_impl!(u32);
+log_codec_impl!(u64);
+log_codec_impl!(i8);
+log_codec_impl!(i16);
+log_codec_impl!(i32);
+log_codec_impl!(i64);
+
+impl LogCodec for () {
+    fn encode(&self, _bytes: &mut Vec<u8>) {}
+
+    fn decode(_bytes: &mut &[u8]) -> Option<Self> {
+        Some(())
+    }
+}
+
+impl LogCodec for String {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        (self.len() as u64).encode(bytes);
+        bytes.extend_from_slice(self.as_bytes());
+    }
+
+    fn decode(bytes: &mut &[u8]) -> Option<Self> {
+        let len = u64::decode(bytes)? as usize;
+        let string = String::from_utf8(bytes.get(..len)?.to_vec()).ok()?;
+        *bytes = &bytes[len..];
+        Some(string)
+    }
+}
+
+/// `WriteAheadLog` appends the insertions and removals made to a `HashTrieMap` to a file or any other `Write` sink, so that `replay` can rebuild the map.
+///
+/// Each record is a tag, a little-endian `u32` payload length, the payload and a little-endian CRC-32 of all three. `compact` rewrites the log as a snapshot of the current map.
+/// 
+/// `LoggedHashTrieMap` pairs a log with the map it describes, logging each change before applying it.
+pub struct WriteAheadLog<W: Write> {
+    sink: W,
+    path: Option<PathBuf>,
+    records: usize,
+}
+
+impl <W: Write> WriteAheadLog<W> {
+    /// Get a new WriteAheadLog appending to `sink`.
+    pub fn new(sink: W) -> Self {
+        Self {
+            sink,
+            path: None,
+            records: 0,
+        }
+    }
+
+    /// Get the number of records appended since the log was opened or last compacted, to decide when to compact.
+    #[must_use]
+    pub fn records(&self) -> usize {
+        self.records
+    }
+
+    /// Append the insertion or replacement of an entry.
+    pub fn log_insert<K: LogCodec, V: LogCodec>(&mut self, key: &K, value: &V) -> io::Result<()> {
+        let mut payload = Vec::new();
+        key.encode(&mut payload);
+        value.encode(&mut payload);
+        self.append(INSERT, &payload)
+    }
+
+    /// Append the removal of an entry.
+    pub fn log_remove<K: LogCodec>(&mut self, key: &K) -> io::Result<()> {
+        let mut payload = Vec::new();
+        key.encode(&mut payload);
+        self.append(REMOVE, &payload)
+    }
+
+    /// Flush the underlying sink.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.sink.flush()
+    }
+
+    /// Write a snapshot of `map` to `sink` and continue the log there. Returns the previous sink, whose records are superseded.
+    pub fn compact<H: Hashword, F: Flagword<H>, K: Key + LogCodec, V: Value + LogCodec, M: HasherBv<H, K>, A: Measure<K, V>>(&mut self, map: &HashTrieMap<H, F, K, V, M, A>, sink: W) -> io::Result<W> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+        let mut compacted = Self::new(sink);
+        compacted.append(CLEAR, &[])?;
+        for (key, value) in map.entries() {
+            compacted.log_insert(key, value)?;
+        }
+        compacted.flush()?;
+        self.records = 0;
+        Ok(core::mem::replace(&mut self.sink, compacted.sink))
+    }
+
+    fn append(&mut self, tag: u8, payload: &[u8]) -> io::Result<()> {
+        let len = u32::try_from(payload.len()).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "log record payload exceeds u32::MAX bytes"))?;
+        let mut record = Vec::with_capacity(9 + payload.len());
+        record.push(tag);
+        record.extend_from_slice(&len.to_le_bytes());
+        record.extend_from_slice(payload);
+        record.extend_from_slice(&crc32(&record).to_le_bytes());
+        self.sink.write_all(&record)?;
+        self.records += 1;
+        Ok(())
+    }
+}
+
+impl WriteAheadLog<File> {
+    /// Open the log file at `path` for appending, creating it if needed.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut log = Self::new(OpenOptions::new().create(true).append(true).open(path.as_ref())?);
+        log.path = Some(path.as_ref().to_path_buf());
+        Ok(log)
+    }
+
+    /// Replace the log file with a snapshot of `map`, written beside it and then renamed over it, syncing the directory so the rename survives a crash.
+    pub fn compact_file<H: Hashword, F: Flagword<H>, K: Key + LogCodec, V: Value + LogCodec, M: HasherBv<H, K>, A: Measure<K, V>>(&mut self, map: &HashTrieMap<H, F, K, V, M, A>) -> io::Result<()> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+        let path = self.path.clone().ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "log was not opened from a path"))?;
+        let mut temp = path.clone().into_os_string();
+        temp.push(".compact");
+        self.compact(map, File::create(&temp)?)?;
+        self.sink.sync_all()?;
+        fs::rename(&temp, &path)?;
+        sync_parent(&path)?;
+        self.sink = OpenOptions::new().append(true).open(&path)?;
+        Ok(())
+    }
+}
+
+/// Rebuild a map from the records of a `WriteAheadLog`, batching them through a `HashTrieMapBuilder`.
+/// 
+/// Replay stops at the first record that is cut short or fails its checksum, as left by a crash mid-append, and ignores everything after it.
+pub fn replay<R: Read, H: Hashword, F: Flagword<H>, K: Key + LogCodec, V: Value + LogCodec, M: HasherBv<H, K>, A: Measure<K, V>>(mut source: R) -> io::Result<HashTrieMap<H, F, K, V, M, A>> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    let mut bytes = Vec::new();
+    source.read_to_end(&mut bytes)?;
+    replay_prefix(&bytes).map(|(map, _len)| map)
+}
+
+// Replay the longest prefix of whole, intact records, returning the map and the length of that prefix.
+#[allow(clippy::type_complexity)]
+fn replay_prefix<H: Hashword, F: Flagword<H>, K: Key + LogCodec, V: Value + LogCodec, M: HasherBv<H, K>, A: Measure<K, V>>(log: &[u8]) -> io::Result<(HashTrieMap<H, F, K, V, M, A>, usize)> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    let invalid = || io::Error::new(io::ErrorKind::InvalidData, "malformed log record");
+    let mut bytes = log;
+
+    let mut builder = HashTrieMapBuilder::new();
+    while bytes.len() >= 5 {
+        let len = u32::from_le_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]) as usize;
+        let (record, mut payload, checksum) = match (bytes.get(..5 + len), bytes.get(5..5 + len), bytes.get(5 + len..9 + len)) {
+            (Some(record), Some(payload), Some(checksum)) => (record, payload, checksum),
+            _ => break,
+        };
+        if crc32(record).to_le_bytes() != checksum {
+            break;
+        }
+        match bytes[0] {
+            INSERT => {
+                let key = K::decode(&mut payload).ok_or_else(invalid)?;
+                let value = V::decode(&mut payload).ok_or_else(invalid)?;
+                builder.insert(key, value);
+            },
+            REMOVE => {
+                builder.remove(K::decode(&mut payload).ok_or_else(invalid)?);
+            },
+            CLEAR => {
+                builder.clear();
+            },
+            _ => return Err(invalid()),
+        }
+        if !payload.is_empty() {
+            return Err(invalid());
+        }
+        bytes = &bytes[9 + len..];
+    }
+    Ok((builder.build(), log.len() - bytes.len()))
+}
+
+/// `LoggedHashTrieMap` holds a `HashTrieMap` together with the `WriteAheadLog` recording it, appending each change to the log before applying it to the map.
+#[must_use]
+pub struct LoggedHashTrieMap <W: Write, H: Hashword, F: Flagword<H>, K: Key + LogCodec, V: Value + LogCodec, M: HasherBv<H, K>, A: Measure<K, V> = ()> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    map: HashTrieMap<H, F, K, V, M, A>,
+    log: WriteAheadLog<W>,
+}
+
+impl <W: Write, H: Hashword, F: Flagword<H>, K: Key + LogCodec, V: Value + LogCodec, M: HasherBv<H, K>, A: Measure<K, V>> LoggedHashTrieMap<W, H, F, K, V, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    /// Get a new LoggedHashTrieMap applying changes to `map` and logging them to `log`, which must already describe `map`.
+    pub fn new(map: HashTrieMap<H, F, K, V, M, A>, log: WriteAheadLog<W>) -> Self {
+        Self {map, log}
+    }
+
+    /// Get the current map.
+    pub fn map(&self) -> &HashTrieMap<H, F, K, V, M, A> {
+        &self.map
+    }
+
+    /// Get the log, to flush it or to check how many records it holds.
+    #[must_use]
+    pub fn log(&mut self) -> &mut WriteAheadLog<W> {
+        &mut self.log
+    }
+
+    /// Split into the map and its log.
+    #[allow(clippy::type_complexity)]
+    pub fn into_parts(self) -> (HashTrieMap<H, F, K, V, M, A>, WriteAheadLog<W>) {
+        (self.map, self.log)
+    }
+
+    /// Log and then apply the insertion or replacement of an entry. The map is unchanged if logging fails.
+    pub fn insert(&mut self, key: K, value: V) -> io::Result<()> {
+        self.log.log_insert(&key, &value)?;
+        self.map = self.map.insert_many(core::iter::once((key, value)));
+        Ok(())
+    }
+
+    /// Log and then apply the removal of an entry, if present. Returns whether an entry was removed. The map is unchanged if logging fails.
+    pub fn remove(&mut self, key: &K) -> io::Result<bool> {
+        let removed = match self.map.remove(key) {
+            Ok((removed, _key, _value)) => removed,
+            Err(_) => return Ok(false),
+        };
+        self.log.log_remove(key)?;
+        self.map = removed;
+        Ok(true)
+    }
+
+    /// Write a snapshot of the map to `sink` and continue the log there. Returns the previous sink, whose records are superseded.
+    pub fn compact(&mut self, sink: W) -> io::Result<W> {
+        self.log.compact(&self.map, sink)
+    }
+}
+
+impl <H: Hashword, F: Flagword<H>, K: Key + LogCodec, V: Value + LogCodec, M: HasherBv<H, K>, A: Measure<K, V>> LoggedHashTrieMap<File, H, F, K, V, M, A> where <F as core::convert::TryFrom<<H as core::ops::BitAnd>::Output>>::Error: core::fmt::Debug {
+    /// Replay the log file at `path`, creating it if needed, and continue logging to it. A damaged tail left by a crash is truncated away so that new records follow the last intact one.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut file = OpenOptions::new().create(true).read(true).write(true).truncate(false).open(path.as_ref())?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+        let (map, len) = replay_prefix(&bytes)?;
+        if len < bytes.len() {
+            file.set_len(len as u64)?;
+            file.sync_all()?;
+        }
+        Ok(Self {map, log: WriteAheadLog::open(path)?})
+    }
+
+    /// Replace the log file with a snapshot of the map, as `WriteAheadLog::compact_file` does.
+    pub fn compact_file(&mut self) -> io::Result<()> {
+        self.log.compact_file(&self.map)
+    }
+}
+
+fn sync_parent(path: &Path) -> io::Result<()> {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => File::open(parent)?.sync_all(),
+        _ => File::open(".")?.sync_all(),
+    }
+}
+
+// The CRC-32 used by zlib and PNG, computed bitwise.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0_u32;
+    for byte in bytes {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xedb88320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{wal::*, *};
+
+    #[test]
+    fn wal_replay_compact() {
+        let mut log = WriteAheadLog::new(Vec::new());
+        let mut map = DefaultHashTrieMap::<i32, String>::new();
+        for i in 0..100 {
+            map = map.insert(i, i.to_string(), false).unwrap().0;
+            log.log_insert(&i, &i.to_string()).unwrap();
+        }
+        for i in (0..100).step_by(3) {
+            map = map.remove(&i).unwrap().0;
+            log.log_remove(&i).unwrap();
+        }
+        map = map.insert(1, String::from("one"), true).unwrap().0;
+        log.log_insert(&1, &String::from("one")).unwrap();
+        assert_eq!(log.records(), 135);
+
+        let replayed: DefaultHashTrieMap<i32, String> = replay(log.sink.as_slice()).unwrap();
+        assert_eq!(replayed.size(), map.size());
+        map.visit(|k, v| assert_eq!(replayed.find(k).unwrap().1, v));
+
+        let full = log.compact(&map, Vec::new()).unwrap();
+        assert_eq!(log.records(), 0);
+        assert!(log.sink.len() < full.len());
+        log.log_remove(&1).unwrap();
+        let mut torn = log.sink.clone();
+        torn.extend_from_slice(&[0, 100, 0]);
+        let replayed: DefaultHashTrieMap<i32, String> = replay(torn.as_slice()).unwrap();
+        assert_eq!(replayed.size(), map.size() - 1);
+        assert!(replayed.find(&1).is_err());
+
+        let mut corrupt = log.sink.clone();
+        let last = corrupt.len() - 9;
+        corrupt[last + 5] ^= 1;
+        let replayed: DefaultHashTrieMap<i32, String> = replay(corrupt.as_slice()).unwrap();
+        assert_eq!(replayed.size(), map.size());
+        assert!(replayed.find(&1).is_ok());
+
+        let mut unknown = vec![7, 0, 0, 0, 0];
+        unknown.extend_from_slice(&crc32(&unknown).to_le_bytes());
+        assert!(replay::<_, u64, u32, i32, String, fnv::FnvHasher, ()>(unknown.as_slice()).is_err());
+
+        let path = std::env::temp_dir().join(format!("hash-trie-wal-{}.log", std::process::id()));
+        let mut file_log = WriteAheadLog::open(&path).unwrap();
+        for i in 0..10 {
+            file_log.log_insert(&i, &i).unwrap();
+        }
+        let small = (0..5).fold(DefaultHashTrieMap::<i32, i32>::new(), |map, i| map.insert(i, i, false).unwrap().0);
+        file_log.compact_file(&small).unwrap();
+        file_log.log_insert(&7, &7).unwrap();
+        file_log.flush().unwrap();
+        let replayed: DefaultHashTrieMap<i32, i32> = replay(File::open(&path).unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(replayed.size(), 6);
+        assert!(replayed.find(&7).is_ok() && replayed.find(&5).is_err());
+    }
+
+    #[test]
+    fn wal_logged_map() {
+        type LoggedMap = LoggedHashTrieMap<File, u64, u32, i32, i32, fnv::FnvHasher>;
+        let path = std::env::temp_dir().join(format!("hash-trie-logged-{}.log", std::process::id()));
+        let mut logged = LoggedMap::open(&path).unwrap();
+        for i in 0..10 {
+            logged.insert(i, i).unwrap();
+        }
+        assert!(logged.remove(&3).unwrap());
+        assert!(!logged.remove(&3).unwrap());
+        assert_eq!(logged.log().records(), 11);
+        logged.log().flush().unwrap();
+        drop(logged);
+
+        OpenOptions::new().append(true).open(&path).unwrap().write_all(&[0, 8, 0, 0, 0, 1, 2]).unwrap();
+        let mut logged = LoggedMap::open(&path).unwrap();
+        assert_eq!(logged.map().size(), 9);
+        logged.insert(3, 30).unwrap();
+        logged.compact_file().unwrap();
+        logged.insert(10, 10).unwrap();
+        let (map, mut log) = logged.into_parts();
+        log.flush().unwrap();
+
+        let reopened = LoggedMap::open(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(reopened.map().size(), 11);
+        assert_eq!(*reopened.map().find(&3).unwrap().1, 30);
+        map.visit(|k, v| assert_eq!(reopened.map().find(k).unwrap().1, v));
+    }
+}